@@ -0,0 +1,32 @@
+use crate::ExternalError;
+use wolfram_wxf::{ToWolfram, WolframValue};
+
+/// The kernel side of the protocol, to drive a [`Session`](crate::Session) without Mathematica
+pub struct Client {
+    socket: zmq::Socket,
+}
+
+impl Client {
+    /// Connect to the endpoint the session printed
+    pub fn connect(endpoint: &str) -> Result<Self, ExternalError> {
+        let socket = zmq::Context::new().socket(zmq::PAIR)?;
+        socket.connect(endpoint)?;
+        Ok(Self { socket })
+    }
+    /// `<|"input" -> input, "args" -> args|>`
+    pub fn evaluate(&self, input: &str, args: Vec<WolframValue>) -> Result<WolframValue, ExternalError> {
+        let mut message = std::collections::BTreeMap::new();
+        message.insert("input", input.to_wolfram());
+        message.insert("args", WolframValue::list(args));
+        self.send(&message.to_wolfram())
+    }
+    /// Send any message and wait for the reply
+    pub fn send(&self, message: &WolframValue) -> Result<WolframValue, ExternalError> {
+        self.send_bytes(&message.try_to_bytes()?)
+    }
+    /// Send raw bytes, the session answers even if they are not WXF
+    pub fn send_bytes(&self, message: &[u8]) -> Result<WolframValue, ExternalError> {
+        self.socket.send(message, 0)?;
+        Ok(WolframValue::from_bytes(&self.socket.recv_bytes(0)?)?)
+    }
+}
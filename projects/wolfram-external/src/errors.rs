@@ -0,0 +1,66 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io,
+};
+use wolfram_wxf::{DecodeError, WXFError};
+
+/// Failure of the transport, evaluation errors are replies
+#[derive(Debug)]
+pub enum ExternalError {
+    Zmq(zmq::Error),
+    Decode(DecodeError),
+    Encode(WXFError),
+    /// Printing the endpoint failed
+    Io(io::Error),
+    /// Missing or invalid command line arguments
+    Arguments(String),
+}
+
+impl Display for ExternalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ExternalError::Zmq(e) => write!(f, "zmq error: {}", e),
+            ExternalError::Decode(e) => write!(f, "{}", e),
+            ExternalError::Encode(e) => write!(f, "{}", e),
+            ExternalError::Io(e) => write!(f, "io error: {}", e),
+            ExternalError::Arguments(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for ExternalError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExternalError::Zmq(e) => Some(e),
+            ExternalError::Decode(e) => Some(e),
+            ExternalError::Encode(e) => Some(e),
+            ExternalError::Io(e) => Some(e),
+            ExternalError::Arguments(_) => None,
+        }
+    }
+}
+
+impl From<zmq::Error> for ExternalError {
+    fn from(e: zmq::Error) -> Self {
+        ExternalError::Zmq(e)
+    }
+}
+
+impl From<io::Error> for ExternalError {
+    fn from(e: io::Error) -> Self {
+        ExternalError::Io(e)
+    }
+}
+
+impl From<DecodeError> for ExternalError {
+    fn from(e: DecodeError) -> Self {
+        ExternalError::Decode(e)
+    }
+}
+
+impl From<WXFError> for ExternalError {
+    fn from(e: WXFError) -> Self {
+        ExternalError::Encode(e)
+    }
+}
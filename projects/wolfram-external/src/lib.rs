@@ -0,0 +1,21 @@
+//! Rust functions as an `ExternalEvaluate` session
+//!
+//! The kernel starts the session with `--port <port>`, the session binds a ZeroMQ `PAIR` socket
+//! on `127.0.0.1` and prints its endpoint as the first line of output. The kernel connects to it,
+//! then sends one WXF association per evaluation:
+//!
+//! ```wl
+//! <|"input" -> "name", "args" -> {1, 2}, "return_type" -> "Expression"|>
+//! ```
+//!
+//! The reply is the WXF result, or a `Failure["RustError", <|...|>]`.
+
+mod client;
+mod errors;
+mod session;
+
+pub use self::{
+    client::Client,
+    errors::ExternalError,
+    session::{Evaluator, Session},
+};
@@ -0,0 +1,130 @@
+use crate::ExternalError;
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+use wolfram_wxf::{wl, ToWolfram, WolframValue};
+
+type Function = Box<dyn Fn(&[WolframValue]) -> Result<WolframValue, String> + Send>;
+
+/// Registered functions, by the name used as `"input"`
+#[derive(Default)]
+pub struct Evaluator {
+    functions: BTreeMap<String, Function>,
+}
+
+/// An [`Evaluator`] bound to a `PAIR` socket
+pub struct Session {
+    evaluator: Evaluator,
+    socket: zmq::Socket,
+    endpoint: String,
+}
+
+impl Evaluator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Make `f` callable with `<|"input" -> name, "args" -> {...}|>`, an `Err` becomes a `Failure` with the message
+    pub fn register<F, T>(&mut self, name: &str, f: F) -> &mut Self
+    where
+        F: Fn(&[WolframValue]) -> Result<T, String> + Send + 'static,
+        T: ToWolfram,
+    {
+        self.functions.insert(name.to_string(), Box::new(move |args| f(args).map(|v| v.to_wolfram())));
+        self
+    }
+    /// Answer one decoded message
+    pub fn evaluate(&self, message: &WolframValue) -> WolframValue {
+        let name = match message.get("input") {
+            Ok(WolframValue::String(s)) => s,
+            _ => return failure("InvalidMessage", "Message must be an association with a string \"input\"."),
+        };
+        let args = match message.get("args") {
+            Ok(list) if list.head() == WolframValue::symbol("List") => list.args(),
+            Ok(_) => return failure("InvalidMessage", "\"args\" must be a list."),
+            Err(_) => &[],
+        };
+        let function = match self.functions.get(name) {
+            Some(f) => f,
+            None => return failure("UnknownFunction", &format!("No function named {} is registered.", name)),
+        };
+        let result = match catch_unwind(AssertUnwindSafe(|| function(args))) {
+            Ok(Ok(value)) => value,
+            Ok(Err(e)) => return failure("EvaluationError", &e),
+            Err(e) => {
+                let message = e.downcast_ref::<&str>().map(|s| s.to_string()).or_else(|| e.downcast_ref::<String>().cloned());
+                return failure("Panic", &message.unwrap_or_else(|| "Function panicked.".to_string()));
+            }
+        };
+        match message.get("return_type") {
+            Ok(WolframValue::String(s)) if s == "String" => result.to_string().to_wolfram(),
+            _ => result,
+        }
+    }
+    /// Bytes of the reply to the bytes of a message, malformed input is answered with a `Failure`
+    pub fn reply(&self, message: &[u8]) -> Vec<u8> {
+        let result = match WolframValue::from_bytes(message) {
+            Ok(message) => self.evaluate(&message),
+            Err(e) => failure("InvalidMessage", &e.to_string()),
+        };
+        result.try_to_bytes().unwrap_or_else(|e| failure("InvalidResult", &e.to_string()).to_bytes())
+    }
+    /// Bind a `PAIR` socket, `tcp://127.0.0.1:*` picks a free port
+    pub fn bind(self, endpoint: &str) -> Result<Session, ExternalError> {
+        let socket = zmq::Context::new().socket(zmq::PAIR)?;
+        socket.bind(endpoint)?;
+        let endpoint = socket.get_last_endpoint()?.unwrap_or_else(|_| endpoint.to_string());
+        Ok(Session { evaluator: self, socket, endpoint })
+    }
+    /// Bind the port of the `--port` command line argument and serve until the socket fails
+    pub fn run(self) -> Result<(), ExternalError> {
+        self.run_with_args(std::env::args().skip(1))
+    }
+    /// The same as [`Evaluator::run`] with the arguments after the program name, `--port 0` picks a free port
+    pub fn run_with_args<I>(self, args: I) -> Result<(), ExternalError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter();
+        let port = loop {
+            match args.next().as_deref() {
+                Some("--port") => break args.next(),
+                Some(_) => continue,
+                None => return Err(ExternalError::Arguments("missing --port <port>".to_string())),
+            }
+        };
+        let port = match port.as_deref().map(str::parse::<u16>) {
+            Some(Ok(0)) => "*".to_string(),
+            Some(Ok(port)) => port.to_string(),
+            _ => return Err(ExternalError::Arguments(format!("invalid port {:?}", port.unwrap_or_default()))),
+        };
+        self.bind(&format!("tcp://127.0.0.1:{}", port))?.run()
+    }
+}
+
+impl Session {
+    /// Address with the actual port
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+    /// Wait for one message and reply
+    pub fn serve_one(&self) -> Result<(), ExternalError> {
+        let message = self.socket.recv_bytes(0)?;
+        self.socket.send(self.evaluator.reply(&message), 0)?;
+        Ok(())
+    }
+    /// Print the endpoint for the kernel to connect to, then serve until the socket fails
+    pub fn run(&self) -> Result<(), ExternalError> {
+        let mut out = std::io::stdout();
+        // the kernel waits for this line
+        writeln!(out, "{}", self.endpoint).and_then(|_| out.flush())?;
+        loop {
+            self.serve_one()?
+        }
+    }
+}
+
+fn failure(code: &str, message: &str) -> WolframValue {
+    wl!(Failure["RustError", <|"MessageTemplate" -> #message, "MessageParameters" -> <||>, "FailureCode" -> #code|>])
+}
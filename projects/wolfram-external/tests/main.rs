@@ -0,0 +1,65 @@
+use std::{sync::mpsc, thread};
+use wolfram_external::{Client, Evaluator, ExternalError};
+use wolfram_wxf::{wl, ToWolfram, WolframValue};
+
+fn evaluator() -> Evaluator {
+    let mut evaluator = Evaluator::new();
+    evaluator
+        .register("add", |args| {
+            args.iter().map(|v| v.to_string().parse::<i64>().map_err(|_| format!("{} is not an integer", v))).sum::<Result<i64, _>>()
+        })
+        .register("reverse", |args| Ok(args.iter().rev().cloned().collect::<Vec<_>>()))
+        .register("crash", |_| -> Result<i64, String> { panic!("crashed") });
+    evaluator
+}
+
+/// A session on its own thread serving `n` messages, the client connects like the kernel
+fn start(n: usize) -> (Client, thread::JoinHandle<()>) {
+    let (sender, receiver) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let session = evaluator().bind("tcp://127.0.0.1:*").unwrap();
+        sender.send(session.endpoint().to_string()).unwrap();
+        for _ in 0..n {
+            session.serve_one().unwrap()
+        }
+    });
+    (Client::connect(&receiver.recv().unwrap()).unwrap(), handle)
+}
+
+fn failure_code(value: &WolframValue) -> Option<String> {
+    match value.part(&[2]).ok()?.get("FailureCode").ok()? {
+        WolframValue::String(s) if value.head() == WolframValue::symbol("Failure") => Some(s.clone()),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_evaluate() {
+    let (client, handle) = start(3);
+    assert_eq!(client.evaluate("add", vec![1.to_wolfram(), 2.to_wolfram(), 39.to_wolfram()]).unwrap(), 42i64.to_wolfram());
+    assert_eq!(client.evaluate("reverse", vec![wl!(x), "y".to_wolfram()]).unwrap(), wl!({"y", x}));
+    let message = wl!(<|"input" -> "reverse", "args" -> {1, f[x]}, "return_type" -> "String"|>);
+    assert_eq!(client.send(&message).unwrap(), "{f[x],1}".to_wolfram());
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_failures() {
+    let (client, handle) = start(6);
+    assert_eq!(failure_code(&client.evaluate("missing", vec![]).unwrap()).as_deref(), Some("UnknownFunction"));
+    assert_eq!(failure_code(&client.evaluate("add", vec![wl!(x)]).unwrap()).as_deref(), Some("EvaluationError"));
+    assert_eq!(failure_code(&client.evaluate("crash", vec![]).unwrap()).as_deref(), Some("Panic"));
+    assert_eq!(failure_code(&client.send(&wl!({1, 2})).unwrap()).as_deref(), Some("InvalidMessage"));
+    assert_eq!(failure_code(&client.send_bytes(b"not wxf").unwrap()).as_deref(), Some("InvalidMessage"));
+    // the session keeps serving after failures
+    assert_eq!(client.evaluate("add", vec![]).unwrap(), 0i64.to_wolfram());
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_arguments() {
+    let run = |args: &[&str]| evaluator().run_with_args(args.iter().map(|s| s.to_string()));
+    assert!(matches!(run(&[]), Err(ExternalError::Arguments(_))));
+    assert!(matches!(run(&["--port"]), Err(ExternalError::Arguments(_))));
+    assert!(matches!(run(&["--port", "http"]), Err(ExternalError::Arguments(_))));
+}
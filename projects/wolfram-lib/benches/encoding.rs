@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use std::{collections::BTreeMap, io::sink};
+use wolfram_wxf::{CompressOptions, EncodeOptions, ToWolfram, WolframValue};
+
+/// 1000 rows of 100 mixed integers and reals
+fn nested_lists() -> WolframValue {
+    let row = |i: i32| -> Vec<WolframValue> {
+        (0..100).map(|j| if j % 2 == 0 { (i * j).to_wolfram() } else { (i as f64 / 7.0).to_wolfram() }).collect()
+    };
+    (0..1000).map(row).collect::<Vec<_>>().to_wolfram()
+}
+
+/// 10000 records keyed by string
+fn associations() -> WolframValue {
+    let records: BTreeMap<String, BTreeMap<&str, WolframValue>> = (0..10000)
+        .map(|i| {
+            let mut record = BTreeMap::new();
+            record.insert("id", i.to_wolfram());
+            record.insert("name", format!("user{}", i).to_wolfram());
+            record.insert("score", (i as f64).sqrt().to_wolfram());
+            (format!("key{}", i), record)
+        })
+        .collect();
+    records.to_wolfram()
+}
+
+fn bench_encoding(c: &mut Criterion) {
+    for (name, value) in [("nested_lists", nested_lists()), ("associations", associations())].iter() {
+        let options = EncodeOptions::default();
+        let len = value.encoded_len().unwrap();
+        let mut group = c.benchmark_group(*name);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_function("to_bytes", |b| b.iter(|| black_box(value).to_bytes()));
+        group.bench_function("write_to/presized", |b| {
+            b.iter_batched_ref(
+                || Vec::with_capacity(len),
+                |out| black_box(value).write_to(out, &options).unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_function("write_to/sink", |b| b.iter(|| black_box(value).write_to(&mut sink(), &options).unwrap()));
+        group.bench_function("encoded_len", |b| b.iter(|| black_box(value).encoded_len().unwrap()));
+        group.finish();
+    }
+}
+
+fn bench_compression(c: &mut Criterion) {
+    let value = nested_lists();
+    let mut group = c.benchmark_group("compression");
+    group.throughput(Throughput::Bytes(value.encoded_len().unwrap() as u64));
+    group.sample_size(20);
+    let parallel = CompressOptions { chunk_size: 1 << 17, ..CompressOptions::parallel() };
+    for (name, options) in [("fast", CompressOptions::fast()), ("best", CompressOptions::default()), ("parallel", parallel)].iter() {
+        group.bench_function(*name, |b| b.iter(|| black_box(&value).to_compressed_with(options)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_encoding, bench_compression);
+criterion_main!(benches);
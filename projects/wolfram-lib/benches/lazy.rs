@@ -0,0 +1,23 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::{collections::BTreeMap, fs::File, io::BufReader};
+use wolfram_wxf::{LazyReader, ToWolfram};
+
+/// 100000 string keys with small values, indexing is dominated by the keys
+fn bench_index(c: &mut Criterion) {
+    let dict: BTreeMap<String, i64> = (0..100000).map(|i| (format!("key{}", i), i)).collect();
+    let bytes = dict.to_wolfram().to_bytes();
+    let path = std::env::temp_dir().join("wolfram_wxf_bench_lazy.wxf");
+    std::fs::write(&path, &bytes).unwrap();
+    let mut group = c.benchmark_group("lazy");
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    group.sample_size(20);
+    group.bench_function("index/file", |b| b.iter(|| LazyReader::new(BufReader::new(File::open(&path).unwrap())).unwrap().length()));
+    group.bench_function("get/file", |b| {
+        b.iter(|| LazyReader::new(BufReader::new(File::open(&path).unwrap())).unwrap().get("key50000").unwrap())
+    });
+    group.finish();
+    std::fs::remove_file(&path).unwrap();
+}
+
+criterion_group!(benches, bench_index);
+criterion_main!(benches);
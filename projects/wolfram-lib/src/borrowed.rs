@@ -0,0 +1,99 @@
+use crate::{
+    utils::{array_to_owned, array_type, big_integer, normalized_symbol_name},
+    ToWolfram, WolframValue,
+};
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+
+/// Expression whose strings, symbols, byte arrays and array buffers borrow from the decoded input
+///
+/// Read with [`WolframValueRef::from_bytes`], so a memory-mapped file is never copied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WolframValueRef<'a> {
+    Skip,
+    /// Function with name, args
+    Function(Box<WolframValueRef<'a>>, Vec<WolframValueRef<'a>>),
+    String(&'a str),
+    Bytes(&'a [u8]),
+    /// Name as written, context included
+    Symbol(&'a str),
+    Integer8(i8),
+    Integer16(i16),
+    Integer32(i32),
+    Integer64(i64),
+    /// Decimal digits
+    BigInteger(&'a str),
+    Decimal64([u8; 8]),
+    BigDecimal(&'a str),
+    PackedArray(ArrayRef<'a>),
+    NumericArray(ArrayRef<'a>),
+    /// Entries in the order of the input, as `key -> (Rule, value)`
+    Association(Vec<(WolframValueRef<'a>, (WolframValueRef<'a>, WolframValueRef<'a>))>),
+    Rule,
+    RuleDelayed,
+}
+
+/// Element buffer of a packed or numeric array, not aligned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrayRef<'a> {
+    /// Type byte of the WXF spec, e.g. `0x23` for `Real64`
+    pub element_type: u8,
+    pub dimensions: Vec<usize>,
+    /// Little-endian elements in row-major order
+    pub data: &'a [u8],
+}
+
+impl<'a> ArrayRef<'a> {
+    /// `"Integer8"`, `"Real64"`, `"ComplexReal64"`...
+    pub fn type_name(&self) -> Option<&'static str> {
+        array_type(self.element_type).map(|(name, _)| name)
+    }
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.dimensions.iter().product()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<'a> WolframValueRef<'a> {
+    /// Copy into an owned value, the same as `WolframValue::from_bytes` on the input
+    pub fn to_owned(&self) -> WolframValue {
+        match self {
+            WolframValueRef::Skip => WolframValue::Skip,
+            WolframValueRef::Function(head, args) => {
+                WolframValue::Function(Box::new(head.as_ref().to_owned()), args.iter().map(|v| v.to_owned()).collect())
+            }
+            WolframValueRef::String(s) => WolframValue::String(s.to_string()),
+            WolframValueRef::Bytes(b) => WolframValue::Bytes(b.to_vec()),
+            WolframValueRef::Symbol(s) => WolframValue::Symbol(normalized_symbol_name(s)),
+            WolframValueRef::Integer8(n) => WolframValue::Integer8(*n),
+            WolframValueRef::Integer16(n) => WolframValue::Integer16(*n),
+            WolframValueRef::Integer32(n) => WolframValue::Integer32(*n),
+            WolframValueRef::Integer64(n) => WolframValue::Integer64(*n),
+            // the digits were checked while decoding
+            WolframValueRef::BigInteger(s) => big_integer(s).unwrap_or_else(|| WolframValue::String(s.to_string())),
+            WolframValueRef::Decimal64(d) => WolframValue::Decimal64(*d),
+            WolframValueRef::BigDecimal(s) => WolframValue::BigDecimal(s.to_string()),
+            WolframValueRef::PackedArray(a) => array_to_owned(false, a.element_type, &a.dimensions, a.data),
+            WolframValueRef::NumericArray(a) => array_to_owned(true, a.element_type, &a.dimensions, a.data),
+            WolframValueRef::Association(items) => {
+                WolframValue::Association(items.iter().map(|(k, (r, v))| (k.to_owned(), (r.to_owned(), v.to_owned()))).collect())
+            }
+            WolframValueRef::Rule => WolframValue::Rule,
+            WolframValueRef::RuleDelayed => WolframValue::RuleDelayed,
+        }
+    }
+}
+
+impl<'a> ToWolfram for WolframValueRef<'a> {
+    fn to_wolfram(&self) -> WolframValue {
+        self.to_owned()
+    }
+}
+
+impl<'a> From<WolframValueRef<'a>> for WolframValue {
+    fn from(value: WolframValueRef<'a>) -> Self {
+        value.to_owned()
+    }
+}
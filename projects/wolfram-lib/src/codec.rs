@@ -0,0 +1,107 @@
+use crate::{CompressOptions, DecodeError, DecodeLimit, DecodeLimits, EncodeOptions, WXFError, WolframValue};
+use bytes::{Buf, BufMut, BytesMut};
+use std::convert::TryFrom;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Bytes of the big-endian length before each payload
+const PREFIX: usize = 4;
+
+/// Frames of a `u32` big-endian length followed by an `8:` or `8C:` payload
+///
+/// The framing is the default of `LengthDelimitedCodec`, so either side may use that instead.
+#[derive(Debug, Clone)]
+pub struct WXFCodec {
+    encode: EncodeOptions,
+    compression: Option<CompressOptions>,
+    limits: DecodeLimits,
+    max_frame_length: usize,
+}
+
+impl Default for WXFCodec {
+    /// Uncompressed frames of at most 64 MiB
+    fn default() -> Self {
+        Self { encode: EncodeOptions::default(), compression: None, limits: DecodeLimits::default(), max_frame_length: 1 << 26 }
+    }
+}
+
+impl WXFCodec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_options(options: EncodeOptions) -> Self {
+        Self { encode: options, ..Self::default() }
+    }
+    /// Send `8C:` payloads, both kinds are always accepted
+    pub fn compressed(options: CompressOptions) -> Self {
+        Self { encode: options.encode, compression: Some(options), ..Self::default() }
+    }
+    /// Bounds for each received expression
+    pub fn set_limits(&mut self, limits: DecodeLimits) {
+        self.limits = limits
+    }
+    /// Longer frames are rejected before they are buffered
+    pub fn set_max_frame_length(&mut self, length: usize) {
+        self.max_frame_length = length
+    }
+}
+
+impl Decoder for WXFCodec {
+    type Item = WolframValue;
+    type Error = DecodeError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<WolframValue>, DecodeError> {
+        if src.len() < PREFIX {
+            return Ok(None);
+        }
+        let length = u32::from_be_bytes([src[0], src[1], src[2], src[3]]) as usize;
+        if length > self.max_frame_length {
+            return Err(DecodeError::LimitExceeded { limit: DecodeLimit::FrameLength, offset: 0 });
+        }
+        if src.len() < PREFIX + length {
+            src.reserve(PREFIX + length - src.len());
+            return Ok(None);
+        }
+        src.advance(PREFIX);
+        let payload = src.split_to(length);
+        WolframValue::from_bytes_with(&payload, &self.limits).map(Some)
+    }
+}
+
+impl Encoder<&WolframValue> for WXFCodec {
+    type Error = WXFError;
+
+    fn encode(&mut self, item: &WolframValue, dst: &mut BytesMut) -> Result<(), WXFError> {
+        let start = dst.len();
+        dst.put_u32(0);
+        let mut writer = (&mut *dst).writer();
+        let written = match &self.compression {
+            Some(options) => item.write_compressed_to(&mut writer, options),
+            None => item.write_to(&mut writer, &self.encode),
+        };
+        let length = dst.len() - start - PREFIX;
+        let max = self.max_frame_length.min(u32::MAX as usize);
+        let prefix = written.and_then(|_| match u32::try_from(length) {
+            Ok(n) if length <= max => Ok(n),
+            _ => Err(WXFError::FrameTooLong { length, max }),
+        });
+        match prefix {
+            Ok(n) => {
+                dst[start..start + PREFIX].copy_from_slice(&n.to_be_bytes());
+                Ok(())
+            }
+            Err(e) => {
+                // nothing of a failed frame is sent
+                dst.truncate(start);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Encoder<WolframValue> for WXFCodec {
+    type Error = WXFError;
+
+    fn encode(&mut self, item: WolframValue, dst: &mut BytesMut) -> Result<(), WXFError> {
+        Encoder::<&WolframValue>::encode(self, &item, dst)
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{
+    utils::{integer_to_value, normalized_symbol_name},
+    PartSpec, WolframValue,
+};
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::{self, Display, Formatter};
+
+/// One differing subexpression, `None` means the part is absent on that side
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difference {
+    pub path: Vec<PartSpec>,
+    pub left: Option<WolframValue>,
+    pub right: Option<WolframValue>,
+}
+
+/// All differences between two expressions
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diff {
+    pub differences: Vec<Difference>,
+}
+
+/// What [`WolframValue::diff_with`] treats as equal, the default compares values exactly
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffOptions {
+    /// `Integer8[1]` and `Integer64[1]` are the same integer
+    pub ignore_integer_width: bool,
+    /// `System`None` and `None` are the same symbol
+    pub ignore_symbol_context: bool,
+}
+
+impl DiffOptions {
+    /// Equal when Mathematica reads both as the same expression
+    pub fn loose() -> Self {
+        Self { ignore_integer_width: true, ignore_symbol_context: true }
+    }
+}
+
+impl Diff {
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+impl Display for Difference {
+    /// Sides that print the same, such as integers of different widths, are followed by their variant
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let path: Vec<String> = self.path.iter().map(|p| p.to_string()).collect();
+        write!(f, "{{{}}}: ", path.join(", "))?;
+        let (left, right) = (self.left.as_ref().map(|v| v.to_string()), self.right.as_ref().map(|v| v.to_string()));
+        let same = left.is_some() && left == right;
+        for (i, (side, value)) in [(left, &self.left), (right, &self.right)].iter().enumerate() {
+            if i == 1 {
+                write!(f, " => ")?;
+            }
+            match (side, value) {
+                (Some(s), Some(v)) if same => write!(f, "{} ({})", s, variant(v))?,
+                (Some(s), _) => write!(f, "{}", s)?,
+                _ => write!(f, "<absent>")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn variant(value: &WolframValue) -> &'static str {
+    match value {
+        WolframValue::Skip => "Skip",
+        WolframValue::Function(..) => "Function",
+        WolframValue::String(_) => "String",
+        WolframValue::Bytes(_) => "Bytes",
+        WolframValue::Symbol(_) => "Symbol",
+        WolframValue::Integer8(_) => "Integer8",
+        WolframValue::Integer16(_) => "Integer16",
+        WolframValue::Integer32(_) => "Integer32",
+        WolframValue::Integer64(_) => "Integer64",
+        #[cfg(feature = "bigint")]
+        WolframValue::BigInteger(_) => "BigInteger",
+        WolframValue::Decimal64(_) => "Decimal64",
+        WolframValue::BigDecimal(_) => "BigDecimal",
+        WolframValue::PackedArray(_) => "PackedArray",
+        WolframValue::NumericArray(_) => "NumericArray",
+        WolframValue::Association(_) => "Association",
+        WolframValue::Rule => "Rule",
+        WolframValue::RuleDelayed => "RuleDelayed",
+    }
+}
+
+impl Display for Diff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for d in &self.differences {
+            writeln!(f, "{}", d)?
+        }
+        Ok(())
+    }
+}
+
+impl WolframValue {
+    /// Compare two expressions part by part, like `Part` paths in `Position`
+    pub fn diff(&self, other: &WolframValue) -> Diff {
+        self.diff_with(other, &DiffOptions::default())
+    }
+    pub fn diff_with(&self, other: &WolframValue, options: &DiffOptions) -> Diff {
+        let mut out = Diff::default();
+        diff_at(&mut vec![], Some(self), Some(other), options, &mut out.differences);
+        out
+    }
+}
+
+fn diff_at(path: &mut Vec<PartSpec>, left: Option<&WolframValue>, right: Option<&WolframValue>, options: &DiffOptions, out: &mut Vec<Difference>) {
+    match (left, right) {
+        (Some(l), Some(r)) if l == r => {}
+        (Some(l), Some(r)) if options.ignore_integer_width && l.integer_value().is_some() && l.integer_value() == r.integer_value() => {}
+        (Some(WolframValue::Symbol(l)), Some(WolframValue::Symbol(r)))
+            if options.ignore_symbol_context && normalized_symbol_name(l) == normalized_symbol_name(r) => {}
+        (Some(WolframValue::Function(lh, la)), Some(WolframValue::Function(rh, ra))) if lh == rh || la.len() == ra.len() => {
+            diff_child(path, PartSpec::Index(0), Some(lh), Some(rh), options, out);
+            for i in 0..la.len().max(ra.len()) {
+                diff_child(path, PartSpec::Index(i as isize + 1), la.get(i), ra.get(i), options, out);
+            }
+        }
+        (Some(WolframValue::PackedArray(la)), Some(WolframValue::PackedArray(ra)))
+        | (Some(WolframValue::NumericArray(la)), Some(WolframValue::NumericArray(ra))) => {
+            for i in 0..la.len().max(ra.len()) {
+                diff_child(path, PartSpec::Index(i as isize + 1), la.get(i), ra.get(i), options, out);
+            }
+        }
+        (Some(WolframValue::Association(l)), Some(WolframValue::Association(r))) => {
+            // keys are matched by their normal form, which is the key itself for a strict diff
+            let mut pending: BTreeMap<WolframValue, &WolframValue> = r.keys().map(|k| (normalized(k, options), k)).collect();
+            for (k, (lr, lv)) in l {
+                let other = pending.remove(&normalized(k, options)).and_then(|o| r.get_key_value(o));
+                match other {
+                    Some((rk, (rr, rv))) if lr != rr => out.push(Difference {
+                        path: [path.as_slice(), &[PartSpec::Key(k.clone())]].concat(),
+                        left: Some(rule(lr, k, lv)),
+                        right: Some(rule(rr, rk, rv)),
+                    }),
+                    other => diff_child(path, PartSpec::Key(k.clone()), Some(lv), other.map(|(_, (_, v))| v), options, out),
+                }
+            }
+            for k in pending.into_values() {
+                diff_child(path, PartSpec::Key(k.clone()), None, r.get(k).map(|(_, v)| v), options, out);
+            }
+        }
+        _ => out.push(Difference { path: path.clone(), left: left.cloned(), right: right.cloned() }),
+    }
+}
+
+/// `Rule[key, value]` or `RuleDelayed[key, value]`, for entries that differ in the rule
+fn rule(rule: &WolframValue, key: &WolframValue, value: &WolframValue) -> WolframValue {
+    let head = if *rule == WolframValue::RuleDelayed { "RuleDelayed" } else { "Rule" };
+    WolframValue::Function(Box::new(WolframValue::symbol(head)), vec![key.clone(), value.clone()])
+}
+
+/// Representative of the values that `options` treat as equal
+fn normalized(value: &WolframValue, options: &DiffOptions) -> WolframValue {
+    match value {
+        WolframValue::Symbol(s) if options.ignore_symbol_context => WolframValue::Symbol(normalized_symbol_name(s)),
+        WolframValue::Function(head, args) if *options != DiffOptions::default() => {
+            WolframValue::Function(Box::new(normalized(head, options)), args.iter().map(|v| normalized(v, options)).collect())
+        }
+        WolframValue::Association(dict) if *options != DiffOptions::default() => {
+            WolframValue::Association(dict.iter().map(|(k, (r, v))| (normalized(k, options), (r.clone(), normalized(v, options)))).collect())
+        }
+        _ if options.ignore_integer_width => value.integer_value().map(integer_to_value).unwrap_or_else(|| value.clone()),
+        _ => value.clone(),
+    }
+}
+
+fn diff_child(
+    path: &mut Vec<PartSpec>,
+    part: PartSpec,
+    left: Option<&WolframValue>,
+    right: Option<&WolframValue>,
+    options: &DiffOptions,
+    out: &mut Vec<Difference>,
+) {
+    path.push(part);
+    diff_at(path, left, right, options, out);
+    path.pop();
+}
+
+/// Failure message of `assert_wolfram_eq!(wxf: ..)`, the bytes must match once `8C:` input is inflated
+#[doc(hidden)]
+pub fn wxf_mismatch(left: &[u8], right: &[u8]) -> Option<String> {
+    let (left, right) = (uncompressed(left), uncompressed(right));
+    if left == right {
+        return None;
+    }
+    let l = WolframValue::from_bytes(&left).expect("left side is not valid WXF");
+    let r = WolframValue::from_bytes(&right).expect("right side is not valid WXF");
+    let diff = l.diff(&r);
+    match diff.is_empty() {
+        true => Some(format!("{}: bytes {:?} => {:?}\n", l, left, right)),
+        false => Some(diff.to_string()),
+    }
+}
+
+#[cfg(feature = "compression")]
+fn uncompressed(input: &[u8]) -> Vec<u8> {
+    crate::utils::inflate(input, &crate::DecodeLimits::default()).expect("compressed side is not valid WXF")
+}
+
+#[cfg(not(feature = "compression"))]
+fn uncompressed(input: &[u8]) -> Vec<u8> {
+    input.to_vec()
+}
+
+/// Assert that two expressions are equal, printing the [`Diff`] otherwise
+///
+/// `wxf:` compares encoded bytes, `loose:` compares with [`DiffOptions::loose`].
+///
+/// ```
+/// # use wolfram_wxf::{assert_wolfram_eq, ToWolfram};
+/// assert_wolfram_eq!(vec![1, 2], vec![1, 2]);
+/// assert_wolfram_eq!(loose: vec![1i8], vec![1i64]);
+/// assert_wolfram_eq!(wxf: vec![1u8].to_wolfram_bytes(), vec![1u8].to_wolfram_solid());
+/// ```
+#[macro_export]
+macro_rules! assert_wolfram_eq {
+    (wxf: $left:expr, $right:expr $(,)?) => {{
+        if let Some(message) = $crate::diff::wxf_mismatch(&$left, &$right) {
+            panic!("assertion failed: `(left == right)`\n{}", message)
+        }
+    }};
+    (loose: $left:expr, $right:expr $(,)?) => {{
+        let options = $crate::diff::DiffOptions::loose();
+        let diff = $crate::ToWolfram::to_wolfram(&$left).diff_with(&$crate::ToWolfram::to_wolfram(&$right), &options);
+        if !diff.is_empty() {
+            panic!("assertion failed: `(left == right)`\n{}", diff)
+        }
+    }};
+    ($left:expr, $right:expr $(,)?) => {{
+        let diff = $crate::ToWolfram::to_wolfram(&$left).diff(&$crate::ToWolfram::to_wolfram(&$right));
+        if !diff.is_empty() {
+            panic!("assertion failed: `(left == right)`\n{}", diff)
+        }
+    }};
+}
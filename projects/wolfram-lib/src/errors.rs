@@ -0,0 +1,272 @@
+use crate::WolframValue;
+use alloc::string::String;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::{error::Error, io, sync::Arc};
+
+/// Failure of a `Part` or `Lookup` style access
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartError {
+    /// `Part[expr, index]` where `index` is outside `-length..=length`
+    OutOfRange { depth: usize, index: isize, length: usize },
+    /// `Lookup[assoc, key]` where `key` is not present
+    KeyAbsent { depth: usize, key: WolframValue },
+    /// The expression at `depth` is an atom and has no parts
+    Atomic { depth: usize, value: WolframValue },
+    /// A span such as `All` was used where a single subexpression is required
+    Span { depth: usize },
+    /// A position of `indices` indices into an array of `rank` dimensions
+    Rank { indices: usize, rank: usize },
+}
+
+impl Display for PartError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PartError::OutOfRange { depth, index, length } => {
+                write!(f, "part {} does not exist at depth {}, expression has length {}", index, depth, length)
+            }
+            PartError::KeyAbsent { depth, key } => write!(f, "key {} is absent at depth {}", key, depth),
+            PartError::Atomic { depth, value } => write!(f, "{} at depth {} is atomic and has no parts", value, depth),
+            PartError::Span { depth } => write!(f, "span at depth {} does not select a single subexpression", depth),
+            PartError::Rank { indices, rank } => write!(f, "position of {} indices into an array of rank {}", indices, rank),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PartError {}
+
+/// A value without the shape a [`FromWolfram`](crate::FromWolfram) type expects
+#[derive(Debug, Clone, PartialEq)]
+pub struct FromWolframError {
+    /// Description of the accepted values, e.g. `"Integer in i8 range"`
+    pub expected: &'static str,
+    pub found: WolframValue,
+}
+
+impl Display for FromWolframError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {}, found {}", self.expected, self.found)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for FromWolframError {}
+
+/// Failure while reading WXF bytes
+#[derive(Debug, Clone)]
+pub enum DecodeError {
+    /// Input does not start with `8:` or `8C:`
+    InvalidHeader,
+    /// `8C:` input cannot be borrowed, see `utils::inflate`
+    Compressed,
+    /// Input ended in the middle of a token
+    UnexpectedEnd { offset: usize },
+    /// Unknown token byte
+    InvalidToken { token: u8, offset: usize },
+    /// Unknown element type of a packed or numeric array
+    InvalidArrayType { token: u8, offset: usize },
+    /// String or symbol is not valid UTF-8
+    InvalidUtf8 { offset: usize },
+    /// Length or big integer digits could not be parsed
+    InvalidNumber { offset: usize },
+    /// Input continues after the expression
+    TrailingBytes { offset: usize },
+    /// The `8C:` payload could not be inflated
+    Decompress(String),
+    /// Input is larger than the configured `DecodeLimits`
+    LimitExceeded { limit: DecodeLimit, offset: usize },
+    /// Reading the underlying stream failed, shared so the error stays `Clone`
+    #[cfg(feature = "std")]
+    Io(Arc<io::Error>),
+}
+
+/// Io errors are equal only if they are the same error
+impl PartialEq for DecodeError {
+    fn eq(&self, other: &Self) -> bool {
+        use DecodeError::*;
+        match (self, other) {
+            (InvalidHeader, InvalidHeader) | (Compressed, Compressed) => true,
+            (UnexpectedEnd { offset: a }, UnexpectedEnd { offset: b }) => a == b,
+            (InvalidToken { token: a, offset: i }, InvalidToken { token: b, offset: j }) => a == b && i == j,
+            (InvalidArrayType { token: a, offset: i }, InvalidArrayType { token: b, offset: j }) => a == b && i == j,
+            (InvalidUtf8 { offset: a }, InvalidUtf8 { offset: b }) => a == b,
+            (InvalidNumber { offset: a }, InvalidNumber { offset: b }) => a == b,
+            (TrailingBytes { offset: a }, TrailingBytes { offset: b }) => a == b,
+            (Decompress(a), Decompress(b)) => a == b,
+            (LimitExceeded { limit: a, offset: i }, LimitExceeded { limit: b, offset: j }) => a == b && i == j,
+            #[cfg(feature = "std")]
+            (Io(a), Io(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for DecodeError {}
+
+/// Which of the `DecodeLimits` was exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeLimit {
+    Depth,
+    Elements,
+    Length,
+    BigIntegerDigits,
+    DecompressedSize,
+    /// Length prefix of a framed message
+    FrameLength,
+    /// Bytes of an unfinished record in a [`StreamReader`](crate::StreamReader)
+    RecordSize,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidHeader => write!(f, "input does not start with a WXF header"),
+            DecodeError::Compressed => write!(f, "compressed input must be inflated before borrowing"),
+            DecodeError::UnexpectedEnd { offset } => write!(f, "unexpected end of input at byte {}", offset),
+            DecodeError::InvalidToken { token, offset } => write!(f, "invalid token 0x{:02X} at byte {}", token, offset),
+            DecodeError::InvalidArrayType { token, offset } => write!(f, "invalid array type 0x{:02X} at byte {}", token, offset),
+            DecodeError::InvalidUtf8 { offset } => write!(f, "invalid utf-8 at byte {}", offset),
+            DecodeError::InvalidNumber { offset } => write!(f, "invalid number at byte {}", offset),
+            DecodeError::TrailingBytes { offset } => write!(f, "trailing bytes after expression at byte {}", offset),
+            DecodeError::Decompress(e) => write!(f, "decompression failed: {}", e),
+            DecodeError::LimitExceeded { limit, offset } => write!(f, "{:?} limit exceeded at byte {}", limit, offset),
+            #[cfg(feature = "std")]
+            DecodeError::Io(e) => write!(f, "io error: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DecodeError::Io(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for DecodeError {
+    fn from(e: io::Error) -> Self {
+        DecodeError::Io(Arc::new(e))
+    }
+}
+
+/// Failure of a [`LazyReader`](crate::LazyReader) access
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum LazyError {
+    Io(io::Error),
+    Decode(DecodeError),
+    Part(PartError),
+}
+
+#[cfg(feature = "std")]
+impl Display for LazyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LazyError::Io(e) => write!(f, "io error: {}", e),
+            LazyError::Decode(e) => write!(f, "{}", e),
+            LazyError::Part(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for LazyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LazyError::Io(e) => Some(e),
+            LazyError::Decode(e) => Some(e),
+            LazyError::Part(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for LazyError {
+    fn from(e: io::Error) -> Self {
+        LazyError::Io(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<DecodeError> for LazyError {
+    fn from(e: DecodeError) -> Self {
+        LazyError::Decode(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<PartError> for LazyError {
+    fn from(e: PartError) -> Self {
+        LazyError::Part(e)
+    }
+}
+
+/// Failure while writing WXF bytes, also the error type of `serde_wxf`
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WXFError {
+    /// The value has no WXF form
+    UnsupportedValue(String),
+    /// A symbol name that Mathematica cannot read
+    InvalidSymbol(String),
+    /// A packed or numeric array that is not rectangular
+    InvalidArrayShape(String),
+    /// The output could not be written
+    #[cfg(feature = "std")]
+    Io(io::Error),
+    /// The zlib stream for `8C:` failed
+    Compression(String),
+    /// An encoded frame of [`WXFCodec`](crate::WXFCodec) is longer than its maximum or than the `u32` prefix
+    #[cfg(feature = "codec")]
+    FrameTooLong { length: usize, max: usize },
+    /// Message from a `Serialize` implementation
+    Custom(String),
+}
+
+impl Display for WXFError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WXFError::UnsupportedValue(v) => write!(f, "unsupported value: {}", v),
+            WXFError::InvalidSymbol(s) => write!(f, "invalid symbol name: {:?}", s),
+            WXFError::InvalidArrayShape(s) => write!(f, "invalid array shape: {}", s),
+            #[cfg(feature = "std")]
+            WXFError::Io(e) => write!(f, "io error: {}", e),
+            WXFError::Compression(e) => write!(f, "compression failed: {}", e),
+            #[cfg(feature = "codec")]
+            WXFError::FrameTooLong { length, max } => write!(f, "frame of {} bytes is longer than {}", length, max),
+            WXFError::Custom(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for WXFError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WXFError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for WXFError {
+    fn from(e: io::Error) -> Self {
+        WXFError::Io(e)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for WXFError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        WXFError::Custom(msg.to_string())
+    }
+}
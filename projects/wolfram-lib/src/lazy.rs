@@ -0,0 +1,307 @@
+use crate::{
+    utils::{array_type, decode_body, part_specs, position},
+    DecodeError, DecodeLimits, LazyError, PartError, PartSpec, ToWolfram, WolframValue,
+};
+use std::{
+    collections::BTreeMap,
+    io::{self, Read, Seek, SeekFrom},
+};
+
+/// Skips shorter than this are read instead of seeking, so a `BufReader` keeps its buffer
+const SEEK_THRESHOLD: u64 = 4096;
+
+/// Reads parts of an uncompressed WXF file without decoding the rest
+///
+/// Subexpressions are located by skipping over tokens, only the requested ones are decoded.
+/// Offsets in errors are positions in the underlying reader.
+pub struct LazyReader<R> {
+    reader: R,
+    root: Node,
+    limits: DecodeLimits,
+}
+
+/// Byte range of a subexpression, `children` is filled when it is first visited
+#[derive(Debug)]
+struct Node {
+    offset: u64,
+    length: u64,
+    children: Option<Children>,
+}
+
+#[derive(Debug)]
+enum Children {
+    Function(Box<Node>, Vec<Node>),
+    /// Keys are decoded, later duplicates replace earlier ones as in `from_bytes`
+    Association(BTreeMap<WolframValue, Node>),
+    /// Atoms and arrays are decoded whole
+    Leaf,
+}
+
+impl<R: Read + Seek> LazyReader<R> {
+    /// Check the header and index the top level
+    pub fn new(reader: R) -> Result<Self, LazyError> {
+        LazyReader::with_depth(reader, 1)
+    }
+    /// Index `depth` levels up front, deeper levels are indexed when first visited
+    pub fn with_depth(reader: R, depth: usize) -> Result<Self, LazyError> {
+        LazyReader::with_limits(reader, depth, DecodeLimits::default())
+    }
+    /// Bounds for indexing and for every decoded subexpression, association keys included
+    pub fn with_limits(mut reader: R, depth: usize, limits: DecodeLimits) -> Result<Self, LazyError> {
+        let start = reader.stream_position()?;
+        let mut scanner = Scanner { reader: &mut reader, position: start, captured: None };
+        match [scanner.read_byte()?, scanner.read_byte()?] {
+            [b'8', b':'] => {}
+            [b'8', b'C'] => return Err(DecodeError::Compressed.into()),
+            _ => return Err(DecodeError::InvalidHeader.into()),
+        }
+        let mut root = Node { offset: scanner.position, length: 0, children: None };
+        index(&mut reader, &mut root, depth.max(1), &limits)?;
+        let end = root.offset + root.length;
+        if reader.seek(SeekFrom::End(0))? != end {
+            return Err(DecodeError::TrailingBytes { offset: end as usize }.into());
+        }
+        Ok(Self { reader, root, limits })
+    }
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+    /// `Length[expr]` of the whole expression
+    pub fn length(&self) -> usize {
+        match &self.root.children {
+            Some(Children::Function(_, args)) => args.len(),
+            Some(Children::Association(dict)) => dict.len(),
+            _ => 0,
+        }
+    }
+    /// `Keys[assoc]` of the whole expression, empty if it is not an association
+    pub fn keys(&self) -> Vec<WolframValue> {
+        match &self.root.children {
+            Some(Children::Association(dict)) => dict.keys().cloned().collect(),
+            _ => vec![],
+        }
+    }
+    /// Decode everything
+    pub fn value(&mut self) -> Result<WolframValue, LazyError> {
+        decode(&mut self.reader, &self.root, &self.limits)
+    }
+    /// `Lookup[assoc, key]`
+    pub fn get(&mut self, key: impl ToWolfram) -> Result<WolframValue, LazyError> {
+        self.part(&[PartSpec::Key(key.to_wolfram())])
+    }
+    /// `Part[expr, i, j, ...]`, only the selected subexpression is decoded
+    pub fn part<P: Into<PartSpec> + Clone>(&mut self, path: &[P]) -> Result<WolframValue, LazyError> {
+        let Self { reader, root, limits } = self;
+        let path = part_specs(path);
+        let mut node = root;
+        for (i, spec) in path.iter().enumerate() {
+            let depth = i + 1;
+            if node.children.is_none() {
+                index(reader, node, 1, limits)?
+            }
+            if let Some(Children::Leaf) = node.children {
+                // arrays are expanded by the decoder, atoms report their value
+                let value = decode(reader, node, limits)?;
+                let mut this = &value;
+                for (j, spec) in path[i..].iter().enumerate() {
+                    this = this.part_step(spec, depth + j)?;
+                }
+                return Ok(this.clone());
+            }
+            node = match (node.children.as_mut(), spec) {
+                (Some(Children::Leaf), _) | (None, _) => unreachable!("indexed above"),
+                (_, PartSpec::All) => return Err(PartError::Span { depth }.into()),
+                (Some(Children::Function(head, _)), PartSpec::Index(0)) => head,
+                (Some(Children::Function(_, args)), PartSpec::Index(index)) => {
+                    let i = position(*index, args.len(), depth)?;
+                    &mut args[i]
+                }
+                (Some(Children::Function(..)), PartSpec::Key(key)) => return Err(PartError::KeyAbsent { depth, key: key.clone() }.into()),
+                (Some(Children::Association(dict)), PartSpec::Index(index)) => {
+                    let i = position(*index, dict.len(), depth)?;
+                    dict.values_mut().nth(i).unwrap()
+                }
+                (Some(Children::Association(dict)), PartSpec::Key(key)) => match dict.get_mut(key) {
+                    Some(node) => node,
+                    None => return Err(PartError::KeyAbsent { depth, key: key.clone() }.into()),
+                },
+            };
+        }
+        decode(reader, node, limits)
+    }
+}
+
+/// Fill in `depth` levels of children below `node` and its length
+fn index<R: Read + Seek>(reader: &mut R, node: &mut Node, depth: usize, limits: &DecodeLimits) -> Result<(), LazyError> {
+    if depth == 0 {
+        return Ok(());
+    }
+    reader.seek(SeekFrom::Start(node.offset))?;
+    let mut scanner = Scanner { reader, position: node.offset, captured: None };
+    let mut children = match scanner.read_byte()? {
+        b'f' => {
+            let n = scanner.read_length()?;
+            let head = scanner.read_node()?;
+            let mut args = Vec::with_capacity(n.min(SEEK_THRESHOLD as usize));
+            for _ in 0..n {
+                args.push(scanner.read_node()?)
+            }
+            Children::Function(Box::new(head), args)
+        }
+        b'A' => {
+            let n = scanner.read_length()?;
+            let mut dict = BTreeMap::new();
+            for _ in 0..n {
+                let offset = scanner.position;
+                match scanner.read_byte()? {
+                    b'-' | b':' => {}
+                    token => return Err(DecodeError::InvalidToken { token, offset: offset as usize }.into()),
+                }
+                // decoded from the bytes just scanned, seeking back would drop the buffer of a `BufReader`
+                scanner.captured = Some(Vec::new());
+                let key = scanner.read_node();
+                let bytes = scanner.captured.take().unwrap_or_default();
+                let key = key?;
+                let key = decode_body(&bytes, limits).map_err(|e| shift_offset(e, key.offset as usize))?;
+                dict.insert(key, scanner.read_node()?);
+            }
+            Children::Association(dict)
+        }
+        _ => {
+            scanner.reader.seek(SeekFrom::Start(node.offset))?;
+            scanner.position = node.offset;
+            scanner.skip_value()?;
+            Children::Leaf
+        }
+    };
+    node.length = scanner.position - node.offset;
+    match &mut children {
+        Children::Function(head, args) => {
+            index(reader, head, depth - 1, limits)?;
+            args.iter_mut().try_for_each(|v| index(reader, v, depth - 1, limits))?
+        }
+        Children::Association(dict) => dict.values_mut().try_for_each(|v| index(reader, v, depth - 1, limits))?,
+        Children::Leaf => {}
+    }
+    node.children = Some(children);
+    Ok(())
+}
+
+fn decode<R: Read + Seek>(reader: &mut R, node: &Node, limits: &DecodeLimits) -> Result<WolframValue, LazyError> {
+    reader.seek(SeekFrom::Start(node.offset))?;
+    let mut buffer = vec![0; node.length as usize];
+    reader.read_exact(&mut buffer)?;
+    decode_body(&buffer, limits).map_err(|e| shift_offset(e, node.offset as usize).into())
+}
+
+/// Offsets of the decoder are relative to the subexpression
+fn shift_offset(e: DecodeError, base: usize) -> DecodeError {
+    match e {
+        DecodeError::UnexpectedEnd { offset } => DecodeError::UnexpectedEnd { offset: base + offset },
+        DecodeError::InvalidToken { token, offset } => DecodeError::InvalidToken { token, offset: base + offset },
+        DecodeError::InvalidArrayType { token, offset } => DecodeError::InvalidArrayType { token, offset: base + offset },
+        DecodeError::InvalidUtf8 { offset } => DecodeError::InvalidUtf8 { offset: base + offset },
+        DecodeError::InvalidNumber { offset } => DecodeError::InvalidNumber { offset: base + offset },
+        DecodeError::TrailingBytes { offset } => DecodeError::TrailingBytes { offset: base + offset },
+        DecodeError::LimitExceeded { limit, offset } => DecodeError::LimitExceeded { limit, offset: base + offset },
+        e => e,
+    }
+}
+
+/// Walks tokens without building values
+struct Scanner<'r, R> {
+    reader: &'r mut R,
+    position: u64,
+    /// Bytes read while this is set, skips read them instead of seeking
+    captured: Option<Vec<u8>>,
+}
+
+impl<'r, R: Read + Seek> Scanner<'r, R> {
+    fn read_byte(&mut self) -> Result<u8, LazyError> {
+        let mut out = [0];
+        match self.reader.read_exact(&mut out) {
+            Ok(()) => {
+                self.position += 1;
+                if let Some(captured) = &mut self.captured {
+                    captured.push(out[0])
+                }
+                Ok(out[0])
+            }
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Err(DecodeError::UnexpectedEnd { offset: self.position as usize }.into()),
+            Err(e) => Err(e.into()),
+        }
+    }
+    fn read_length(&mut self) -> Result<usize, LazyError> {
+        let offset = self.position as usize;
+        let mut out = 0usize;
+        for shift in (0..70).step_by(7) {
+            let byte = self.read_byte()?;
+            out |= ((byte & 0x7F) as usize).checked_shl(shift).ok_or(DecodeError::InvalidNumber { offset })?;
+            if byte & 0x80 == 0 {
+                return Ok(out);
+            }
+        }
+        Err(DecodeError::InvalidNumber { offset }.into())
+    }
+    fn skip(&mut self, n: u64) -> Result<(), LazyError> {
+        let skipped = if let Some(captured) = &mut self.captured {
+            (&mut *self.reader).take(n).read_to_end(captured)? as u64
+        }
+        else if n < SEEK_THRESHOLD {
+            io::copy(&mut (&mut *self.reader).take(n), &mut io::sink())?
+        }
+        else {
+            let end = self.reader.seek(SeekFrom::End(0))?;
+            let target = self.position.saturating_add(n).min(end);
+            self.reader.seek(SeekFrom::Start(target))?;
+            target - self.position
+        };
+        self.position += skipped;
+        if skipped < n {
+            return Err(DecodeError::UnexpectedEnd { offset: self.position as usize }.into());
+        }
+        Ok(())
+    }
+    fn read_node(&mut self) -> Result<Node, LazyError> {
+        let offset = self.position;
+        self.skip_value()?;
+        Ok(Node { offset, length: self.position - offset, children: None })
+    }
+    /// Iterative, so deep nesting cannot overflow the stack
+    fn skip_value(&mut self) -> Result<(), LazyError> {
+        let mut pending = 1usize;
+        while pending > 0 {
+            pending -= 1;
+            let offset = self.position as usize;
+            match self.read_byte()? {
+                b'f' => pending = pending.saturating_add(self.read_length()?).saturating_add(1),
+                // rule marker, key and value
+                b'A' => pending = pending.saturating_add(self.read_length()?.saturating_mul(3)),
+                b'-' | b':' => {}
+                b'S' | b's' | b'B' | b'I' | b'R' => {
+                    let n = self.read_length()?;
+                    self.skip(n as u64)?
+                }
+                b'C' => self.skip(1)?,
+                b'j' => self.skip(2)?,
+                b'i' => self.skip(4)?,
+                b'L' | b'r' => self.skip(8)?,
+                0xC1 | 0xC2 => {
+                    let offset = self.position as usize;
+                    let token = self.read_byte()?;
+                    let size = match array_type(token) {
+                        Some((_, size)) => size,
+                        None => return Err(DecodeError::InvalidArrayType { token, offset }.into()),
+                    };
+                    let mut count = size as u64;
+                    for _ in 0..self.read_length()? {
+                        count = count.checked_mul(self.read_length()? as u64).ok_or(DecodeError::InvalidNumber { offset })?;
+                    }
+                    self.skip(count)?
+                }
+                token => return Err(DecodeError::InvalidToken { token, offset }.into()),
+            }
+        }
+        Ok(())
+    }
+}
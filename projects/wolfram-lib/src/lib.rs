@@ -1,11 +1,73 @@
-use num::BigInt;
-use std::{
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    boxed::Box,
     collections::BTreeMap,
-    fmt::{self, Display},
+    format,
+    string::{String, ToString},
+    vec::Vec,
 };
+use core::fmt::{self, Display};
+#[cfg(feature = "bigint")]
+use num::BigInt;
 
+mod borrowed;
+#[cfg(feature = "codec")]
+mod codec;
+mod errors;
+pub mod diff;
+#[cfg(feature = "std")]
+pub mod lazy;
 pub mod objects;
+pub mod patterns;
+#[cfg(feature = "compression")]
+pub mod stream;
 pub mod utils;
+pub mod visitor;
+
+pub use borrowed::{ArrayRef, WolframValueRef};
+#[cfg(feature = "codec")]
+pub use codec::WXFCodec;
+#[cfg(feature = "std")]
+pub use errors::LazyError;
+pub use errors::{DecodeError, DecodeLimit, FromWolframError, PartError, WXFError};
+#[cfg(feature = "std")]
+pub use lazy::LazyReader;
+#[cfg(feature = "compression")]
+pub use stream::{StreamReader, StreamWriter};
+#[cfg(feature = "compression")]
+pub use utils::CompressOptions;
+pub use utils::{DecodeLimits, EncodeOptions, PartSpec, WXFWrite};
+pub use wolfram_wxf_macros::wl;
+
+/// Paths used by the expansion of [`wl!`], which must also work in `no_std` crates
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::{boxed::Box, string::String, vec};
+    pub use core::{iter::FromIterator, str::FromStr};
+}
+
+/// An integer literal of [`wl!`] beyond `i64`
+#[cfg(feature = "bigint")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __big_integer {
+    ($digits:literal) => {
+        $crate::WolframValue::BigInteger($crate::__private::FromStr::from_str($digits).unwrap())
+    };
+}
+
+/// An integer literal of [`wl!`] beyond `i64`
+#[cfg(not(feature = "bigint"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __big_integer {
+    ($digits:literal) => {
+        compile_error!(concat!("integer literal ", $digits, " does not fit i64, enable the `bigint` feature of wolfram_wxf"))
+    };
+}
 
 pub trait ToWolfram {
     fn to_wolfram(&self) -> WolframValue;
@@ -15,12 +77,34 @@ pub trait ToWolfram {
     fn to_wolfram_bytes(&self) -> Vec<u8> {
         self.to_wolfram().to_bytes()
     }
+    #[cfg(feature = "compression")]
     fn to_wolfram_solid(&self) -> Vec<u8> {
         self.to_wolfram().to_compressed()
     }
+    fn try_to_wolfram_bytes(&self) -> Result<Vec<u8>, WXFError> {
+        self.to_wolfram().try_to_bytes()
+    }
+    #[cfg(feature = "compression")]
+    fn try_to_wolfram_solid(&self) -> Result<Vec<u8>, WXFError> {
+        self.to_wolfram().try_to_compressed()
+    }
+    #[cfg(feature = "compression")]
+    fn to_wolfram_solid_with(&self, options: &CompressOptions) -> Vec<u8> {
+        self.to_wolfram().to_compressed_with(options)
+    }
+    #[cfg(feature = "compression")]
+    fn try_to_wolfram_solid_with(&self, options: &CompressOptions) -> Result<Vec<u8>, WXFError> {
+        self.to_wolfram().try_to_compressed_with(options)
+    }
+}
+
+/// The inverse of [`ToWolfram`] for the expressions it produces
+pub trait FromWolfram: Sized {
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError>;
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd)]
+#[non_exhaustive]
 pub enum WolframValue {
     Skip,
     /// Function with name, args
@@ -32,6 +116,8 @@ pub enum WolframValue {
     Integer16(i16),
     Integer32(i32),
     Integer64(i64),
+    /// Only with the `bigint` feature, `WolframValue` is `#[non_exhaustive]` so enabling it does not break downstream matches
+    #[cfg(feature = "bigint")]
     BigInteger(BigInt),
     /// Do not use `f64`, because partial order cannot be defined
     Decimal64([u8; 8]),
@@ -52,7 +138,7 @@ impl Display for WolframValue {
             WolframValue::Skip => write!(f, ""),
             WolframValue::Function(head, args) => {
                 let v: Vec<String> = args.iter().map(|v| v.to_string()).collect();
-                if head.to_string() == "List" { write!(f, "{{{}}}", v.join(",")) } else { write!(f, "{}[{}]", head.to_string(), v.join(",")) }
+                if head.to_string() == "List" { write!(f, "{{{}}}", v.join(",")) } else { write!(f, "{}[{}]", head, v.join(",")) }
             }
             WolframValue::String(s) => write!(f, "{:?}", s),
             WolframValue::Bytes(b) => {
@@ -64,11 +150,18 @@ impl Display for WolframValue {
             WolframValue::Integer16(i) => write!(f, "{}", i),
             WolframValue::Integer32(i) => write!(f, "{}", i),
             WolframValue::Integer64(i) => write!(f, "{}", i),
+            #[cfg(feature = "bigint")]
             WolframValue::BigInteger(i) => write!(f, "{}", i),
             WolframValue::Decimal64(d) => write!(f, "{}`", f64::from_le_bytes(*d)),
             WolframValue::BigDecimal(d) => write!(f, "{}", d),
-            WolframValue::PackedArray(_) => unimplemented!(),
-            WolframValue::NumericArray(_) => unimplemented!(),
+            WolframValue::PackedArray(v) => {
+                let v: Vec<String> = v.iter().map(|v| v.to_string()).collect();
+                write!(f, "{{{}}}", v.join(","))
+            }
+            WolframValue::NumericArray(v) => {
+                let v: Vec<String> = v.iter().map(|v| v.to_string()).collect();
+                write!(f, "NumericArray[{{{}}}]", v.join(","))
+            }
             WolframValue::Association(dict) => {
                 let v: Vec<String> = dict.iter().map(|(k, (r, v))| format!("{}{}{}", k, r, v)).collect();
                 write!(f, "<|{}|>", v.join(","))
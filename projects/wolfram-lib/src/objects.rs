@@ -1,5 +1,129 @@
-use crate::WolframValue;
+use crate::{utils::is_machine_number, FromWolfram, FromWolframError, PartError, ToWolfram, WolframValue};
+use alloc::{boxed::Box, vec, vec::Vec};
 
 pub fn date_object(input: &str) -> WolframValue {
     WolframValue::function("DateObject", vec![input])
 }
+
+/// `SparseArray[Automatic, dims, default, {1, {rowPointers, columnIndices}, values}]`
+///
+/// Values are stored row by row as in compressed sparse row form, a vector is a single row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseArray {
+    pub dimensions: Vec<usize>,
+    /// Value at every position that is not stored, usually `0`
+    pub default: WolframValue,
+    /// Start of every row in `column_indices` followed by the number of values
+    pub row_pointers: Vec<usize>,
+    /// 1-based position of every value after its row, or in the vector
+    pub column_indices: Vec<Vec<usize>>,
+    pub values: Vec<WolframValue>,
+}
+
+impl SparseArray {
+    /// Sparse array with the values at the 1-based `positions`, later duplicates are ignored
+    ///
+    /// Fails if a position does not have one index per dimension or lies outside `dimensions`.
+    pub fn from_positions<I>(dimensions: Vec<usize>, default: WolframValue, positions: I) -> Result<Self, PartError>
+    where
+        I: IntoIterator<Item = (Vec<usize>, WolframValue)>,
+    {
+        let mut entries: Vec<(Vec<usize>, WolframValue)> = positions.into_iter().collect();
+        for (position, _) in &entries {
+            check_position(position, &dimensions)?
+        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries.dedup_by(|later, first| later.0 == first.0);
+        let rows = if dimensions.len() > 1 { dimensions[0] } else { 1 };
+        let mut row_pointers = vec![0; rows + 1];
+        let mut column_indices = Vec::with_capacity(entries.len());
+        let mut values = Vec::with_capacity(entries.len());
+        for (mut position, value) in entries {
+            if dimensions.len() > 1 {
+                row_pointers[position.remove(0)] += 1;
+            }
+            else {
+                row_pointers[1] += 1;
+            }
+            column_indices.push(position);
+            values.push(value);
+        }
+        for i in 1..row_pointers.len() {
+            row_pointers[i] += row_pointers[i - 1];
+        }
+        Ok(Self { dimensions, default, row_pointers, column_indices, values })
+    }
+    /// Value at the 1-based `position`, the default when it is not stored
+    pub fn get(&self, position: &[usize]) -> Option<&WolframValue> {
+        if position.len() != self.dimensions.len() || position.iter().zip(&self.dimensions).any(|(i, d)| *i < 1 || i > d) {
+            return None;
+        }
+        let (row, rest) = if self.dimensions.len() > 1 { (position[0] - 1, &position[1..]) } else { (0, position) };
+        let range = *self.row_pointers.get(row)?..*self.row_pointers.get(row + 1)?;
+        let stored = self.column_indices.get(range.clone())?.iter().position(|c| c == rest);
+        Some(stored.map_or(&self.default, |i| &self.values[range.start + i]))
+    }
+}
+
+/// One index per dimension, each within `1..=dimension`
+fn check_position(position: &[usize], dimensions: &[usize]) -> Result<(), PartError> {
+    if dimensions.is_empty() || position.len() != dimensions.len() {
+        return Err(PartError::Rank { indices: position.len(), rank: dimensions.len() });
+    }
+    match position.iter().zip(dimensions).enumerate().find(|(_, (i, d))| **i < 1 || i > d) {
+        Some((depth, (i, d))) => Err(PartError::OutOfRange { depth: depth + 1, index: *i as isize, length: *d }),
+        None => Ok(()),
+    }
+}
+
+fn integers(items: &[usize]) -> WolframValue {
+    WolframValue::PackedArray(items.iter().map(|i| i.to_wolfram()).collect())
+}
+
+/// A packed array of machine numbers, otherwise a list
+fn packed(items: Vec<WolframValue>) -> WolframValue {
+    if items.iter().all(is_machine_number) && !items.is_empty() { WolframValue::PackedArray(items) } else { WolframValue::list(items) }
+}
+
+impl ToWolfram for SparseArray {
+    fn to_wolfram(&self) -> WolframValue {
+        let columns = match self.column_indices.is_empty() {
+            true => WolframValue::list(vec![]),
+            false => WolframValue::PackedArray(self.column_indices.iter().map(|c| WolframValue::list(c.iter().map(|i| i.to_wolfram()).collect())).collect()),
+        };
+        let data = WolframValue::list(vec![1.to_wolfram(), WolframValue::list(vec![integers(&self.row_pointers), columns]), packed(self.values.clone())]);
+        let args = vec![WolframValue::symbol("Automatic"), integers(&self.dimensions), self.default.clone(), data];
+        WolframValue::Function(Box::new(WolframValue::symbol("SparseArray")), args)
+    }
+}
+
+/// Every stored index after the row lies within `dimensions`, or the index of a vector
+fn inside(dimensions: &[usize], column_indices: &[Vec<usize>]) -> bool {
+    let columns = if dimensions.len() > 1 { &dimensions[1..] } else { dimensions };
+    column_indices.iter().all(|c| check_position(c, columns).is_ok())
+}
+
+impl FromWolfram for SparseArray {
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        let error = || FromWolframError { expected: "SparseArray[Automatic, dims, default, {1, {rowPointers, columnIndices}, values}]", found: value.clone() };
+        let args = match value {
+            WolframValue::Function(head, args) if **head == WolframValue::symbol("SparseArray") && args.len() == 4 => args,
+            _ => return Err(error()),
+        };
+        let dimensions = Vec::<usize>::from_wolfram(&args[1]).map_err(|_| error())?;
+        let (version, (row_pointers, column_indices), values) =
+            <(usize, (Vec<usize>, Vec<Vec<usize>>), Vec<WolframValue>)>::from_wolfram(&args[3]).map_err(|_| error())?;
+        let rows = if dimensions.len() > 1 { dimensions[0] } else { 1 };
+        let consistent = version == 1
+            && row_pointers.len() == rows + 1
+            && row_pointers.windows(2).all(|w| w[0] <= w[1])
+            && row_pointers.first() == Some(&0)
+            && row_pointers.last() == Some(&values.len())
+            && column_indices.len() == values.len()
+            && inside(&dimensions, &column_indices);
+        match consistent {
+            true => Ok(Self { dimensions, default: args[2].clone(), row_pointers, column_indices, values }),
+            false => Err(error()),
+        }
+    }
+}
@@ -0,0 +1,266 @@
+mod rules;
+
+use crate::{utils::normalized_symbol_name, WolframValue};
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    sync::Arc,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::{self, Debug, Formatter};
+
+pub use self::rules::Rule;
+
+/// Values captured by named patterns, sequences are captured as `Sequence[...]`
+pub type Bindings = BTreeMap<String, WolframValue>;
+
+/// Pattern object, modeled on `Blank`, `Pattern` and `Condition`
+#[derive(Clone)]
+pub enum Pattern {
+    /// An exact expression, integer widths, symbol contexts and packing are ignored
+    Literal(WolframValue),
+    /// `_` or `_head`
+    Blank(Option<WolframValue>),
+    /// `__` or `__head`
+    BlankSequence(Option<WolframValue>),
+    /// `___` or `___head`
+    BlankNullSequence(Option<WolframValue>),
+    /// `name:pattern`
+    Named(String, Box<Pattern>),
+    /// `pattern /; test`
+    Condition(Box<Pattern>, Arc<dyn Fn(&Bindings) -> bool + Send + Sync>),
+    /// `p1 | p2 | ...`
+    Alternatives(Vec<Pattern>),
+    /// `head[args]`, also matches the items of a packed array with head `List`
+    Function(Box<Pattern>, Vec<Pattern>),
+    /// `<|key -> value, ...|>`, each entry matches one pair in any order, whatever its rule
+    Association(Vec<(Pattern, Pattern)>),
+}
+
+impl Debug for Pattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Literal(v) => write!(f, "{}", v),
+            Pattern::Blank(h) => write!(f, "_{}", h.as_ref().map(|h| h.to_string()).unwrap_or_default()),
+            Pattern::BlankSequence(h) => write!(f, "__{}", h.as_ref().map(|h| h.to_string()).unwrap_or_default()),
+            Pattern::BlankNullSequence(h) => write!(f, "___{}", h.as_ref().map(|h| h.to_string()).unwrap_or_default()),
+            Pattern::Named(name, p) => write!(f, "{}:{:?}", name, p),
+            Pattern::Condition(p, _) => write!(f, "{:?}/;<test>", p),
+            Pattern::Alternatives(ps) => f.debug_list().entries(ps).finish(),
+            Pattern::Function(head, args) => write!(f, "{:?}{:?}", head, args),
+            Pattern::Association(entries) => {
+                let entries: Vec<String> = entries.iter().map(|(k, v)| format!("{:?}->{:?}", k, v)).collect();
+                write!(f, "<|{}|>", entries.join(","))
+            }
+        }
+    }
+}
+
+impl From<WolframValue> for Pattern {
+    /// Read a pattern written in `FullForm`, e.g. `f[Pattern[x, Blank[Integer]], BlankNullSequence[]]`
+    fn from(value: WolframValue) -> Self {
+        Pattern::from(&value)
+    }
+}
+
+impl From<&WolframValue> for Pattern {
+    fn from(value: &WolframValue) -> Self {
+        let (head, args) = match value {
+            WolframValue::Function(head, args) => (head.as_ref(), args.as_slice()),
+            WolframValue::Association(dict) => {
+                return Pattern::Association(dict.iter().map(|(k, (_, v))| (Pattern::from(k), Pattern::from(v))).collect());
+            }
+            _ => return Pattern::Literal(value.clone()),
+        };
+        let name = match head {
+            WolframValue::Symbol(s) => normalized_symbol_name(s),
+            _ => String::new(),
+        };
+        match (name.as_str(), args) {
+            ("Blank", []) => Pattern::Blank(None),
+            ("Blank", [h]) => Pattern::Blank(Some(h.clone())),
+            ("BlankSequence", []) => Pattern::BlankSequence(None),
+            ("BlankSequence", [h]) => Pattern::BlankSequence(Some(h.clone())),
+            ("BlankNullSequence", []) => Pattern::BlankNullSequence(None),
+            ("BlankNullSequence", [h]) => Pattern::BlankNullSequence(Some(h.clone())),
+            ("Pattern", [WolframValue::Symbol(s), p]) => Pattern::named(s.as_str(), Pattern::from(p)),
+            ("Alternatives", ps) => Pattern::Alternatives(ps.iter().map(Pattern::from).collect()),
+            _ => Pattern::Function(Box::new(Pattern::from(head)), args.iter().map(Pattern::from).collect()),
+        }
+    }
+}
+
+impl Pattern {
+    /// `_`
+    pub fn blank() -> Self {
+        Pattern::Blank(None)
+    }
+    /// `_head`
+    pub fn blank_head(head: &str) -> Self {
+        Pattern::Blank(Some(WolframValue::symbol(head)))
+    }
+    /// `__`
+    pub fn sequence() -> Self {
+        Pattern::BlankSequence(None)
+    }
+    /// `___`
+    pub fn null_sequence() -> Self {
+        Pattern::BlankNullSequence(None)
+    }
+    /// `name:pattern`, `x_` is `Pattern::named("x", Pattern::blank())`
+    pub fn named(name: &str, pattern: Pattern) -> Self {
+        Pattern::Named(name.to_string(), Box::new(pattern))
+    }
+    /// `head[args]`
+    pub fn function(head: &str, args: Vec<Pattern>) -> Self {
+        Pattern::Function(Box::new(Pattern::Literal(WolframValue::symbol(head))), args)
+    }
+    /// `<|key -> value, ...|>`
+    pub fn association(entries: Vec<(Pattern, Pattern)>) -> Self {
+        Pattern::Association(entries)
+    }
+    /// `self /; test`
+    pub fn condition<F>(self, test: F) -> Self
+    where
+        F: Fn(&Bindings) -> bool + Send + Sync + 'static,
+    {
+        Pattern::Condition(Box::new(self), Arc::new(test))
+    }
+    /// `MatchQ[value, self]`, returns the captured bindings on success
+    pub fn match_value(&self, value: &WolframValue, bindings: &Bindings) -> Option<Bindings> {
+        match self {
+            Pattern::Literal(v) => if same_expression(v, value) { Some(bindings.clone()) } else { None },
+            Pattern::Blank(h) | Pattern::BlankSequence(h) | Pattern::BlankNullSequence(h) => {
+                if head_matches(h, value) { Some(bindings.clone()) } else { None }
+            }
+            Pattern::Named(name, p) => bind(p.match_value(value, bindings)?, name, value.clone()),
+            Pattern::Condition(p, test) => p.match_value(value, bindings).filter(|b| test(b)),
+            Pattern::Alternatives(ps) => ps.iter().find_map(|p| p.match_value(value, bindings)),
+            Pattern::Function(head, args) => match value {
+                WolframValue::Function(h, a) => match_sequence(args, a, &head.match_value(h, bindings)?),
+                WolframValue::PackedArray(items) => match_sequence(args, items, &head.match_value(&value.head(), bindings)?),
+                _ => None,
+            },
+            Pattern::Association(entries) => match value {
+                WolframValue::Association(dict) if dict.len() == entries.len() => {
+                    let items: Vec<_> = dict.iter().map(|(k, (_, v))| (k, v)).collect();
+                    match_entries(entries, &items, bindings)
+                }
+                _ => None,
+            },
+        }
+    }
+    /// Minimum length if this pattern stands for a sequence of arguments
+    fn sequence_min(&self) -> Option<usize> {
+        match self {
+            Pattern::BlankSequence(_) => Some(1),
+            Pattern::BlankNullSequence(_) => Some(0),
+            Pattern::Named(_, p) | Pattern::Condition(p, _) => p.sequence_min(),
+            _ => None,
+        }
+    }
+    fn match_items(&self, items: &[WolframValue], bindings: &Bindings) -> Option<Bindings> {
+        match self {
+            Pattern::BlankSequence(h) | Pattern::BlankNullSequence(h) => {
+                if items.iter().all(|v| head_matches(h, v)) { Some(bindings.clone()) } else { None }
+            }
+            Pattern::Named(name, p) => bind(p.match_items(items, bindings)?, name, WolframValue::function("Sequence", items.to_vec())),
+            Pattern::Condition(p, test) => p.match_items(items, bindings).filter(|b| test(b)),
+            _ => match items {
+                [v] => self.match_value(v, bindings),
+                _ => None,
+            },
+        }
+    }
+}
+
+fn match_sequence(patterns: &[Pattern], values: &[WolframValue], bindings: &Bindings) -> Option<Bindings> {
+    let (first, rest) = match patterns.split_first() {
+        Some(s) => s,
+        None => return if values.is_empty() { Some(bindings.clone()) } else { None },
+    };
+    match first.sequence_min() {
+        Some(min) => (min..=values.len()).find_map(|k| {
+            let b = first.match_items(&values[..k], bindings)?;
+            match_sequence(rest, &values[k..], &b)
+        }),
+        None => {
+            let (v, vs) = values.split_first()?;
+            match_sequence(rest, vs, &first.match_value(v, bindings)?)
+        }
+    }
+}
+
+/// Every pattern entry takes one of the remaining entries, backtracking on conflicting bindings
+fn match_entries(patterns: &[(Pattern, Pattern)], entries: &[(&WolframValue, &WolframValue)], bindings: &Bindings) -> Option<Bindings> {
+    let ((key, value), rest) = match patterns.split_first() {
+        Some(s) => s,
+        None => return Some(bindings.clone()),
+    };
+    (0..entries.len()).find_map(|i| {
+        let (k, v) = entries[i];
+        let b = value.match_value(v, &key.match_value(k, bindings)?)?;
+        let others: Vec<_> = entries[..i].iter().chain(&entries[i + 1..]).copied().collect();
+        match_entries(rest, &others, &b)
+    })
+}
+
+fn head_matches(head: &Option<WolframValue>, value: &WolframValue) -> bool {
+    match head {
+        Some(h) => same_expression(&value.head(), h),
+        None => true,
+    }
+}
+
+/// Equal as Mathematica reads them, see [`Pattern::Literal`]
+fn same_expression(a: &WolframValue, b: &WolframValue) -> bool {
+    match (a, b) {
+        (WolframValue::Symbol(x), WolframValue::Symbol(y)) => normalized_symbol_name(x) == normalized_symbol_name(y),
+        (
+            WolframValue::Function(..) | WolframValue::PackedArray(_) | WolframValue::NumericArray(_),
+            WolframValue::Function(..) | WolframValue::PackedArray(_) | WolframValue::NumericArray(_),
+        ) => {
+            same_expression(&a.head(), &b.head())
+                && a.args().len() == b.args().len()
+                && a.args().iter().zip(b.args()).all(|(x, y)| same_expression(x, y))
+        }
+        (WolframValue::Association(x), WolframValue::Association(y)) => {
+            x.len() == y.len()
+                && x.iter().all(|(k, (r, v))| y.iter().any(|(l, (s, w))| r == s && same_expression(k, l) && same_expression(v, w)))
+        }
+        _ => match (a.integer_value(), b.integer_value()) {
+            (Some(x), Some(y)) => x == y,
+            _ => a == b,
+        },
+    }
+}
+
+/// The same name must always capture the same value
+fn bind(mut bindings: Bindings, name: &str, value: WolframValue) -> Option<Bindings> {
+    match bindings.get(name) {
+        Some(old) if *old != value => None,
+        Some(_) => Some(bindings),
+        None => {
+            bindings.insert(name.to_string(), value);
+            Some(bindings)
+        }
+    }
+}
+
+impl WolframValue {
+    /// `MatchQ[expr, pattern]`
+    pub fn matches(&self, pattern: &Pattern) -> bool {
+        pattern.match_value(self, &Bindings::new()).is_some()
+    }
+    /// `Cases[expr, pattern]`, searches the arguments or association values at level 1
+    pub fn cases(&self, pattern: &Pattern) -> Vec<WolframValue> {
+        match self {
+            WolframValue::Function(_, args) => args.iter().filter(|v| v.matches(pattern)).cloned().collect(),
+            WolframValue::Association(dict) => dict.values().map(|(_, v)| v).filter(|v| v.matches(pattern)).cloned().collect(),
+            _ => vec![],
+        }
+    }
+}
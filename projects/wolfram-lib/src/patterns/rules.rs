@@ -0,0 +1,115 @@
+use super::{Bindings, Pattern};
+use crate::{ToWolfram, WolframValue};
+use alloc::{boxed::Box, sync::Arc, vec, vec::Vec};
+
+/// Same as the default `MaxIterations` of `ReplaceRepeated`
+const MAX_ITERATIONS: usize = 65536;
+
+/// `pattern -> template` or `pattern :> f[bindings]`
+#[derive(Clone)]
+pub struct Rule {
+    pattern: Pattern,
+    replacement: Replacement,
+}
+
+#[derive(Clone)]
+enum Replacement {
+    Template(WolframValue),
+    Computed(Arc<dyn Fn(&Bindings) -> WolframValue + Send + Sync>),
+}
+
+impl Rule {
+    /// Symbols in `template` named after a pattern are substituted by the captured values
+    pub fn new(pattern: impl Into<Pattern>, template: impl ToWolfram) -> Self {
+        Self { pattern: pattern.into(), replacement: Replacement::Template(template.to_wolfram()) }
+    }
+    /// The replacement is computed from the captured values
+    pub fn delayed<F>(pattern: impl Into<Pattern>, f: F) -> Self
+    where
+        F: Fn(&Bindings) -> WolframValue + Send + Sync + 'static,
+    {
+        Self { pattern: pattern.into(), replacement: Replacement::Computed(Arc::new(f)) }
+    }
+    /// Rewrite `value` if it matches the whole pattern
+    pub fn apply(&self, value: &WolframValue) -> Option<WolframValue> {
+        let bindings = self.pattern.match_value(value, &Bindings::new())?;
+        match &self.replacement {
+            Replacement::Template(t) => Some(substitute(t, &bindings)),
+            Replacement::Computed(f) => Some(f(&bindings)),
+        }
+    }
+}
+
+impl WolframValue {
+    /// `Replace[expr, rules]`, only the whole expression is rewritten
+    pub fn replace(&self, rules: &[Rule]) -> WolframValue {
+        rules.iter().find_map(|r| r.apply(self)).unwrap_or_else(|| self.clone())
+    }
+    /// `ReplaceAll[expr, rules]`, rewritten parts are not visited again
+    pub fn replace_all(&self, rules: &[Rule]) -> WolframValue {
+        if let Some(v) = rules.iter().find_map(|r| r.apply(self)) {
+            return v;
+        }
+        match self {
+            WolframValue::Function(head, args) => {
+                WolframValue::Function(Box::new(head.replace_all(rules)), splice(args.iter().map(|v| v.replace_all(rules))))
+            }
+            WolframValue::Association(dict) => {
+                let map = dict.iter().map(|(k, (r, v))| (k.replace_all(rules), (r.clone(), v.replace_all(rules))));
+                WolframValue::Association(map.collect())
+            }
+            WolframValue::PackedArray(_) | WolframValue::NumericArray(_) => rebuild_array(self, splice(self.args().iter().map(|v| v.replace_all(rules)))),
+            _ => self.clone(),
+        }
+    }
+    /// `ReplaceRepeated[expr, rules]`, stops when nothing changes
+    pub fn replace_repeated(&self, rules: &[Rule]) -> WolframValue {
+        let mut this = self.clone();
+        for _ in 0..MAX_ITERATIONS {
+            let next = this.replace_all(rules);
+            if next == this {
+                break;
+            }
+            this = next
+        }
+        this
+    }
+}
+
+fn substitute(template: &WolframValue, bindings: &Bindings) -> WolframValue {
+    match template {
+        WolframValue::Symbol(s) => bindings.get(s).cloned().unwrap_or_else(|| template.clone()),
+        WolframValue::Function(head, args) => {
+            WolframValue::Function(Box::new(substitute(head, bindings)), splice(args.iter().map(|v| substitute(v, bindings))))
+        }
+        WolframValue::Association(dict) => {
+            let map = dict.iter().map(|(k, (r, v))| (substitute(k, bindings), (r.clone(), substitute(v, bindings))));
+            WolframValue::Association(map.collect())
+        }
+        WolframValue::PackedArray(_) | WolframValue::NumericArray(_) => {
+            rebuild_array(template, splice(template.args().iter().map(|v| substitute(v, bindings))))
+        }
+        _ => template.clone(),
+    }
+}
+
+/// A packed array is unpacked to a `List` once an item changes, as in Mathematica, a numeric array keeps its type
+fn rebuild_array(array: &WolframValue, items: Vec<WolframValue>) -> WolframValue {
+    match array {
+        _ if items == array.args() => array.clone(),
+        WolframValue::NumericArray(_) => WolframValue::NumericArray(items),
+        _ => WolframValue::list(items),
+    }
+}
+
+/// `f[a, Sequence[b, c]]` becomes `f[a, b, c]`
+fn splice(args: impl Iterator<Item = WolframValue>) -> Vec<WolframValue> {
+    let mut out = vec![];
+    for v in args {
+        match v {
+            WolframValue::Function(head, items) if *head == WolframValue::symbol("Sequence") => out.extend(items),
+            _ => out.push(v),
+        }
+    }
+    out
+}
@@ -0,0 +1,217 @@
+use crate::{
+    utils::decode_prefix, CompressOptions, DecodeError, DecodeLimit, DecodeLimits, EncodeOptions, WXFError, WolframValue,
+};
+use flate2::bufread::ZlibDecoder;
+use std::io::{self, Read, Write};
+
+/// Smallest read from the underlying reader
+const READ_SIZE: usize = 1 << 16;
+/// Default of [`StreamReader::set_max_record_size`]
+const MAX_RECORD_SIZE: usize = 1 << 26;
+
+/// Writes independent expressions one after another, each with its own header
+///
+/// The output is readable by [`StreamReader`], or record by record with `BinaryRead` in Mathematica.
+pub struct StreamWriter<W> {
+    writer: W,
+    encode: EncodeOptions,
+    compression: Option<CompressOptions>,
+}
+
+impl<W: Write> StreamWriter<W> {
+    /// Records with an `8:` header
+    pub fn new(writer: W) -> Self {
+        Self::with_options(writer, EncodeOptions::default())
+    }
+    pub fn with_options(writer: W, options: EncodeOptions) -> Self {
+        Self { writer, encode: options, compression: None }
+    }
+    /// Records with an `8C:` header, deflated one by one
+    pub fn compressed(writer: W, options: CompressOptions) -> Self {
+        Self { writer, encode: options.encode, compression: Some(options) }
+    }
+    /// Append one record
+    pub fn write(&mut self, value: &WolframValue) -> Result<(), WXFError> {
+        match &self.compression {
+            Some(options) => value.write_compressed_to(&mut self.writer, options),
+            None => value.write_to(&mut self.writer, &self.encode),
+        }
+    }
+    pub fn flush(&mut self) -> Result<(), WXFError> {
+        Ok(self.writer.flush()?)
+    }
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Iterates the records of a stream, compressed or not
+///
+/// A corrupted record yields one error, then reading resumes at the next `8:` or `8C:` header.
+/// A record still unfinished after 64 MiB is reported as corrupted too, so a bad length prefix
+/// cannot hold back the rest of the stream until the input ends.
+/// Records shorter than 64 KiB are decoded as soon as they are complete, so a pipe can be followed while it is written.
+/// Longer records are decoded again only after the pending bytes have doubled or the input has ended.
+pub struct StreamReader<R> {
+    reader: R,
+    /// Initialized once, only `start..end` holds unread input
+    buffer: Vec<u8>,
+    start: usize,
+    end: usize,
+    eof: bool,
+    /// Discarding bytes until the next header
+    skipping: bool,
+    limits: DecodeLimits,
+    max_record_size: usize,
+}
+
+impl<R: Read> StreamReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: vec![],
+            start: 0,
+            end: 0,
+            eof: false,
+            skipping: false,
+            limits: DecodeLimits::default(),
+            max_record_size: MAX_RECORD_SIZE,
+        }
+    }
+    /// Bounds for each record
+    pub fn set_limits(&mut self, limits: DecodeLimits) {
+        self.limits = limits
+    }
+    /// Bytes read for one record before it fails with [`DecodeLimit::RecordSize`] and reading resumes at the next header
+    pub fn set_max_record_size(&mut self, size: usize) {
+        self.max_record_size = size
+    }
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+    fn pending(&self) -> &[u8] {
+        &self.buffer[self.start..self.end]
+    }
+    /// Read until the pending bytes have doubled, by at least `READ_SIZE`, or the input ends.
+    ///
+    /// A pending record shorter than `READ_SIZE` is retried after every read instead.
+    fn fill(&mut self) -> io::Result<()> {
+        if self.start > READ_SIZE && self.start > self.end / 2 {
+            self.buffer.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+        }
+        let target = self.end + READ_SIZE.max(self.end - self.start);
+        if self.buffer.len() < target {
+            // only bytes never used before are zeroed
+            self.buffer.resize(target, 0);
+        }
+        while self.end < target {
+            match self.reader.read(&mut self.buffer[self.end..target]) {
+                Ok(0) => {
+                    self.eof = true;
+                    break;
+                }
+                Ok(n) => self.end += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+            if self.end - self.start < READ_SIZE {
+                break;
+            }
+        }
+        Ok(())
+    }
+    /// A failed read ends the stream
+    fn io_error(&mut self, e: io::Error) -> DecodeError {
+        self.eof = true;
+        self.skipping = true;
+        e.into()
+    }
+}
+
+impl<R: Read> Iterator for StreamReader<R> {
+    type Item = Result<WolframValue, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.skipping {
+                match find_header(self.pending()) {
+                    Some(i) => {
+                        self.start += i;
+                        self.skipping = false
+                    }
+                    None if self.eof => return None,
+                    None => {
+                        // keep what may be the beginning of a header
+                        self.start = self.start.max(self.end.saturating_sub(2));
+                        if let Err(e) = self.fill() {
+                            return Some(Err(self.io_error(e)));
+                        }
+                        continue;
+                    }
+                }
+            }
+            let pending = self.pending();
+            if pending.is_empty() && self.eof {
+                return None;
+            }
+            let size = pending.len();
+            return match read_record(pending, &self.limits) {
+                Ok((value, used)) => {
+                    self.start += used;
+                    Some(Ok(value))
+                }
+                Err(DecodeError::UnexpectedEnd { .. }) if !self.eof && size <= self.max_record_size => match self.fill() {
+                    Ok(()) => continue,
+                    Err(e) => Some(Err(self.io_error(e))),
+                },
+                Err(DecodeError::UnexpectedEnd { .. }) if !self.eof => {
+                    self.start += 1;
+                    self.skipping = true;
+                    Some(Err(DecodeError::LimitExceeded { limit: DecodeLimit::RecordSize, offset: size }))
+                }
+                Err(e) => {
+                    self.start += 1;
+                    self.skipping = true;
+                    Some(Err(e))
+                }
+            };
+        }
+    }
+}
+
+/// One record at the start of `input` and its length
+fn read_record(input: &[u8], limits: &DecodeLimits) -> Result<(WolframValue, usize), DecodeError> {
+    if let Some(body) = input.strip_prefix(b"8:") {
+        let (value, used) = decode_prefix(body, limits)?;
+        return Ok((value, 2 + used));
+    }
+    let body = match input.strip_prefix(b"8C:") {
+        Some(body) => body,
+        None if b"8C:".starts_with(input) => return Err(DecodeError::UnexpectedEnd { offset: input.len() }),
+        None => return Err(DecodeError::InvalidHeader),
+    };
+    let max = limits.max_decompressed_size;
+    let mut decoder = ZlibDecoder::new(body);
+    let mut buffer = vec![];
+    match (&mut decoder).take((max as u64).saturating_add(1)).read_to_end(&mut buffer) {
+        Ok(_) if buffer.len() > max => return Err(DecodeError::LimitExceeded { limit: DecodeLimit::DecompressedSize, offset: max }),
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Err(DecodeError::UnexpectedEnd { offset: input.len() }),
+        Err(e) => return Err(DecodeError::Decompress(e.to_string())),
+    }
+    let (value, used) = decode_prefix(&buffer, limits)?;
+    if used != buffer.len() {
+        return Err(DecodeError::TrailingBytes { offset: used });
+    }
+    Ok((value, 3 + decoder.total_in() as usize))
+}
+
+/// Position of the first `8:` or `8C:`
+fn find_header(input: &[u8]) -> Option<usize> {
+    (0..input.len()).find(|&i| input[i..].starts_with(b"8:") || input[i..].starts_with(b"8C:"))
+}
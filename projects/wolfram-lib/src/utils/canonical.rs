@@ -0,0 +1,108 @@
+use crate::WolframValue;
+use alloc::string::{String, ToString};
+use core::cmp::Ordering;
+#[cfg(feature = "bigint")]
+use num::{BigInt, ToPrimitive};
+
+/// Any integer value, `i64` is enough without the `bigint` feature
+#[cfg(feature = "bigint")]
+pub(crate) type Integer = BigInt;
+#[cfg(not(feature = "bigint"))]
+pub(crate) type Integer = i64;
+
+impl WolframValue {
+    /// Integers of different widths are the same expression
+    pub(crate) fn integer_value(&self) -> Option<Integer> {
+        match self {
+            WolframValue::Integer8(i) => Some(Integer::from(*i)),
+            WolframValue::Integer16(i) => Some(Integer::from(*i)),
+            WolframValue::Integer32(i) => Some(Integer::from(*i)),
+            WolframValue::Integer64(i) => Some(Integer::from(*i)),
+            #[cfg(feature = "bigint")]
+            WolframValue::BigInteger(i) => Some(i.clone()),
+            _ => None,
+        }
+    }
+    fn real_value(&self) -> Option<f64> {
+        match self {
+            WolframValue::Decimal64(d) => Some(f64::from_le_bytes(*d)),
+            WolframValue::BigDecimal(d) => d.split('`').next().and_then(|s| s.parse().ok()),
+            _ => self.integer_value().and_then(integer_to_f64),
+        }
+    }
+}
+
+#[cfg(feature = "bigint")]
+pub(crate) fn integer_to_f64(i: Integer) -> Option<f64> {
+    i.to_f64()
+}
+
+#[cfg(not(feature = "bigint"))]
+pub(crate) fn integer_to_f64(i: Integer) -> Option<f64> {
+    Some(i as f64)
+}
+
+/// One representative for all widths of the same integer
+#[cfg(feature = "bigint")]
+pub(crate) fn integer_to_value(i: Integer) -> WolframValue {
+    WolframValue::BigInteger(i)
+}
+
+#[cfg(not(feature = "bigint"))]
+pub(crate) fn integer_to_value(i: Integer) -> WolframValue {
+    WolframValue::Integer64(i)
+}
+/// The name without a `Global` or `System` context, the inverse of the context added by the encoder
+/// The symbol name without a `Global`` or `System`` context, the inverse of the context added by the encoder
+pub fn normalized_symbol_name(input: &str) -> String {
+    match input.strip_prefix("Global`").or_else(|| input.strip_prefix("System`")) {
+        Some(s) => s.to_string(),
+        None => input.to_string(),
+    }
+}
+
+/// The order of `Sort`: numbers, strings, symbols, then expressions by length, head and arguments
+pub fn canonical_order(a: &WolframValue, b: &WolframValue) -> Ordering {
+    let category = category(a).cmp(&category(b));
+    if category != Ordering::Equal {
+        return category;
+    }
+    match (a, b) {
+        (WolframValue::String(x), WolframValue::String(y)) | (WolframValue::Symbol(x), WolframValue::Symbol(y)) => alphabetic_order(x, y),
+        (WolframValue::Function(xh, xa), WolframValue::Function(yh, ya)) => xa
+            .len()
+            .cmp(&ya.len())
+            .then_with(|| canonical_order(xh, yh))
+            .then_with(|| xa.iter().zip(ya).map(|(x, y)| canonical_order(x, y)).find(|o| *o != Ordering::Equal).unwrap_or(Ordering::Equal)),
+        _ => match (a.integer_value(), b.integer_value()) {
+            (Some(x), Some(y)) => x.cmp(&y),
+            (x, y) => match (a.real_value(), b.real_value()) {
+                (Some(p), Some(q)) => p.partial_cmp(&q).unwrap_or(Ordering::Equal).then(y.is_some().cmp(&x.is_some())),
+                _ => a.cmp(b),
+            },
+        },
+    }
+}
+
+fn category(value: &WolframValue) -> u8 {
+    match value {
+        WolframValue::Integer8(_)
+        | WolframValue::Integer16(_)
+        | WolframValue::Integer32(_)
+        | WolframValue::Integer64(_)
+        | WolframValue::Decimal64(_)
+        | WolframValue::BigDecimal(_) => 0,
+        WolframValue::String(_) => 1,
+        WolframValue::Symbol(_) => 2,
+        WolframValue::Bytes(_) => 3,
+        #[cfg(feature = "bigint")]
+        WolframValue::BigInteger(_) => 0,
+        _ => 4,
+    }
+}
+
+/// Case is ignored first, then lower case comes before upper case
+fn alphabetic_order(a: &str, b: &str) -> Ordering {
+    let lower = a.chars().flat_map(char::to_lowercase).cmp(b.chars().flat_map(char::to_lowercase));
+    lower.then_with(|| a.chars().map(char::is_uppercase).cmp(b.chars().map(char::is_uppercase))).then_with(|| a.cmp(b))
+}
@@ -0,0 +1,242 @@
+use super::{
+    is_machine_number,
+    table::{cell_type, dataset, missing, table_columns, text, unix_time, CellType},
+};
+use crate::{objects::date_object, FromWolfram, ToWolfram, WolframValue};
+use arrow::{
+    array::{
+        Array, ArrayRef, AsArray, BinaryArray, BooleanArray, Float64Array, Int64Array, NullArray, OffsetSizeTrait, RecordBatch, StringArray,
+    },
+    compute::concat_batches,
+    datatypes::*,
+    error::ArrowError,
+    ipc::{
+        reader::{FileReader, StreamReader},
+        writer::FileWriter,
+    },
+    temporal_conversions::{date32_to_datetime, date64_to_datetime},
+    util::display::array_value_to_string,
+};
+use std::{
+    io::{Cursor, Write},
+    sync::Arc,
+};
+
+/// `FromUnixTime[t, TimeZone -> tz]`
+fn timestamp(value: i64, unit: &TimeUnit, tz: Option<&str>) -> WolframValue {
+    let per_second = match unit {
+        TimeUnit::Second => 1,
+        TimeUnit::Millisecond => 1_000,
+        TimeUnit::Microsecond => 1_000_000,
+        TimeUnit::Nanosecond => 1_000_000_000,
+    };
+    unix_time(value, per_second, tz)
+}
+
+fn list<O: OffsetSizeTrait>(array: &dyn Array) -> Vec<WolframValue> {
+    let array = array.as_list::<O>();
+    (0..array.len()).map(|i| WolframValue::list(column(array.value(i).as_ref()))).collect()
+}
+
+macro_rules! primitive {
+    ($array:expr, $t:ty) => {
+        $array.as_primitive::<$t>().iter().map(|v| v.map_or_else(missing, |v| v.to_wolfram())).collect()
+    };
+}
+
+/// Every element of an Arrow array, nulls become `Missing["NotAvailable"]`
+pub fn column(array: &dyn Array) -> Vec<WolframValue> {
+    let mut out: Vec<WolframValue> = match array.data_type() {
+        DataType::Null => vec![missing(); array.len()],
+        DataType::Boolean => array.as_boolean().iter().map(|v| v.map_or_else(missing, |v| v.to_wolfram())).collect(),
+        DataType::Int8 => primitive!(array, Int8Type),
+        DataType::Int16 => primitive!(array, Int16Type),
+        DataType::Int32 => primitive!(array, Int32Type),
+        DataType::Int64 => primitive!(array, Int64Type),
+        DataType::UInt8 => primitive!(array, UInt8Type),
+        DataType::UInt16 => primitive!(array, UInt16Type),
+        DataType::UInt32 => primitive!(array, UInt32Type),
+        DataType::UInt64 => primitive!(array, UInt64Type),
+        DataType::Float16 => array.as_primitive::<Float16Type>().iter().map(|v| v.map_or_else(missing, |v| f32::from(v).to_wolfram())).collect(),
+        DataType::Float32 => primitive!(array, Float32Type),
+        DataType::Float64 => primitive!(array, Float64Type),
+        DataType::Decimal128(precision, _) => {
+            // exact digits with the precision of the type, as `123.45`5.`
+            let array = array.as_primitive::<Decimal128Type>();
+            (0..array.len()).map(|i| WolframValue::BigDecimal(format!("{}`{}.", array.value_as_string(i), precision))).collect()
+        }
+        DataType::Utf8 => array.as_string::<i32>().iter().map(|v| v.map_or_else(missing, |v| v.to_wolfram())).collect(),
+        DataType::LargeUtf8 => array.as_string::<i64>().iter().map(|v| v.map_or_else(missing, |v| v.to_wolfram())).collect(),
+        DataType::Utf8View => array.as_string_view().iter().map(|v| v.map_or_else(missing, |v| v.to_wolfram())).collect(),
+        DataType::Binary => array.as_binary::<i32>().iter().map(|v| v.map_or_else(missing, |v| WolframValue::Bytes(v.to_vec()))).collect(),
+        DataType::LargeBinary => array.as_binary::<i64>().iter().map(|v| v.map_or_else(missing, |v| WolframValue::Bytes(v.to_vec()))).collect(),
+        DataType::BinaryView => array.as_binary_view().iter().map(|v| v.map_or_else(missing, |v| WolframValue::Bytes(v.to_vec()))).collect(),
+        DataType::FixedSizeBinary(_) => array.as_fixed_size_binary().iter().map(|v| v.map_or_else(missing, |v| WolframValue::Bytes(v.to_vec()))).collect(),
+        DataType::Date32 => array
+            .as_primitive::<Date32Type>()
+            .iter()
+            .map(|v| v.and_then(date32_to_datetime).map_or_else(missing, |d| date_object(&d.date().to_string())))
+            .collect(),
+        DataType::Date64 => array
+            .as_primitive::<Date64Type>()
+            .iter()
+            .map(|v| v.and_then(date64_to_datetime).map_or_else(missing, |d| date_object(&d.date().to_string())))
+            .collect(),
+        DataType::Timestamp(unit, tz) => {
+            let values: Vec<Option<i64>> = match unit {
+                TimeUnit::Second => array.as_primitive::<TimestampSecondType>().iter().collect(),
+                TimeUnit::Millisecond => array.as_primitive::<TimestampMillisecondType>().iter().collect(),
+                TimeUnit::Microsecond => array.as_primitive::<TimestampMicrosecondType>().iter().collect(),
+                TimeUnit::Nanosecond => array.as_primitive::<TimestampNanosecondType>().iter().collect(),
+            };
+            values.into_iter().map(|v| v.map_or_else(missing, |v| timestamp(v, unit, tz.as_deref()))).collect()
+        }
+        DataType::Dictionary(..) => {
+            let dictionary = array.as_any_dictionary();
+            let values = column(dictionary.values().as_ref());
+            dictionary.normalized_keys().into_iter().map(|k| values.get(k).cloned().unwrap_or_else(missing)).collect()
+        }
+        DataType::List(_) => list::<i32>(array),
+        DataType::LargeList(_) => list::<i64>(array),
+        DataType::FixedSizeList(..) => {
+            let array = array.as_fixed_size_list();
+            (0..array.len()).map(|i| WolframValue::list(column(array.value(i).as_ref()))).collect()
+        }
+        DataType::Struct(fields) => {
+            let array = array.as_struct();
+            let columns: Vec<Vec<WolframValue>> = array.columns().iter().map(|c| column(c.as_ref())).collect();
+            (0..array.len())
+                .map(|i| {
+                    let entries = fields.iter().zip(columns.iter()).map(|(f, c)| (f.name().to_wolfram(), c[i].clone()));
+                    WolframValue::association_in_order(entries.collect())
+                })
+                .collect()
+        }
+        DataType::Map(..) => {
+            let array = array.as_map();
+            (0..array.len())
+                .map(|i| {
+                    let entries = array.value(i);
+                    let keys = column(entries.column(0).as_ref());
+                    let values = column(entries.column(1).as_ref());
+                    WolframValue::association_in_order(keys.into_iter().zip(values).collect())
+                })
+                .collect()
+        }
+        _ => (0..array.len()).map(|i| array_value_to_string(array, i).map_or_else(|_| missing(), |s| s.to_wolfram())).collect(),
+    };
+    if let Some(nulls) = array.logical_nulls() {
+        for i in nulls.iter().enumerate().filter(|(_, valid)| !valid).map(|(i, _)| i) {
+            out[i] = missing();
+        }
+    }
+    out
+}
+
+impl ToWolfram for RecordBatch {
+    /// `Dataset[{<|"column" -> value, ...|>, ...}]`
+    fn to_wolfram(&self) -> WolframValue {
+        let names: Vec<WolframValue> = self.schema().fields().iter().map(|f| f.name().to_wolfram()).collect();
+        let columns: Vec<Vec<WolframValue>> = self.columns().iter().map(|c| column(c.as_ref())).collect();
+        dataset(&names, &columns, self.num_rows())
+    }
+}
+
+/// `<|"column" -> NumericArray[...], ...|>` in schema order, columns with nulls or other types are lists
+pub fn record_batch_columns(batch: &RecordBatch) -> WolframValue {
+    let schema = batch.schema();
+    let entries = schema.fields().iter().zip(batch.columns()).map(|(field, array)| {
+        let items = column(array.as_ref());
+        let numeric = field.data_type().is_numeric() && items.iter().all(is_machine_number);
+        let values = if numeric && !items.is_empty() { WolframValue::NumericArray(items) } else { WolframValue::list(items) };
+        (field.name().to_wolfram(), values)
+    });
+    WolframValue::association_in_order(entries.collect())
+}
+
+/// The Arrow type of the [`CellType`]
+fn array(cells: &[Option<&WolframValue>]) -> ArrayRef {
+    match cell_type(cells) {
+        CellType::Null => Arc::new(NullArray::new(cells.len())),
+        CellType::Integer => Arc::new(cells.iter().map(|v| v.and_then(|v| i64::from_wolfram(v).ok())).collect::<Int64Array>()),
+        CellType::Real => Arc::new(cells.iter().map(|v| v.and_then(|v| f64::from_wolfram(v).ok())).collect::<Float64Array>()),
+        CellType::Boolean => Arc::new(cells.iter().map(|v| v.and_then(|v| bool::from_wolfram(v).ok())).collect::<BooleanArray>()),
+        CellType::Bytes => {
+            let bytes = cells.iter().map(|v| match v {
+                Some(WolframValue::Bytes(b)) => Some(b.as_slice()),
+                _ => None,
+            });
+            Arc::new(bytes.collect::<BinaryArray>())
+        }
+        CellType::Text => Arc::new(cells.iter().map(|v| v.map(text)).collect::<StringArray>()),
+    }
+}
+
+/// A `RecordBatch` from `Dataset[rows]`, a list of associations or an association of columns
+///
+/// The schema keeps the order of the columns, see [`WolframValue::association_in_order`].
+/// Missing values become nulls, a column takes the type of int64, float64, boolean, binary or utf8
+/// that holds all its values.
+pub fn to_record_batch(value: &WolframValue) -> Result<RecordBatch, ArrowError> {
+    let columns = table_columns(value).map_err(ArrowError::InvalidArgumentError)?;
+    let arrays: Vec<(String, ArrayRef)> = columns.iter().map(|(name, cells)| (name.clone(), array(cells))).collect();
+    let fields: Vec<Field> = arrays.iter().map(|(name, array)| Field::new(name.as_str(), array.data_type().clone(), true)).collect();
+    let schema = Arc::new(Schema::new(fields));
+    match arrays.is_empty() {
+        true => Ok(RecordBatch::new_empty(schema)),
+        false => RecordBatch::try_new(schema, arrays.into_iter().map(|(_, a)| a).collect()),
+    }
+}
+
+/// All batches of an Arrow IPC file or stream as one `Dataset`
+pub fn parse_arrow(input: &[u8]) -> Result<WolframValue, ArrowError> {
+    let (schema, batches) = if input.starts_with(b"ARROW1") {
+        let reader = FileReader::try_new(Cursor::new(input), None)?;
+        (reader.schema(), reader.collect::<Result<Vec<_>, _>>()?)
+    }
+    else {
+        let reader = StreamReader::try_new(input, None)?;
+        (reader.schema(), reader.collect::<Result<Vec<_>, _>>()?)
+    };
+    Ok(concat_batches(&schema, &batches)?.to_wolfram())
+}
+
+/// Write tabular `value` as an Arrow IPC file, see [`to_record_batch`]
+pub fn write_arrow<W: Write>(value: &WolframValue, out: &mut W) -> Result<(), ArrowError> {
+    let batch = to_record_batch(value)?;
+    let mut writer = FileWriter::try_new(out, &batch.schema())?;
+    writer.write(&batch)?;
+    writer.finish()
+}
+
+#[cfg(feature = "parquet")]
+pub use self::parquet::{parse_parquet, write_parquet};
+
+#[cfg(feature = "parquet")]
+mod parquet {
+    use super::to_record_batch;
+    use crate::{ToWolfram, WolframValue};
+    use arrow::{array::RecordBatchReader, compute::concat_batches};
+    use bytes::Bytes;
+    use parquet::{
+        arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter},
+        errors::ParquetError,
+    };
+    use std::io::Write;
+
+    /// Every row group of a Parquet file as one `Dataset`
+    pub fn parse_parquet(input: &[u8]) -> Result<WolframValue, ParquetError> {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(Bytes::copy_from_slice(input))?.build()?;
+        let schema = reader.schema();
+        let batches = reader.collect::<Result<Vec<_>, _>>()?;
+        Ok(concat_batches(&schema, &batches)?.to_wolfram())
+    }
+
+    /// Write tabular `value` as a Parquet file, see [`to_record_batch`]
+    pub fn write_parquet<W: Write + Send>(value: &WolframValue, out: W) -> Result<(), ParquetError> {
+        let batch = to_record_batch(value)?;
+        let mut writer = ArrowWriter::try_new(out, batch.schema(), None)?;
+        writer.write(&batch)?;
+        writer.close().map(|_| ())
+    }
+}
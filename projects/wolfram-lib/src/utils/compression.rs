@@ -0,0 +1,126 @@
+use crate::{EncodeOptions, WXFError, WolframValue};
+use flate2::{write::ZlibEncoder, Compress, Compression, FlushCompress, Status};
+use std::{
+    io::{BufWriter, Write},
+    thread,
+};
+
+/// How `8C:` output is deflated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressOptions {
+    /// zlib level from `0` (stored) to `9` (smallest)
+    pub level: u32,
+    /// Threads used to deflate, `1` streams from the encoder, `0` uses every available core
+    pub threads: usize,
+    /// Uncompressed bytes per thread when `threads` is not `1`
+    pub chunk_size: usize,
+    /// Choices made before compressing
+    pub encode: EncodeOptions,
+}
+
+impl Default for CompressOptions {
+    /// Same as `to_compressed`
+    fn default() -> Self {
+        Self { level: 9, threads: 1, chunk_size: 1 << 20, encode: EncodeOptions::default() }
+    }
+}
+
+impl CompressOptions {
+    /// Low CPU cost for payloads sent over a fast network
+    pub fn fast() -> Self {
+        Self { level: 1, ..Self::default() }
+    }
+    /// Deflate `chunk_size` pieces on every core, for very large payloads
+    pub fn parallel() -> Self {
+        Self { threads: 0, ..Self::default() }
+    }
+}
+
+impl WolframValue {
+    /// Write the `8C:` header and the deflated expression.
+    ///
+    /// With one thread the encoder streams into zlib, otherwise the expression is encoded first and deflated in chunks.
+    pub fn write_compressed_to<W: Write>(&self, out: &mut W, options: &CompressOptions) -> Result<(), WXFError> {
+        if options.level > 9 {
+            return Err(WXFError::Compression(format!("invalid level {}", options.level)));
+        }
+        out.write_all(b"8C:")?;
+        let threads = match options.threads {
+            0 => thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            n => n,
+        };
+        if threads == 1 {
+            // tokens are tiny, deflate them in larger pieces
+            let mut e = BufWriter::with_capacity(1 << 16, ZlibEncoder::new(out, Compression::new(options.level)));
+            self.write_bytes_with(&mut e, &options.encode)?;
+            let e = e.into_inner().map_err(|e| WXFError::Io(e.into_error()))?;
+            e.finish()?;
+            return Ok(());
+        }
+        let mut input = Vec::new();
+        self.write_bytes_with(&mut input, &options.encode)?;
+        write_parallel(&input, out, options.level, threads, options.chunk_size.max(1))
+    }
+}
+
+/// Chunks end with a sync flush so the raw deflate streams concatenate, only the last one is final
+fn write_parallel<W: Write>(input: &[u8], out: &mut W, level: u32, threads: usize, chunk_size: usize) -> Result<(), WXFError> {
+    let header = match level {
+        0..=1 => 0x01,
+        2..=5 => 0x5E,
+        6 => 0x9C,
+        _ => 0xDA,
+    };
+    out.write_all(&[0x78, header])?;
+    let chunks: Vec<&[u8]> = if input.is_empty() { vec![input] } else { input.chunks(chunk_size).collect() };
+    let last = chunks.len() - 1;
+    for (batch, group) in chunks.chunks(threads).enumerate() {
+        let deflated: Vec<_> = thread::scope(|s| {
+            let handles: Vec<_> = group
+                .iter()
+                .enumerate()
+                .map(|(i, chunk)| s.spawn(move || deflate(chunk, level, batch * threads + i == last)))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap_or_else(|_| Err(WXFError::Compression("worker panicked".to_string())))).collect()
+        });
+        for d in deflated {
+            out.write_all(&d?)?;
+        }
+    }
+    out.write_all(&adler32(input).to_be_bytes())?;
+    Ok(())
+}
+
+fn deflate(chunk: &[u8], level: u32, last: bool) -> Result<Vec<u8>, WXFError> {
+    let mut c = Compress::new(Compression::new(level), false);
+    let mut out = Vec::with_capacity(chunk.len() / 2 + 64);
+    let flush = if last { FlushCompress::Finish } else { FlushCompress::Sync };
+    loop {
+        let input = &chunk[c.total_in() as usize..];
+        let status = c.compress_vec(input, &mut out, flush).map_err(|e| WXFError::Compression(e.to_string()))?;
+        let done = match status {
+            Status::StreamEnd => true,
+            _ => !last && c.total_in() as usize == chunk.len() && out.len() < out.capacity(),
+        };
+        if done {
+            return Ok(out);
+        }
+        out.reserve(out.capacity().max(64));
+    }
+}
+
+/// Checksum of the uncompressed data at the end of a zlib stream
+fn adler32(input: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    // largest block before `b` can overflow
+    for block in input.chunks(5552) {
+        for &byte in block {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
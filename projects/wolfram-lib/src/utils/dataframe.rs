@@ -0,0 +1,137 @@
+use super::{
+    array_items, is_machine_number,
+    table::{cell_type, dataset, date, missing, present, table_columns, text, unix_time, CellType},
+};
+use crate::{FromWolfram, FromWolframError, ToWolfram, WolframValue};
+use polars::prelude::*;
+
+fn any_value(value: AnyValue) -> WolframValue {
+    match value {
+        AnyValue::Null => missing(),
+        AnyValue::Boolean(b) => b.to_wolfram(),
+        AnyValue::String(s) => s.to_wolfram(),
+        AnyValue::StringOwned(s) => s.as_str().to_wolfram(),
+        AnyValue::UInt8(n) => n.to_wolfram(),
+        AnyValue::UInt16(n) => n.to_wolfram(),
+        AnyValue::UInt32(n) => n.to_wolfram(),
+        AnyValue::UInt64(n) => n.to_wolfram(),
+        AnyValue::Int8(n) => n.to_wolfram(),
+        AnyValue::Int16(n) => n.to_wolfram(),
+        AnyValue::Int32(n) => n.to_wolfram(),
+        AnyValue::Int64(n) => n.to_wolfram(),
+        AnyValue::Int128(n) => n.to_wolfram(),
+        AnyValue::Float32(n) => n.to_wolfram(),
+        AnyValue::Float64(n) => n.to_wolfram(),
+        AnyValue::Date(days) => date(days as i64),
+        AnyValue::Datetime(v, unit, tz) => datetime(v, unit, tz.map(|tz| tz.as_str())),
+        AnyValue::DatetimeOwned(v, unit, tz) => datetime(v, unit, tz.as_ref().map(|tz| tz.as_str())),
+        AnyValue::List(series) => WolframValue::list(column(&series)),
+        AnyValue::Binary(b) => WolframValue::Bytes(b.to_vec()),
+        AnyValue::BinaryOwned(b) => WolframValue::Bytes(b),
+        other => other.to_string().to_wolfram(),
+    }
+}
+
+fn datetime(value: i64, unit: TimeUnit, tz: Option<&str>) -> WolframValue {
+    let per_second = match unit {
+        TimeUnit::Milliseconds => 1_000,
+        TimeUnit::Microseconds => 1_000_000,
+        TimeUnit::Nanoseconds => 1_000_000_000,
+    };
+    unix_time(value, per_second, tz)
+}
+
+fn cells<T: ToWolfram>(iter: impl Iterator<Item = Option<T>>) -> Vec<WolframValue> {
+    iter.map(|v| v.map_or_else(missing, |v| v.to_wolfram())).collect()
+}
+
+/// Every element of a series, nulls become `Missing["NotAvailable"]`
+fn column(series: &Series) -> Vec<WolframValue> {
+    // typed chunks where possible, `get` looks up the chunk for every cell
+    let typed = match series.dtype() {
+        DataType::Boolean => series.bool().map(|c| cells(c.iter())),
+        DataType::String => series.str().map(|c| cells(c.iter())),
+        DataType::UInt8 => series.u8().map(|c| cells(c.iter())),
+        DataType::UInt16 => series.u16().map(|c| cells(c.iter())),
+        DataType::UInt32 => series.u32().map(|c| cells(c.iter())),
+        DataType::UInt64 => series.u64().map(|c| cells(c.iter())),
+        DataType::Int8 => series.i8().map(|c| cells(c.iter())),
+        DataType::Int16 => series.i16().map(|c| cells(c.iter())),
+        DataType::Int32 => series.i32().map(|c| cells(c.iter())),
+        DataType::Int64 => series.i64().map(|c| cells(c.iter())),
+        DataType::Float32 => series.f32().map(|c| cells(c.iter())),
+        DataType::Float64 => series.f64().map(|c| cells(c.iter())),
+        DataType::Binary => series.binary().map(|c| c.iter().map(|v| v.map_or_else(missing, |b| WolframValue::Bytes(b.to_vec()))).collect()),
+        DataType::Date => series.date().map(|c| c.physical().iter().map(|v| v.map_or_else(missing, |days| date(days as i64))).collect()),
+        DataType::Datetime(unit, tz) => series.datetime().map(|c| {
+            let tz = tz.as_ref().map(|tz| tz.as_str());
+            c.physical().iter().map(|v| v.map_or_else(missing, |v| datetime(v, *unit, tz))).collect()
+        }),
+        DataType::List(_) => series.list().map(|c| c.into_iter().map(|v| v.map_or_else(missing, |s| WolframValue::list(column(&s)))).collect()),
+        _ => return (0..series.len()).map(|i| series.get(i).map_or_else(|_| missing(), any_value)).collect(),
+    };
+    typed.unwrap_or_default()
+}
+
+impl ToWolfram for Series {
+    /// A packed array for numeric series without nulls, a list otherwise
+    fn to_wolfram(&self) -> WolframValue {
+        let items = column(self);
+        let numeric = self.dtype().is_primitive_numeric() && self.null_count() == 0 && items.iter().all(is_machine_number);
+        if numeric && !items.is_empty() { WolframValue::PackedArray(items) } else { WolframValue::list(items) }
+    }
+}
+
+impl ToWolfram for DataFrame {
+    /// `Dataset[{<|"column" -> value, ...|>, ...}]`, see [`data_frame_columns`] for the columns
+    fn to_wolfram(&self) -> WolframValue {
+        let names: Vec<WolframValue> = self.get_column_names().iter().map(|name| name.as_str().to_wolfram()).collect();
+        let columns: Vec<Vec<WolframValue>> = self.get_columns().iter().map(|c| column(c.as_materialized_series())).collect();
+        dataset(&names, &columns, self.height())
+    }
+}
+
+/// `<|"column" -> {...}, ...|>` in frame order, numeric columns without nulls are packed
+pub fn data_frame_columns(frame: &DataFrame) -> WolframValue {
+    let entries = frame.get_columns().iter().map(|c| (c.name().as_str().to_wolfram(), c.as_materialized_series().to_wolfram()));
+    WolframValue::association_in_order(entries.collect())
+}
+
+/// The series of the [`CellType`], missing cells are nulls
+fn series(name: &str, cells: &[Option<&WolframValue>]) -> Series {
+    let name = PlSmallStr::from_str(name);
+    match cell_type(cells) {
+        CellType::Null => Series::new_null(name, cells.len()),
+        CellType::Integer => Series::new(name, cells.iter().map(|v| v.and_then(|v| i64::from_wolfram(v).ok())).collect::<Vec<_>>()),
+        CellType::Real => Series::new(name, cells.iter().map(|v| v.and_then(|v| f64::from_wolfram(v).ok())).collect::<Vec<_>>()),
+        CellType::Boolean => Series::new(name, cells.iter().map(|v| v.and_then(|v| bool::from_wolfram(v).ok())).collect::<Vec<_>>()),
+        CellType::Bytes => {
+            let bytes = cells.iter().map(|v| match v {
+                Some(WolframValue::Bytes(b)) => Some(b.as_slice()),
+                _ => None,
+            });
+            Series::new(name, bytes.collect::<Vec<_>>())
+        }
+        CellType::Text => Series::new(name, cells.iter().map(|v| v.map(text)).collect::<Vec<_>>()),
+    }
+}
+
+impl FromWolfram for Series {
+    /// A list or packed array, the column type is the narrowest of int64, float64, boolean, binary and string
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        match array_items(value) {
+            Some(items) => Ok(series("", &present(items))),
+            None => Err(FromWolframError { expected: "List", found: value.clone() }),
+        }
+    }
+}
+
+impl FromWolfram for DataFrame {
+    /// `Dataset[rows]`, a list of associations or an association of columns,
+    /// columns keep the order of the association or of the first row that has them
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        let error = || FromWolframError { expected: "Dataset, list of associations or association of columns", found: value.clone() };
+        let columns = table_columns(value).map_err(|_| error())?;
+        DataFrame::new(columns.iter().map(|(name, cells)| series(name, cells).into_column()).collect()).map_err(|_| error())
+    }
+}
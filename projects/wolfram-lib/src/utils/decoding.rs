@@ -0,0 +1,425 @@
+use crate::{
+    utils::normalized_symbol_name,
+    ArrayRef, DecodeError, DecodeLimit, ToWolfram, WolframValue, WolframValueRef,
+};
+use super::varint::decode_varint;
+use alloc::{boxed::Box, collections::BTreeMap, string::ToString, vec, vec::Vec};
+#[cfg(feature = "compression")]
+use flate2::read::ZlibDecoder;
+#[cfg(feature = "bigint")]
+use num::BigInt;
+#[cfg(feature = "compression")]
+use std::io::Read;
+
+/// Bounds on untrusted input, exceeding any of them fails with [`DecodeError::LimitExceeded`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// Nesting of functions and associations
+    pub max_depth: usize,
+    /// Total number of expressions, array elements and the lists holding them included
+    pub max_elements: usize,
+    /// Bytes in a single string, symbol or byte array
+    pub max_length: usize,
+    /// Decimal digits of a single big integer
+    pub max_big_integer_digits: usize,
+    /// Bytes after inflating an `8C:` payload
+    pub max_decompressed_size: usize,
+}
+
+impl Default for DecodeLimits {
+    /// Depth is bounded by the default `$RecursionLimit`, elements to 2^26 and inflated `8C:` payloads to 1 GiB.
+    ///
+    /// Lengths and big integer digits are only bounded by the size of the input.
+    fn default() -> Self {
+        Self { max_depth: 1024, max_elements: 1 << 26, max_decompressed_size: 1 << 30, ..Self::unlimited() }
+    }
+}
+
+impl DecodeLimits {
+    pub fn unlimited() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_elements: usize::MAX,
+            max_length: usize::MAX,
+            max_big_integer_digits: usize::MAX,
+            max_decompressed_size: usize::MAX,
+        }
+    }
+}
+
+impl WolframValue {
+    /// Read an expression written by `BinarySerialize`, with or without compression
+    pub fn from_bytes(input: &[u8]) -> Result<WolframValue, DecodeError> {
+        WolframValue::from_bytes_with(input, &DecodeLimits::default())
+    }
+    /// `8C:` input fails with [`DecodeError::Compressed`] without the `compression` feature
+    pub fn from_bytes_with(input: &[u8], limits: &DecodeLimits) -> Result<WolframValue, DecodeError> {
+        #[cfg(feature = "compression")]
+        if input.starts_with(b"8C:") {
+            let buffer = inflate(input, limits)?;
+            return Decoder::new(&buffer[2..], limits).read_document();
+        }
+        #[cfg(not(feature = "compression"))]
+        if input.starts_with(b"8C:") {
+            return Err(DecodeError::Compressed);
+        }
+        match input.strip_prefix(b"8:") {
+            Some(body) => Decoder::new(body, limits).read_document(),
+            None => Err(DecodeError::InvalidHeader),
+        }
+    }
+}
+
+impl<'a> WolframValueRef<'a> {
+    /// Borrow an uncompressed expression, `8C:` input must go through [`inflate`] first
+    pub fn from_bytes(input: &'a [u8]) -> Result<WolframValueRef<'a>, DecodeError> {
+        WolframValueRef::from_bytes_with(input, &DecodeLimits::default())
+    }
+    pub fn from_bytes_with(input: &'a [u8], limits: &DecodeLimits) -> Result<WolframValueRef<'a>, DecodeError> {
+        if input.starts_with(b"8C:") {
+            return Err(DecodeError::Compressed);
+        }
+        match input.strip_prefix(b"8:") {
+            Some(body) => Decoder::new(body, limits).read_document_ref(),
+            None => Err(DecodeError::InvalidHeader),
+        }
+    }
+}
+
+/// Turn `8C:` input into the same expression with an `8:` header, other input is copied as it is
+#[cfg(feature = "compression")]
+pub fn inflate(input: &[u8], limits: &DecodeLimits) -> Result<Vec<u8>, DecodeError> {
+    let body = match input.strip_prefix(b"8C:") {
+        Some(body) => body,
+        None => return Ok(input.to_vec()),
+    };
+    let mut buffer = b"8:".to_vec();
+    let max = limits.max_decompressed_size;
+    if let Err(e) = ZlibDecoder::new(body).take((max as u64).saturating_add(1)).read_to_end(&mut buffer) {
+        return Err(DecodeError::Decompress(e.to_string()));
+    }
+    if buffer.len() - 2 > max {
+        return Err(DecodeError::LimitExceeded { limit: DecodeLimit::DecompressedSize, offset: max });
+    }
+    Ok(buffer)
+}
+
+/// Expression without a header
+#[cfg(feature = "std")]
+pub(crate) fn decode_body(body: &[u8], limits: &DecodeLimits) -> Result<WolframValue, DecodeError> {
+    Decoder::new(body, limits).read_document()
+}
+
+/// Expression at the start of `body` and the number of bytes it takes
+#[cfg(feature = "compression")]
+pub(crate) fn decode_prefix(body: &[u8], limits: &DecodeLimits) -> Result<(WolframValue, usize), DecodeError> {
+    let mut decoder = Decoder::new(body, limits);
+    let out = decoder.read_value()?;
+    Ok((out, decoder.offset))
+}
+
+struct Decoder<'i> {
+    input: &'i [u8],
+    offset: usize,
+    limits: DecodeLimits,
+    depth: usize,
+    elements: usize,
+}
+
+impl<'i> Decoder<'i> {
+    fn new(input: &'i [u8], limits: &DecodeLimits) -> Self {
+        Self { input, offset: 0, limits: *limits, depth: 0, elements: 0 }
+    }
+    fn check(&self, limit: DecodeLimit, value: usize, max: usize, offset: usize) -> Result<(), DecodeError> {
+        if value > max { Err(DecodeError::LimitExceeded { limit, offset }) } else { Ok(()) }
+    }
+    /// Count `n` more expressions against the element limit
+    fn count_elements(&mut self, n: usize, offset: usize) -> Result<(), DecodeError> {
+        self.elements = self.elements.saturating_add(n);
+        self.check(DecodeLimit::Elements, self.elements, self.limits.max_elements, offset)
+    }
+    fn read_document(mut self) -> Result<WolframValue, DecodeError> {
+        let out = self.read_value()?;
+        if self.offset != self.input.len() {
+            return Err(DecodeError::TrailingBytes { offset: self.offset });
+        }
+        Ok(out)
+    }
+    /// Only functions and associations recurse, atoms are read elsewhere to keep this frame small
+    fn read_value(&mut self) -> Result<WolframValue, DecodeError> {
+        let offset = self.offset;
+        self.count_elements(1, offset)?;
+        self.depth += 1;
+        self.check(DecodeLimit::Depth, self.depth, self.limits.max_depth, offset)?;
+        let out = match self.read_byte()? {
+            b'f' => self.read_function(),
+            b'A' => self.read_association(),
+            token => self.read_atom(token, offset),
+        };
+        self.depth -= 1;
+        out
+    }
+    fn read_function(&mut self) -> Result<WolframValue, DecodeError> {
+        let n = self.read_length()?;
+        let head = self.read_value()?;
+        let mut args = Vec::with_capacity(n.min(self.remaining()));
+        for _ in 0..n {
+            args.push(self.read_value()?)
+        }
+        Ok(WolframValue::Function(Box::new(head), args))
+    }
+    fn read_association(&mut self) -> Result<WolframValue, DecodeError> {
+        let n = self.read_length()?;
+        let mut map = BTreeMap::new();
+        for _ in 0..n {
+            let offset = self.offset;
+            let rule = match self.read_byte()? {
+                b'-' => WolframValue::Rule,
+                b':' => WolframValue::RuleDelayed,
+                token => return Err(DecodeError::InvalidToken { token, offset }),
+            };
+            let key = self.read_value()?;
+            let value = self.read_value()?;
+            map.insert(key, (rule, value));
+        }
+        Ok(WolframValue::Association(map))
+    }
+    #[inline(never)]
+    fn read_atom(&mut self, token: u8, offset: usize) -> Result<WolframValue, DecodeError> {
+        let out = match token {
+            b'S' => WolframValue::String(self.read_str()?.to_string()),
+            b's' => WolframValue::Symbol(normalized_symbol_name(self.read_str()?)),
+            b'B' => {
+                let n = self.read_length()?;
+                self.check(DecodeLimit::Length, n, self.limits.max_length, offset)?;
+                WolframValue::Bytes(self.read_slice(n)?.to_vec())
+            }
+            b'I' => {
+                let offset = self.offset;
+                let digits = self.read_str()?;
+                self.check(DecodeLimit::BigIntegerDigits, digits.len(), self.limits.max_big_integer_digits, offset)?;
+                big_integer(digits).ok_or(DecodeError::InvalidNumber { offset })?
+            }
+            b'R' => WolframValue::BigDecimal(self.read_str()?.to_string()),
+            b'C' => WolframValue::Integer8(i8::from_le_bytes(self.read_array()?)),
+            b'j' => WolframValue::Integer16(i16::from_le_bytes(self.read_array()?)),
+            b'i' => WolframValue::Integer32(i32::from_le_bytes(self.read_array()?)),
+            b'L' => WolframValue::Integer64(i64::from_le_bytes(self.read_array()?)),
+            b'r' => WolframValue::Decimal64(self.read_array()?),
+            0xC1 | 0xC2 => {
+                let a = self.read_array_ref()?;
+                array_to_owned(token == 0xC2, a.element_type, &a.dimensions, a.data)
+            }
+            token => return Err(DecodeError::InvalidToken { token, offset }),
+        };
+        Ok(out)
+    }
+    fn read_document_ref(mut self) -> Result<WolframValueRef<'i>, DecodeError> {
+        let out = self.read_ref()?;
+        if self.offset != self.input.len() {
+            return Err(DecodeError::TrailingBytes { offset: self.offset });
+        }
+        Ok(out)
+    }
+    /// Same as `read_value`, without copying
+    fn read_ref(&mut self) -> Result<WolframValueRef<'i>, DecodeError> {
+        let offset = self.offset;
+        self.count_elements(1, offset)?;
+        self.depth += 1;
+        self.check(DecodeLimit::Depth, self.depth, self.limits.max_depth, offset)?;
+        let out = match self.read_byte()? {
+            b'f' => self.read_function_ref(),
+            b'A' => self.read_association_ref(),
+            token => self.read_atom_ref(token, offset),
+        };
+        self.depth -= 1;
+        out
+    }
+    fn read_function_ref(&mut self) -> Result<WolframValueRef<'i>, DecodeError> {
+        let n = self.read_length()?;
+        let head = self.read_ref()?;
+        let mut args = Vec::with_capacity(n.min(self.remaining()));
+        for _ in 0..n {
+            args.push(self.read_ref()?)
+        }
+        Ok(WolframValueRef::Function(Box::new(head), args))
+    }
+    fn read_association_ref(&mut self) -> Result<WolframValueRef<'i>, DecodeError> {
+        let n = self.read_length()?;
+        let mut items = Vec::with_capacity(n.min(self.remaining()));
+        for _ in 0..n {
+            let offset = self.offset;
+            let rule = match self.read_byte()? {
+                b'-' => WolframValueRef::Rule,
+                b':' => WolframValueRef::RuleDelayed,
+                token => return Err(DecodeError::InvalidToken { token, offset }),
+            };
+            let key = self.read_ref()?;
+            let value = self.read_ref()?;
+            items.push((key, (rule, value)));
+        }
+        Ok(WolframValueRef::Association(items))
+    }
+    #[inline(never)]
+    fn read_atom_ref(&mut self, token: u8, offset: usize) -> Result<WolframValueRef<'i>, DecodeError> {
+        let out = match token {
+            b'S' => WolframValueRef::String(self.read_str()?),
+            b's' => WolframValueRef::Symbol(self.read_str()?),
+            b'B' => {
+                let n = self.read_length()?;
+                self.check(DecodeLimit::Length, n, self.limits.max_length, offset)?;
+                WolframValueRef::Bytes(self.read_slice(n)?)
+            }
+            b'I' => WolframValueRef::BigInteger(self.read_digits()?),
+            b'R' => WolframValueRef::BigDecimal(self.read_str()?),
+            b'C' => WolframValueRef::Integer8(i8::from_le_bytes(self.read_array()?)),
+            b'j' => WolframValueRef::Integer16(i16::from_le_bytes(self.read_array()?)),
+            b'i' => WolframValueRef::Integer32(i32::from_le_bytes(self.read_array()?)),
+            b'L' => WolframValueRef::Integer64(i64::from_le_bytes(self.read_array()?)),
+            b'r' => WolframValueRef::Decimal64(self.read_array()?),
+            0xC1 => WolframValueRef::PackedArray(self.read_array_ref()?),
+            0xC2 => WolframValueRef::NumericArray(self.read_array_ref()?),
+            token => return Err(DecodeError::InvalidToken { token, offset }),
+        };
+        Ok(out)
+    }
+    /// Big integer digits, checked against the limit and parsed
+    fn read_digits(&mut self) -> Result<&'i str, DecodeError> {
+        let offset = self.offset;
+        let digits = self.read_str()?;
+        self.check(DecodeLimit::BigIntegerDigits, digits.len(), self.limits.max_big_integer_digits, offset)?;
+        match big_integer(digits) {
+            Some(_) => Ok(digits),
+            None => Err(DecodeError::InvalidNumber { offset }),
+        }
+    }
+    fn read_array_ref(&mut self) -> Result<ArrayRef<'i>, DecodeError> {
+        let offset = self.offset;
+        let token = self.read_byte()?;
+        let size = match array_type(token) {
+            Some((_, size)) => size,
+            None => return Err(DecodeError::InvalidArrayType { token, offset }),
+        };
+        let rank = self.read_length()?;
+        let mut dims = Vec::with_capacity(rank.min(self.remaining()));
+        for _ in 0..rank {
+            dims.push(self.read_length()?)
+        }
+        let count = dims.iter().try_fold(1usize, |a, b| a.checked_mul(*b)).ok_or(DecodeError::InvalidNumber { offset })?;
+        // `nest` builds a list for every index of the leading dimensions, even when the array is empty
+        let lists = dims.iter().take(rank.saturating_sub(1)).scan(1usize, |n, d| {
+            *n = n.saturating_mul(*d);
+            Some(*n)
+        });
+        self.count_elements(lists.fold(count, usize::saturating_add), offset)?;
+        let data = self.read_slice(count.checked_mul(size).ok_or(DecodeError::InvalidNumber { offset })?)?;
+        Ok(ArrayRef { element_type: token, dimensions: dims, data })
+    }
+    fn read_byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.read_slice(1)?[0])
+    }
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut out = [0; N];
+        out.copy_from_slice(self.read_slice(N)?);
+        Ok(out)
+    }
+    fn read_slice(&mut self, n: usize) -> Result<&'i [u8], DecodeError> {
+        if n > self.remaining() {
+            return Err(DecodeError::UnexpectedEnd { offset: self.input.len() });
+        }
+        let out = &self.input[self.offset..self.offset + n];
+        self.offset += n;
+        Ok(out)
+    }
+    fn read_length(&mut self) -> Result<usize, DecodeError> {
+        match decode_varint(&self.input[self.offset..]) {
+            Some((n, size)) => {
+                self.offset += size;
+                Ok(n)
+            }
+            None if self.remaining() < 10 => Err(DecodeError::UnexpectedEnd { offset: self.input.len() }),
+            None => Err(DecodeError::InvalidNumber { offset: self.offset }),
+        }
+    }
+    fn read_str(&mut self) -> Result<&'i str, DecodeError> {
+        let n = self.read_length()?;
+        let offset = self.offset;
+        self.check(DecodeLimit::Length, n, self.limits.max_length, offset)?;
+        core::str::from_utf8(self.read_slice(n)?).map_err(|_| DecodeError::InvalidUtf8 { offset })
+    }
+    fn remaining(&self) -> usize {
+        self.input.len() - self.offset
+    }
+}
+
+#[cfg(feature = "bigint")]
+pub(crate) fn big_integer(digits: &str) -> Option<WolframValue> {
+    digits.parse::<BigInt>().ok().map(WolframValue::BigInteger)
+}
+
+/// Without `bigint` only digits in the range of `i64` can be read
+#[cfg(not(feature = "bigint"))]
+pub(crate) fn big_integer(digits: &str) -> Option<WolframValue> {
+    digits.parse::<i64>().ok().map(WolframValue::Integer64)
+}
+
+/// Packed arrays become nested lists, numeric arrays `NumericArray[list, "Type"]`
+pub(crate) fn array_to_owned(numeric: bool, token: u8, dims: &[usize], data: &[u8]) -> WolframValue {
+    let (name, size) = array_type(token).unwrap_or(("", 1));
+    let items: Vec<WolframValue> = data.chunks_exact(size).map(|c| array_element(token, c)).collect();
+    let list = nest(dims, &items);
+    if numeric { WolframValue::function("NumericArray", vec![list, name.to_wolfram()]) } else { list }
+}
+
+pub(crate) fn array_type(token: u8) -> Option<(&'static str, usize)> {
+    let out = match token {
+        0x00 => ("Integer8", 1),
+        0x01 => ("Integer16", 2),
+        0x02 => ("Integer32", 4),
+        0x03 => ("Integer64", 8),
+        0x10 => ("UnsignedInteger8", 1),
+        0x11 => ("UnsignedInteger16", 2),
+        0x12 => ("UnsignedInteger32", 4),
+        0x13 => ("UnsignedInteger64", 8),
+        0x22 => ("Real32", 4),
+        0x23 => ("Real64", 8),
+        0x33 => ("ComplexReal32", 8),
+        0x34 => ("ComplexReal64", 16),
+        _ => return None,
+    };
+    Some(out)
+}
+
+/// Inverse of `array_type`
+pub(crate) fn array_token(name: &str) -> Option<u8> {
+    [0x00, 0x01, 0x02, 0x03, 0x10, 0x11, 0x12, 0x13, 0x22, 0x23, 0x33, 0x34].iter().copied().find(|t| array_type(*t).map(|(n, _)| n) == Some(name))
+}
+
+fn array_element(token: u8, c: &[u8]) -> WolframValue {
+    let f32_at = |i: usize| f32::from_le_bytes([c[i], c[i + 1], c[i + 2], c[i + 3]]);
+    let f64_at = |i: usize| f64::from_le_bytes([c[i], c[i + 1], c[i + 2], c[i + 3], c[i + 4], c[i + 5], c[i + 6], c[i + 7]]);
+    match token {
+        0x00 => (c[0] as i8).to_wolfram(),
+        0x01 => i16::from_le_bytes([c[0], c[1]]).to_wolfram(),
+        0x02 => i32::from_le_bytes([c[0], c[1], c[2], c[3]]).to_wolfram(),
+        0x03 => i64::from_le_bytes([c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]]).to_wolfram(),
+        0x10 => c[0].to_wolfram(),
+        0x11 => u16::from_le_bytes([c[0], c[1]]).to_wolfram(),
+        0x12 => u32::from_le_bytes([c[0], c[1], c[2], c[3]]).to_wolfram(),
+        0x13 => u64::from_le_bytes([c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]]).to_wolfram(),
+        0x22 => f32_at(0).to_wolfram(),
+        0x23 => f64_at(0).to_wolfram(),
+        0x33 => WolframValue::function("Complex", vec![f32_at(0), f32_at(4)]),
+        _ => WolframValue::function("Complex", vec![f64_at(0), f64_at(8)]),
+    }
+}
+
+pub(crate) fn nest(dims: &[usize], items: &[WolframValue]) -> WolframValue {
+    match dims.split_first() {
+        Some((_, [])) | None => WolframValue::list(items.to_vec()),
+        Some((n, rest)) => {
+            let size = rest.iter().product::<usize>();
+            WolframValue::list((0..*n).map(|i| nest(rest, &items[i * size..(i + 1) * size])).collect())
+        }
+    }
+}
@@ -1,121 +1,502 @@
-use crate::{utils::SYSTEM_SYMBOLS, WolframValue};
-use flate2::{write::ZlibEncoder, Compression};
-use integer_encoding::VarInt;
-use std::{collections::BTreeSet, io::Write, mem::transmute};
+use super::varint::encode_varint;
+#[cfg(feature = "compression")]
+use crate::utils::CompressOptions;
+use crate::{
+    utils::{array_token, array_type, canonical_order, SYSTEM_SYMBOLS},
+    WXFError, WolframValue,
+};
+use alloc::{collections::BTreeMap, format, string::ToString, vec, vec::Vec};
+use core::convert::TryFrom;
+#[cfg(feature = "bigint")]
+use num::ToPrimitive;
 
-impl WolframValue {
-    pub fn to_string(&self) -> String {
-        format!("{}", self)
+/// Destination of the encoder, every `std::io::Write` with the `std` feature, a `Vec<u8>` without
+///
+/// Implement it for a fixed buffer or a serial port in `no_std` builds.
+pub trait WXFWrite {
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), WXFError>;
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> WXFWrite for W {
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), WXFError> {
+        std::io::Write::write_all(self, buf).map_err(WXFError::from)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl WXFWrite for Vec<u8> {
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), WXFError> {
+        self.extend_from_slice(buf);
+        Ok(())
+    }
+}
+
+/// Choices made while writing bytes, the default keeps every value as it is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EncodeOptions {
+    /// Write every integer with the narrowest token that holds it
+    pub canonical_integers: bool,
+    /// Drop the `System`` context from system symbols
+    pub canonical_symbols: bool,
+    /// Write association entries in the order of `KeySort`
+    pub canonical_associations: bool,
+    /// Fail with [`WXFError::InvalidSymbol`] on names Mathematica cannot read, instead of writing them as they are
+    pub validate_symbols: bool,
+}
+
+impl EncodeOptions {
+    /// Same bytes as `BinarySerialize` in Mathematica, except for associations.
+    ///
+    /// `WolframValue::Association` does not keep insertion order, so the bytes of an association
+    /// are those of `BinarySerialize[KeySort[assoc]]`, not of `BinarySerialize[assoc]`.
+    pub fn canonical() -> Self {
+        Self { canonical_integers: true, canonical_symbols: true, canonical_associations: true, validate_symbols: false }
+    }
+    /// Options of the `try_*` methods without arguments
+    pub fn validated() -> Self {
+        Self { validate_symbols: true, ..Self::default() }
     }
+}
+
+impl WolframValue {
+    /// # Panics
+    ///
+    /// If the value has no WXF form, see [`WolframValue::try_to_bytes`]
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut out = Vec::new();
-        out.extend_from_slice(b"8:");
-        self.write_bytes_inner(&mut out);
-        return out;
+        self.to_bytes_with(&EncodeOptions::default())
     }
+    /// # Panics
+    ///
+    /// If the value has no WXF form, see [`WolframValue::try_to_compressed`]
+    #[cfg(feature = "compression")]
     pub fn to_compressed(&self) -> Vec<u8> {
-        let mut input = Vec::new();
-        let mut e = ZlibEncoder::new(vec![], Compression::new(9));
-        self.write_bytes_inner(&mut input);
-        let mut out = Vec::with_capacity(input.len());
-        match e.write_all(&input) {
-            Ok(_) => out.extend_from_slice(b"8C:"),
-            Err(..) => {}
-        };
-        match e.finish() {
-            Ok(o) => out.extend_from_slice(&o),
-            Err(..) => {
-                panic!()
-            }
-        };
-        return out;
+        self.to_compressed_with(&CompressOptions::default())
+    }
+    pub fn to_bytes_with(&self, options: &EncodeOptions) -> Vec<u8> {
+        self.try_to_bytes_with(options).unwrap_or_else(|e| panic!("{}", e))
+    }
+    #[cfg(feature = "compression")]
+    pub fn to_compressed_with(&self, options: &CompressOptions) -> Vec<u8> {
+        self.try_to_compressed_with(options).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Symbol names are validated, unlike [`WolframValue::to_bytes`]
+    pub fn try_to_bytes(&self) -> Result<Vec<u8>, WXFError> {
+        self.try_to_bytes_with(&EncodeOptions::validated())
+    }
+    #[cfg(feature = "compression")]
+    pub fn try_to_compressed(&self) -> Result<Vec<u8>, WXFError> {
+        self.try_to_compressed_with(&CompressOptions { encode: EncodeOptions::validated(), ..CompressOptions::default() })
+    }
+    pub fn try_to_bytes_with(&self, options: &EncodeOptions) -> Result<Vec<u8>, WXFError> {
+        let mut out = Vec::new();
+        self.write_to(&mut out, options)?;
+        Ok(out)
+    }
+    #[cfg(feature = "compression")]
+    pub fn try_to_compressed_with(&self, options: &CompressOptions) -> Result<Vec<u8>, WXFError> {
+        let mut out = Vec::new();
+        self.write_compressed_to(&mut out, options)?;
+        Ok(out)
+    }
+    /// Write the `8:` header and the expression, nothing is allocated except for canonical association order.
+    ///
+    /// Every token is a separate `write_all`, wrap files and sockets in a `BufWriter`,
+    /// a `bytes::BufMut` can be written through `BufMut::writer`.
+    pub fn write_to<W: WXFWrite + ?Sized>(&self, out: &mut W, options: &EncodeOptions) -> Result<(), WXFError> {
+        out.write_all(b"8:")?;
+        self.write_bytes_with(out, options)
+    }
+    /// Length of `to_bytes`, the header included
+    pub fn encoded_len(&self) -> Result<usize, WXFError> {
+        self.encoded_len_with(&EncodeOptions::default())
+    }
+    /// Length of `to_bytes_with(options)`, for `Vec::with_capacity` ahead of `write_to`
+    pub fn encoded_len_with(&self, options: &EncodeOptions) -> Result<usize, WXFError> {
+        let mut counter = ByteCounter(0);
+        self.write_to(&mut counter, options)?;
+        Ok(counter.0)
     }
+    /// # Panics
+    ///
+    /// If the value has no WXF form, see [`WolframValue::write_bytes_with`]
     pub fn write_bytes_inner(&self, out: &mut Vec<u8>) {
+        self.write_bytes_with(out, &EncodeOptions::default()).unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Write the expression without a header
+    pub fn write_bytes_with<W: WXFWrite + ?Sized>(&self, out: &mut W, options: &EncodeOptions) -> Result<(), WXFError> {
+        // deep expressions recurse through here, so the frame is kept small
         match self {
-            WolframValue::Skip => (),
-            WolframValue::Function(head, args) => {
-                out.push(b'f');
-                out.extend_from_slice(&args.len().encode_var_vec());
-                head.write_bytes_inner(out);
-                for v in args {
-                    v.write_bytes_inner(out)
-                }
-            }
-            WolframValue::String(s) => {
-                let len = s.len().encode_var_vec();
-                out.push(b'S');
-                out.extend_from_slice(&len);
-                out.extend_from_slice(s.as_bytes());
-            }
-            WolframValue::Bytes(v) => {
-                let len = v.len().encode_var_vec();
-                out.push(b'B');
-                out.extend_from_slice(&len);
-                out.extend_from_slice(&v);
-            }
-            WolframValue::Symbol(symbol) => {
-                let s = standardized_symbol_name(symbol);
-                let len = symbol.len().encode_var_vec();
-                out.push(b's');
-                out.extend_from_slice(&len);
-                out.extend_from_slice(s.as_bytes());
-            }
-            WolframValue::Integer8(n) => {
-                out.push(b'C');
-                let le: [u8; 1] = unsafe { transmute(n.to_le()) };
-                out.extend_from_slice(&le);
-            }
-            WolframValue::Integer16(n) => {
-                out.push(b'j');
-                let le: [u8; 2] = unsafe { transmute(n.to_le()) };
-                out.extend_from_slice(&le);
-            }
-            WolframValue::Integer32(n) => {
-                out.push(b'i');
-                let le: [u8; 4] = unsafe { transmute(n.to_le()) };
-                out.extend_from_slice(&le);
-            }
-            WolframValue::Integer64(n) => {
-                out.push(b'L');
-                let le: [u8; 8] = unsafe { transmute(n.to_le()) };
-                out.extend_from_slice(&le);
+            WolframValue::Function(head, args) => write_function(head, args, out, options),
+            WolframValue::Association(dict) => write_association(dict, out, options),
+            _ => write_atom(self, out, options),
+        }
+    }
+}
+
+/// Sink for `encoded_len`
+struct ByteCounter(usize);
+
+impl WXFWrite for ByteCounter {
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), WXFError> {
+        self.0 += buf.len();
+        Ok(())
+    }
+}
+
+#[inline(never)]
+fn write_function<W: WXFWrite + ?Sized>(head: &WolframValue, args: &[WolframValue], out: &mut W, options: &EncodeOptions) -> Result<(), WXFError> {
+    if let Some((token, items, dims)) = numeric_array(head, args)? {
+        out.write_all(&[0xC2, token])?;
+        write_varint(dims.len(), out)?;
+        for d in &dims {
+            write_varint(*d, out)?;
+        }
+        return write_typed_elements(token, items, out);
+    }
+    write_token(b'f', args.len(), out)?;
+    head.write_bytes_with(out, options)?;
+    for v in args {
+        v.write_bytes_with(out, options)?
+    }
+    Ok(())
+}
+
+#[inline(never)]
+fn write_association<W: WXFWrite + ?Sized>(
+    dict: &BTreeMap<WolframValue, (WolframValue, WolframValue)>,
+    out: &mut W,
+    options: &EncodeOptions,
+) -> Result<(), WXFError> {
+    write_token(b'A', dict.len(), out)?;
+    let mut write_entry = |k: &WolframValue, r: &WolframValue, v: &WolframValue| -> Result<(), WXFError> {
+        match r {
+            WolframValue::Rule => out.write_all(b"-")?,
+            WolframValue::RuleDelayed => out.write_all(b":")?,
+            _ => return Err(WXFError::UnsupportedValue(format!("{} is not a rule of an association entry", r))),
+        }
+        k.write_bytes_with(out, options)?;
+        v.write_bytes_with(out, options)
+    };
+    if options.canonical_associations {
+        let mut items: Vec<_> = dict.iter().collect();
+        items.sort_by(|(a, _), (b, _)| canonical_order(a, b));
+        for (k, (r, v)) in items {
+            write_entry(k, r, v)?
+        }
+    }
+    else {
+        for (k, (r, v)) in dict {
+            write_entry(k, r, v)?
+        }
+    }
+    Ok(())
+}
+
+#[inline(never)]
+fn write_atom<W: WXFWrite + ?Sized>(value: &WolframValue, out: &mut W, options: &EncodeOptions) -> Result<(), WXFError> {
+    match value {
+        WolframValue::Skip => return Err(WXFError::UnsupportedValue("Skip has no WXF form".to_string())),
+        WolframValue::String(s) => {
+            write_token(b'S', s.len(), out)?;
+            out.write_all(s.as_bytes())?;
+        }
+        WolframValue::Bytes(v) => {
+            write_token(b'B', v.len(), out)?;
+            out.write_all(v)?;
+        }
+        WolframValue::Symbol(symbol) => {
+            if options.validate_symbols && !is_valid_symbol(symbol) {
+                return Err(WXFError::InvalidSymbol(symbol.to_string()));
             }
-            WolframValue::BigInteger(i) => {
-                out.push(b'I');
+            let (context, name) = match symbol.strip_prefix("System`") {
+                Some(name) if options.canonical_symbols && is_system_symbol(name) => ("", name),
+                _ => standardized_symbol_name(symbol),
+            };
+            write_token(b's', context.len() + name.len(), out)?;
+            out.write_all(context.as_bytes())?;
+            out.write_all(name.as_bytes())?;
+        }
+        WolframValue::Integer8(n) => write_integer(b'C', &n.to_le_bytes(), out)?,
+        WolframValue::Integer16(n) if options.canonical_integers => write_narrowest(*n as i64, out)?,
+        WolframValue::Integer16(n) => write_integer(b'j', &n.to_le_bytes(), out)?,
+        WolframValue::Integer32(n) if options.canonical_integers => write_narrowest(*n as i64, out)?,
+        WolframValue::Integer32(n) => write_integer(b'i', &n.to_le_bytes(), out)?,
+        WolframValue::Integer64(n) if options.canonical_integers => write_narrowest(*n, out)?,
+        WolframValue::Integer64(n) => write_integer(b'L', &n.to_le_bytes(), out)?,
+        #[cfg(feature = "bigint")]
+        WolframValue::BigInteger(i) => match i.to_i64() {
+            Some(n) if options.canonical_integers => write_narrowest(n, out)?,
+            _ => {
                 let n = i.to_str_radix(10);
-                let len = n.len().encode_var_vec();
-                out.extend_from_slice(&len);
-                out.extend_from_slice(n.as_bytes());
+                write_token(b'I', n.len(), out)?;
+                out.write_all(n.as_bytes())?;
             }
-            WolframValue::Decimal64(s) => {
-                out.push(b'r');
-                out.extend_from_slice(s);
+        },
+        WolframValue::Decimal64(s) => write_integer(b'r', s, out)?,
+        WolframValue::BigDecimal(d) => {
+            write_token(b'R', d.len(), out)?;
+            out.write_all(d.as_bytes())?;
+        }
+        WolframValue::PackedArray(v) => write_array(0xC1, v, out, options)?,
+        WolframValue::NumericArray(v) => write_array(0xC2, v, out, options)?,
+        WolframValue::Rule | WolframValue::RuleDelayed => return Err(WXFError::UnsupportedValue(format!("bare {} outside of an association", value))),
+        WolframValue::Function(..) | WolframValue::Association(_) => value.write_bytes_with(out, options)?,
+    }
+    Ok(())
+}
+
+/// Token followed by a varint, encoded on the stack
+fn write_token<W: WXFWrite + ?Sized>(token: u8, length: usize, out: &mut W) -> Result<(), WXFError> {
+    let mut buffer = [0u8; 11];
+    buffer[0] = token;
+    let n = encode_varint(length, &mut buffer[1..]);
+    out.write_all(&buffer[..=n])
+}
+
+fn write_varint<W: WXFWrite + ?Sized>(length: usize, out: &mut W) -> Result<(), WXFError> {
+    let mut buffer = [0u8; 10];
+    let n = encode_varint(length, &mut buffer);
+    out.write_all(&buffer[..n])
+}
+
+fn write_integer<W: WXFWrite + ?Sized>(token: u8, bytes: &[u8], out: &mut W) -> Result<(), WXFError> {
+    let mut buffer = [0u8; 9];
+    buffer[0] = token;
+    buffer[1..=bytes.len()].copy_from_slice(bytes);
+    out.write_all(&buffer[..=bytes.len()])
+}
+
+/// Rectangular nested lists of machine integers or reals
+///
+/// Mathematica cannot pack an array with an empty dimension, such a packed array is written as lists.
+fn write_array<W: WXFWrite + ?Sized>(token: u8, items: &[WolframValue], out: &mut W, options: &EncodeOptions) -> Result<(), WXFError> {
+    let dims = array_dims(items);
+    let mut width = 0u8;
+    let mut real = false;
+    scan_array(items, &dims, &mut width, &mut real)?;
+    if token == 0xC1 && dims.contains(&0) {
+        return write_function(&WolframValue::symbol("List"), items, out, options);
+    }
+    out.write_all(&[token, if real { 0x23 } else { width }])?;
+    write_varint(dims.len(), out)?;
+    for d in &dims {
+        write_varint(*d, out)?;
+    }
+    write_elements(items, width, real, out)?;
+    Ok(())
+}
+
+/// Dimensions along the first element of every level
+fn array_dims(items: &[WolframValue]) -> Vec<usize> {
+    let mut dims = vec![items.len()];
+    let mut this = items.first();
+    while let Some(WolframValue::Function(head, args)) = this {
+        if !is_list(head) {
+            break;
+        }
+        dims.push(args.len());
+        this = args.first();
+    }
+    dims
+}
+
+/// Check the shape against `dims` and find the element type
+fn scan_array(items: &[WolframValue], dims: &[usize], width: &mut u8, real: &mut bool) -> Result<(), WXFError> {
+    if items.len() != dims[0] {
+        return Err(WXFError::InvalidArrayShape(format!("expected {} elements, found {}", dims[0], items.len())));
+    }
+    for v in items {
+        match (v, dims.len()) {
+            (WolframValue::Function(head, args), n) if n > 1 && is_list(head) => scan_array(args, &dims[1..], width, real)?,
+            (WolframValue::Function(head, _), 1) if is_list(head) => {
+                return Err(WXFError::InvalidArrayShape(format!("{} is deeper than the first element", v)));
             }
-            WolframValue::BigDecimal(_) => unimplemented!(),
-            WolframValue::PackedArray(_) => unimplemented!(),
-            WolframValue::NumericArray(_) => unimplemented!(),
-            WolframValue::Association(dict) => {
-                out.push(b'A');
-                out.extend_from_slice(&dict.len().encode_var_vec());
-                for (k, (r, v)) in dict {
-                    r.write_bytes_inner(out);
-                    k.write_bytes_inner(out);
-                    v.write_bytes_inner(out);
+            (_, 1) => match machine_integer(v) {
+                Some(n) if i8::try_from(n).is_ok() => {}
+                Some(n) if i16::try_from(n).is_ok() => *width = (*width).max(1),
+                Some(n) if i32::try_from(n).is_ok() => *width = (*width).max(2),
+                Some(_) => *width = (*width).max(3),
+                None => match v {
+                    WolframValue::Decimal64(_) => *real = true,
+                    _ => return Err(WXFError::UnsupportedValue(format!("{} is not a machine number", v))),
+                },
+            },
+            _ => return Err(WXFError::InvalidArrayShape(format!("expected a list, found {}", v))),
+        }
+    }
+    Ok(())
+}
+
+/// Elements in row-major order, the shape was checked by `scan_array`
+fn write_elements<W: WXFWrite + ?Sized>(items: &[WolframValue], width: u8, real: bool, out: &mut W) -> Result<(), WXFError> {
+    for v in items {
+        match (v, machine_integer(v)) {
+            (WolframValue::Function(_, args), _) => write_elements(args, width, real, out)?,
+            (WolframValue::Decimal64(d), _) => out.write_all(d)?,
+            (_, Some(n)) if real => out.write_all(&(n as f64).to_le_bytes())?,
+            (_, Some(n)) => out.write_all(&n.to_le_bytes()[..1 << width])?,
+            _ => unreachable!(),
+        }
+    }
+    Ok(())
+}
+
+/// Type token, elements and dimensions of an array
+type TypedArray<'a> = (u8, &'a [WolframValue], Vec<usize>);
+
+/// The [`TypedArray`] of `NumericArray[list, type]` for a list and a type of WXF arrays
+///
+/// Other forms and complex types are written as the expression, a list that is not rectangular or does not fit the type fails.
+fn numeric_array<'a>(head: &WolframValue, args: &'a [WolframValue]) -> Result<Option<TypedArray<'a>>, WXFError> {
+    let (items, token) = match (head, args) {
+        (WolframValue::Symbol(s), [list, WolframValue::String(kind)]) if s == "NumericArray" => match (list_items(list), array_token(kind)) {
+            // complex arrays are left to Mathematica
+            (Some(items), Some(token)) if token < 0x33 => (items, token),
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    let mut dims = vec![items.len()];
+    let mut this = items.first();
+    while let Some(next) = this.and_then(list_items) {
+        dims.push(next.len());
+        this = next.first();
+    }
+    check_typed_array(token, items, &dims)?;
+    Ok(Some((token, items, dims)))
+}
+
+fn check_typed_array(token: u8, items: &[WolframValue], dims: &[usize]) -> Result<(), WXFError> {
+    if items.len() != dims[0] {
+        return Err(WXFError::InvalidArrayShape(format!("expected {} elements, found {}", dims[0], items.len())));
+    }
+    for v in items {
+        match list_items(v) {
+            Some(next) if dims.len() > 1 => check_typed_array(token, next, &dims[1..])?,
+            None if dims.len() == 1 => {
+                if typed_element(token, v).is_none() {
+                    let kind = array_type(token).map_or("", |(kind, _)| kind);
+                    return Err(WXFError::UnsupportedValue(format!("{} is not a {} element", v, kind)));
                 }
             }
-            WolframValue::Rule => out.push(b'-'),
-            WolframValue::RuleDelayed => out.push(b':'),
+            Some(_) => return Err(WXFError::InvalidArrayShape(format!("{} is deeper than the first element", v))),
+            None => return Err(WXFError::InvalidArrayShape(format!("expected a list, found {}", v))),
+        }
+    }
+    Ok(())
+}
+
+/// Elements in row-major order, the shape was checked by `check_typed_array`
+fn write_typed_elements<W: WXFWrite + ?Sized>(token: u8, items: &[WolframValue], out: &mut W) -> Result<(), WXFError> {
+    for v in items {
+        match (list_items(v), typed_element(token, v)) {
+            (Some(next), _) => write_typed_elements(token, next, out)?,
+            (None, Some((bytes, size))) => out.write_all(&bytes[..size])?,
+            (None, None) => unreachable!(),
+        }
+    }
+    Ok(())
+}
+
+/// Little-endian bytes of `value` as an element of type `token` and their length
+fn typed_element(token: u8, value: &WolframValue) -> Option<([u8; 8], usize)> {
+    let size = array_type(token)?.1;
+    let bits = 8 * size as u32;
+    let bytes = match token {
+        0x00..=0x03 => {
+            let n = machine_integer(value)?;
+            if bits < 64 && (n < -(1 << (bits - 1)) || n >= 1 << (bits - 1)) {
+                return None;
+            }
+            n.to_le_bytes()
+        }
+        0x10..=0x13 => {
+            let n = unsigned_integer(value)?;
+            if bits < 64 && n >> bits != 0 {
+                return None;
+            }
+            n.to_le_bytes()
+        }
+        0x22 => {
+            let mut bytes = [0; 8];
+            bytes[..4].copy_from_slice(&(machine_real(value)? as f32).to_le_bytes());
+            bytes
         }
+        0x23 => machine_real(value)?.to_le_bytes(),
+        _ => return None,
+    };
+    Some((bytes, size))
+}
+
+fn unsigned_integer(value: &WolframValue) -> Option<u64> {
+    match value {
+        #[cfg(feature = "bigint")]
+        WolframValue::BigInteger(n) => n.to_u64(),
+        _ => machine_integer(value).and_then(|n| u64::try_from(n).ok()),
     }
 }
 
-fn standardized_symbol_name(input: &str) -> String {
-    if input.contains('`') {
-        return format!("{}", input);
+fn machine_real(value: &WolframValue) -> Option<f64> {
+    match value {
+        WolframValue::Decimal64(d) => Some(f64::from_le_bytes(*d)),
+        _ => machine_integer(value).map(|n| n as f64),
     }
-    let mut set = BTreeSet::new();
-    for sys in SYSTEM_SYMBOLS.iter() {
-        set.insert(*sys);
+}
+
+/// Elements of a list, packed array or untyped numeric array
+fn list_items(value: &WolframValue) -> Option<&[WolframValue]> {
+    match value {
+        WolframValue::Function(head, args) if is_list(head) => Some(args),
+        WolframValue::PackedArray(items) | WolframValue::NumericArray(items) => Some(items),
+        _ => None,
     }
-    if set.contains(input) { format!("{}", input) } else { format!("Global`{}", input) }
+}
+
+fn machine_integer(value: &WolframValue) -> Option<i64> {
+    match value {
+        WolframValue::Integer8(n) => Some(*n as i64),
+        WolframValue::Integer16(n) => Some(*n as i64),
+        WolframValue::Integer32(n) => Some(*n as i64),
+        WolframValue::Integer64(n) => Some(*n),
+        #[cfg(feature = "bigint")]
+        WolframValue::BigInteger(n) => n.to_i64(),
+        _ => None,
+    }
+}
+
+fn is_list(head: &WolframValue) -> bool {
+    matches!(head, WolframValue::Symbol(s) if s == "List")
+}
+
+/// Letters, digits and `$`, not starting with a digit, contexts separated by `` ` ``
+fn is_valid_symbol(input: &str) -> bool {
+    input.split('`').all(|part| match part.chars().next() {
+        Some(c) if !c.is_ascii_digit() => part.chars().all(|c| c.is_alphanumeric() || c == '$'),
+        _ => false,
+    })
+}
+
+fn write_narrowest<W: WXFWrite + ?Sized>(n: i64, out: &mut W) -> Result<(), WXFError> {
+    if let Ok(n) = i8::try_from(n) {
+        write_integer(b'C', &n.to_le_bytes(), out)
+    }
+    else if let Ok(n) = i16::try_from(n) {
+        write_integer(b'j', &n.to_le_bytes(), out)
+    }
+    else if let Ok(n) = i32::try_from(n) {
+        write_integer(b'i', &n.to_le_bytes(), out)
+    }
+    else {
+        write_integer(b'L', &n.to_le_bytes(), out)
+    }
+}
+
+fn is_system_symbol(input: &str) -> bool {
+    SYSTEM_SYMBOLS.binary_search(&input).is_ok()
+}
+
+/// Context to prepend and the name as written
+fn standardized_symbol_name(input: &str) -> (&'static str, &str) {
+    if input.contains('`') || is_system_symbol(input) { ("", input) } else { ("Global`", input) }
 }
@@ -1,9 +1,15 @@
 use crate::{ToWolfram, WolframValue};
-use num::{bigint::Sign, rational::Ratio, BigInt, BigUint, Complex};
-use std::{
-    collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque},
-    intrinsics::transmute,
+#[cfg(feature = "bigint")]
+use alloc::vec;
+use alloc::{
+    collections::{BTreeMap, BTreeSet, LinkedList, VecDeque},
+    string::{String, ToString},
+    vec::Vec,
 };
+#[cfg(feature = "bigint")]
+use num::{bigint::Sign, rational::Ratio, BigInt, BigUint, Complex};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
 
 impl ToWolfram for WolframValue {
     fn to_wolfram(&self) -> WolframValue {
@@ -31,7 +37,7 @@ impl ToWolfram for &str {
 
 impl ToWolfram for char {
     fn to_wolfram(&self) -> WolframValue {
-        unsafe { std::str::from_utf8_unchecked(&[*self as u8]).to_wolfram() }
+        unsafe { core::str::from_utf8_unchecked(&[*self as u8]).to_wolfram() }
     }
 }
 
@@ -59,6 +65,7 @@ impl ToWolfram for i64 {
     }
 }
 
+#[cfg(feature = "bigint")]
 impl ToWolfram for i128 {
     fn to_wolfram(&self) -> WolframValue {
         WolframValue::integer(*self)
@@ -84,11 +91,21 @@ impl ToWolfram for u32 {
 }
 
 impl ToWolfram for u64 {
+    /// Without the `bigint` feature values above `i64::MAX` become reals
     fn to_wolfram(&self) -> WolframValue {
-        if *self <= 9223372036854775807 { (*self as i64).to_wolfram() } else { WolframValue::integer(*self) }
+        if *self <= 9223372036854775807 {
+            (*self as i64).to_wolfram()
+        }
+        else {
+            #[cfg(feature = "bigint")]
+            return WolframValue::integer(*self);
+            #[cfg(not(feature = "bigint"))]
+            return (*self as f64).to_wolfram();
+        }
     }
 }
 
+#[cfg(feature = "bigint")]
 impl ToWolfram for u128 {
     fn to_wolfram(&self) -> WolframValue {
         WolframValue::integer(*self)
@@ -97,22 +114,24 @@ impl ToWolfram for u128 {
 
 impl ToWolfram for isize {
     fn to_wolfram(&self) -> WolframValue {
-        WolframValue::integer(*self as i64)
+        (*self as i64).to_wolfram()
     }
 }
 
 impl ToWolfram for usize {
     fn to_wolfram(&self) -> WolframValue {
-        WolframValue::integer(*self as u64)
+        (*self as u64).to_wolfram()
     }
 }
 
+#[cfg(feature = "bigint")]
 impl ToWolfram for BigUint {
     fn to_wolfram(&self) -> WolframValue {
         BigInt::from_biguint(Sign::NoSign, self.clone()).to_wolfram()
     }
 }
 
+#[cfg(feature = "bigint")]
 impl ToWolfram for BigInt {
     fn to_wolfram(&self) -> WolframValue {
         WolframValue::BigInteger(self.clone())
@@ -121,16 +140,17 @@ impl ToWolfram for BigInt {
 
 impl ToWolfram for f32 {
     fn to_wolfram(&self) -> WolframValue {
-        WolframValue::Decimal64(unsafe { transmute::<_, [u8; 8]>(*self as f64) })
+        WolframValue::Decimal64((*self as f64).to_le_bytes())
     }
 }
 
 impl ToWolfram for f64 {
     fn to_wolfram(&self) -> WolframValue {
-        WolframValue::Decimal64(unsafe { transmute::<_, [u8; 8]>(*self) })
+        WolframValue::Decimal64(self.to_le_bytes())
     }
 }
 
+#[cfg(feature = "bigint")]
 impl<T: ToWolfram + Clone> ToWolfram for Ratio<T> {
     fn to_wolfram(&self) -> WolframValue {
         let r = vec![(*self.numer()).clone(), (*self.denom()).clone()];
@@ -138,6 +158,7 @@ impl<T: ToWolfram + Clone> ToWolfram for Ratio<T> {
     }
 }
 
+#[cfg(feature = "bigint")]
 impl<T: ToWolfram + Copy> ToWolfram for Complex<T> {
     fn to_wolfram(&self) -> WolframValue {
         let r = vec![self.re, self.im];
@@ -163,6 +184,7 @@ impl<T: ToWolfram> ToWolfram for LinkedList<T> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: ToWolfram> ToWolfram for HashSet<T> {
     fn to_wolfram(&self) -> WolframValue {
         WolframValue::list(self.iter().map(|s| s.to_wolfram()).collect())
@@ -181,7 +203,7 @@ where
     V: ToWolfram,
 {
     fn to_wolfram(&self) -> WolframValue {
-        let ref rule = WolframValue::Rule;
+        let rule = &WolframValue::Rule;
         let mut map = BTreeMap::new();
         for (k, v) in self {
             map.insert(k.to_wolfram(), (rule.clone(), v.to_wolfram()));
@@ -190,13 +212,14 @@ where
     }
 }
 
+#[cfg(feature = "std")]
 impl<K, V> ToWolfram for HashMap<K, V>
 where
     K: ToWolfram,
     V: ToWolfram,
 {
     fn to_wolfram(&self) -> WolframValue {
-        let ref rule = WolframValue::Rule;
+        let rule = &WolframValue::Rule;
         let mut map = BTreeMap::new();
         for (k, v) in self {
             map.insert(k.to_wolfram(), (rule.clone(), v.to_wolfram()));
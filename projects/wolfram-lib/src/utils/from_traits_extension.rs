@@ -4,6 +4,12 @@ pub use self::toml::parse_toml;
 pub use json::parse_json;
 #[cfg(feature = "yaml")]
 pub use yaml::parse_yaml;
+#[cfg(feature = "pickle")]
+pub use pickle::parse_pickle;
+#[cfg(feature = "msgpack")]
+pub use msgpack::{parse_msgpack, to_msgpack, write_msgpack};
+#[cfg(feature = "cbor")]
+pub use cbor::{parse_cbor, to_cbor, write_cbor};
 
 #[cfg(feature = "json")]
 mod json {
@@ -19,7 +25,7 @@ mod json {
                 Value::String(s) => s.to_wolfram(),
                 Value::Array(a) => a.to_wolfram(),
                 Value::Object(o) => {
-                    let ref rule = WolframValue::Rule;
+                    let rule = &WolframValue::Rule;
                     let mut map = BTreeMap::new();
                     for (k, v) in o {
                         map.insert(k.to_wolfram(), (rule.clone(), v.to_wolfram()));
@@ -64,7 +70,7 @@ mod toml {
                 Value::Datetime(o) => date_object(&format!("{}", o)),
                 Value::Array(o) => o.to_wolfram(),
                 Value::Table(o) => {
-                    let ref rule = WolframValue::Rule;
+                    let rule = &WolframValue::Rule;
                     let mut map = BTreeMap::new();
                     for (k, v) in o {
                         map.insert(k.to_wolfram(), (rule.clone(), v.to_wolfram()));
@@ -95,7 +101,7 @@ mod yaml {
                 Yaml::Boolean(b) => b.to_wolfram(),
                 Yaml::Array(o) => o.to_wolfram(),
                 Yaml::Hash(o) => {
-                    let ref rule = WolframValue::Rule;
+                    let rule = &WolframValue::Rule;
                     let mut map = BTreeMap::new();
                     for (k, v) in o {
                         map.insert(k.to_wolfram(), (rule.clone(), v.to_wolfram()));
@@ -121,14 +127,20 @@ mod yaml {
 #[cfg(feature = "pickle")]
 mod pickle {
     use crate::{ToWolfram, WolframValue};
+    use num::BigInt;
     use serde_pickle::{HashableValue, Value};
+
+    /// `serde-pickle` has its own `num-bigint`, go through the digits
+    fn big_integer(digits: &str) -> WolframValue {
+        WolframValue::BigInteger(digits.parse::<BigInt>().unwrap_or_default())
+    }
     impl ToWolfram for Value {
         fn to_wolfram(&self) -> WolframValue {
             match self {
                 Value::None => WolframValue::symbol("None"),
                 Value::Bool(b) => b.to_wolfram(),
                 Value::I64(i) => i.to_wolfram(),
-                Value::Int(i) => i.to_wolfram(),
+                Value::Int(i) => big_integer(&i.to_string()),
                 Value::F64(f) => f.to_wolfram(),
                 Value::Bytes(b) => WolframValue::Bytes(b.clone()),
                 Value::String(s) => s.to_wolfram(),
@@ -146,7 +158,7 @@ mod pickle {
                 HashableValue::None => WolframValue::symbol("None"),
                 HashableValue::Bool(b) => b.to_wolfram(),
                 HashableValue::I64(i) => i.to_wolfram(),
-                HashableValue::Int(i) => i.to_wolfram(),
+                HashableValue::Int(i) => big_integer(&i.to_string()),
                 HashableValue::F64(f) => f.to_wolfram(),
                 HashableValue::Bytes(b) => WolframValue::Bytes(b.clone()),
                 HashableValue::String(s) => s.to_wolfram(),
@@ -155,6 +167,374 @@ mod pickle {
             }
         }
     }
+    pub fn parse_pickle(input: &[u8]) -> serde_pickle::Result<WolframValue> {
+        Ok(serde_pickle::value_from_slice(input)?.to_wolfram())
+    }
+}
+
+#[cfg(feature = "msgpack")]
+mod msgpack {
+    use crate::{utils::normalized_symbol_name, FromWolfram, FromWolframError, ToWolfram, WXFError, WolframValue};
+    use num::ToPrimitive;
+    use rmpv::{decode, encode, Value};
+    use std::{collections::BTreeMap, io::Write};
+
+    /// Head of extension types, `MessagePackExtension[type, ByteArray[...]]`
+    const EXTENSION: &str = "MessagePackExtension";
+
+    impl ToWolfram for Value {
+        fn to_wolfram(&self) -> WolframValue {
+            match self {
+                Value::Nil => WolframValue::symbol("None"),
+                Value::Boolean(b) => b.to_wolfram(),
+                Value::Integer(n) => match n.as_i64() {
+                    Some(n) => n.to_wolfram(),
+                    None => n.as_u64().unwrap_or(0).to_wolfram(),
+                },
+                Value::F32(f) => f.to_wolfram(),
+                Value::F64(f) => f.to_wolfram(),
+                Value::String(s) => match s.as_str() {
+                    Some(s) => s.to_wolfram(),
+                    None => WolframValue::Bytes(s.as_bytes().to_vec()),
+                },
+                Value::Binary(b) => WolframValue::Bytes(b.clone()),
+                Value::Array(o) => o.to_wolfram(),
+                Value::Map(o) => {
+                    let rule = &WolframValue::Rule;
+                    let mut map = BTreeMap::new();
+                    for (k, v) in o {
+                        map.insert(k.to_wolfram(), (rule.clone(), v.to_wolfram()));
+                    }
+                    WolframValue::Association(map)
+                }
+                Value::Ext(kind, data) => WolframValue::Function(
+                    Box::new(WolframValue::symbol(EXTENSION)),
+                    vec![kind.to_wolfram(), WolframValue::Bytes(data.clone())],
+                ),
+            }
+        }
+    }
+
+    impl FromWolfram for Value {
+        /// Integers beyond `u64` and symbols other than `None`, `True` and `False` have no MessagePack form
+        fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+            let error = |expected| Err(FromWolframError { expected, found: value.clone() });
+            let out = match value {
+                WolframValue::Symbol(s) if matches!(normalized_symbol_name(s).as_str(), "None" | "Null") => Value::Nil,
+                WolframValue::Symbol(_) => Value::Boolean(bool::from_wolfram(value)?),
+                WolframValue::String(s) => Value::from(s.as_str()),
+                WolframValue::Bytes(b) => Value::Binary(b.clone()),
+                WolframValue::Decimal64(_) => Value::F64(f64::from_wolfram(value)?),
+                WolframValue::PackedArray(items) | WolframValue::NumericArray(items) => {
+                    Value::Array(items.iter().map(Value::from_wolfram).collect::<Result<_, _>>()?)
+                }
+                WolframValue::Association(dict) => {
+                    let entries = dict.iter().map(|(k, (_, v))| Ok((Value::from_wolfram(k)?, Value::from_wolfram(v)?)));
+                    Value::Map(entries.collect::<Result<_, FromWolframError>>()?)
+                }
+                WolframValue::Function(head, args) => match (head.as_ref(), args.as_slice()) {
+                    (WolframValue::Symbol(s), _) if normalized_symbol_name(s) == "List" => {
+                        Value::Array(args.iter().map(Value::from_wolfram).collect::<Result<_, _>>()?)
+                    }
+                    (WolframValue::Symbol(s), [kind, WolframValue::Bytes(data)]) if normalized_symbol_name(s) == EXTENSION => {
+                        Value::Ext(i8::from_wolfram(kind)?, data.clone())
+                    }
+                    _ => return error("expression with a MessagePack form"),
+                },
+                _ => match value.integer_value() {
+                    Some(n) => match n.to_i64().map(Value::from).or_else(|| n.to_u64().map(Value::from)) {
+                        Some(n) => n,
+                        None => return error("Integer in u64 range"),
+                    },
+                    None => return error("value with a MessagePack form"),
+                },
+            };
+            Ok(out)
+        }
+    }
+
+    /// The MessagePack value of the input, extension types become `MessagePackExtension[type, bytes]`
+    ///
+    /// Bytes after the value are an `InvalidDataRead` error.
+    pub fn parse_msgpack(mut input: &[u8]) -> Result<WolframValue, decode::Error> {
+        let value = decode::read_value(&mut input)?;
+        if !input.is_empty() {
+            let trailing = std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{} bytes after the MessagePack value", input.len()));
+            return Err(decode::Error::InvalidDataRead(trailing));
+        }
+        Ok(value.to_wolfram())
+    }
+
+    /// Write the MessagePack form of `value`, associations become maps and lists arrays
+    pub fn write_msgpack<W: Write>(value: &WolframValue, out: &mut W) -> Result<(), WXFError> {
+        let value = Value::from_wolfram(value).map_err(|e| WXFError::UnsupportedValue(e.to_string()))?;
+        encode::write_value(out, &value).map_err(|e| match e {
+            encode::Error::InvalidMarkerWrite(e) | encode::Error::InvalidDataWrite(e) => WXFError::Io(e),
+        })
+    }
+
+    pub fn to_msgpack(value: &WolframValue) -> Result<Vec<u8>, WXFError> {
+        let mut out = vec![];
+        write_msgpack(value, &mut out)?;
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "cbor")]
+mod cbor {
+    use crate::{utils::normalized_symbol_name, FromWolfram, FromWolframError, ToWolfram, WXFError, WolframValue};
+    use ciborium::{de, ser, value::Integer, Value};
+    use num::{bigint::Sign, BigInt, ToPrimitive};
+    use std::{collections::BTreeMap, convert::TryFrom, io::Write};
+
+    /// Head of tags other than bignums, `CBORTag[tag, value]`
+    const TAG: &str = "CBORTag";
+    /// Tags 2 and 3 of RFC 8949
+    const BIGPOS: u64 = 2;
+    const BIGNEG: u64 = 3;
+
+    fn integer(n: i128) -> WolframValue {
+        match i64::try_from(n) {
+            Ok(n) => n.to_wolfram(),
+            Err(_) => WolframValue::integer(n),
+        }
+    }
+
+    impl ToWolfram for Value {
+        fn to_wolfram(&self) -> WolframValue {
+            match self {
+                Value::Null => WolframValue::symbol("None"),
+                Value::Bool(b) => b.to_wolfram(),
+                Value::Integer(n) => integer(i128::from(*n)),
+                Value::Float(f) => f.to_wolfram(),
+                Value::Text(s) => s.to_wolfram(),
+                Value::Bytes(b) => WolframValue::Bytes(b.clone()),
+                Value::Array(o) => o.to_wolfram(),
+                Value::Map(o) => {
+                    let rule = &WolframValue::Rule;
+                    let mut map = BTreeMap::new();
+                    for (k, v) in o {
+                        map.insert(k.to_wolfram(), (rule.clone(), v.to_wolfram()));
+                    }
+                    WolframValue::Association(map)
+                }
+                Value::Tag(BIGPOS, inner) if inner.is_bytes() => {
+                    WolframValue::integer(BigInt::from_bytes_be(Sign::Plus, inner.as_bytes().map(|b| b.as_slice()).unwrap_or_default()))
+                }
+                Value::Tag(BIGNEG, inner) if inner.is_bytes() => {
+                    let n = BigInt::from_bytes_be(Sign::Plus, inner.as_bytes().map(|b| b.as_slice()).unwrap_or_default());
+                    WolframValue::integer(-n - 1u8)
+                }
+                Value::Tag(t, inner) => WolframValue::Function(Box::new(WolframValue::symbol(TAG)), vec![t.to_wolfram(), inner.to_wolfram()]),
+                // ciborium may add kinds of values
+                other => WolframValue::function("Missing", vec!["Unsupported".to_string(), format!("{:?}", other)]),
+            }
+        }
+    }
+
+    /// Integers beyond `u64` become bignums
+    fn big_integer(n: &BigInt) -> Value {
+        if let Some(n) = n.to_i128().and_then(|n| Integer::try_from(n).ok()) {
+            return Value::Integer(n);
+        }
+        match n.sign() {
+            Sign::Minus => Value::Tag(BIGNEG, Box::new(Value::Bytes((-n - 1u8).to_bytes_be().1))),
+            _ => Value::Tag(BIGPOS, Box::new(Value::Bytes(n.to_bytes_be().1))),
+        }
+    }
+
+    impl FromWolfram for Value {
+        /// Symbols other than `None`, `True` and `False` have no CBOR form
+        fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+            let error = |expected| Err(FromWolframError { expected, found: value.clone() });
+            let out = match value {
+                WolframValue::Symbol(s) if matches!(normalized_symbol_name(s).as_str(), "None" | "Null") => Value::Null,
+                WolframValue::Symbol(_) => Value::Bool(bool::from_wolfram(value)?),
+                WolframValue::String(s) => Value::Text(s.clone()),
+                WolframValue::Bytes(b) => Value::Bytes(b.clone()),
+                WolframValue::Decimal64(_) => Value::Float(f64::from_wolfram(value)?),
+                WolframValue::PackedArray(items) | WolframValue::NumericArray(items) => {
+                    Value::Array(items.iter().map(Value::from_wolfram).collect::<Result<_, _>>()?)
+                }
+                WolframValue::Association(dict) => {
+                    let entries = dict.iter().map(|(k, (_, v))| Ok((Value::from_wolfram(k)?, Value::from_wolfram(v)?)));
+                    Value::Map(entries.collect::<Result<_, FromWolframError>>()?)
+                }
+                WolframValue::Function(head, args) => match (head.as_ref(), args.as_slice()) {
+                    (WolframValue::Symbol(s), _) if normalized_symbol_name(s) == "List" => {
+                        Value::Array(args.iter().map(Value::from_wolfram).collect::<Result<_, _>>()?)
+                    }
+                    (WolframValue::Symbol(s), [t, inner]) if normalized_symbol_name(s) == TAG => {
+                        Value::Tag(u64::from_wolfram(t)?, Box::new(Value::from_wolfram(inner)?))
+                    }
+                    _ => return error("expression with a CBOR form"),
+                },
+                _ => match value.integer_value() {
+                    Some(n) => big_integer(&n),
+                    None => return error("value with a CBOR form"),
+                },
+            };
+            Ok(out)
+        }
+    }
+
+    /// The CBOR item of the input, bignums become integers and other tags `CBORTag[tag, value]`
+    ///
+    /// Bytes after the item are a `Semantic` error.
+    pub fn parse_cbor(input: &[u8]) -> Result<WolframValue, de::Error<std::io::Error>> {
+        let mut rest = input;
+        let value = de::from_reader::<Value, _>(&mut rest)?;
+        if !rest.is_empty() {
+            return Err(de::Error::Semantic(Some(input.len() - rest.len()), "bytes after the CBOR item".to_string()));
+        }
+        Ok(value.to_wolfram())
+    }
+
+    /// Write the CBOR form of `value`, associations become maps and lists arrays
+    pub fn write_cbor<W: Write>(value: &WolframValue, out: &mut W) -> Result<(), WXFError> {
+        let value = Value::from_wolfram(value).map_err(|e| WXFError::UnsupportedValue(e.to_string()))?;
+        ser::into_writer(&value, out).map_err(|e| match e {
+            ser::Error::Io(e) => WXFError::Io(e),
+            ser::Error::Value(e) => WXFError::Custom(e),
+        })
+    }
+
+    pub fn to_cbor(value: &WolframValue) -> Result<Vec<u8>, WXFError> {
+        let mut out = vec![];
+        write_cbor(value, &mut out)?;
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "ndarray")]
+mod ndarray {
+    use crate::{
+        utils::{decoding::nest, is_machine_number},
+        ToWolfram, WolframValue,
+    };
+    use ndarray::{ArrayBase, Data, Dimension};
+
+    impl<A, S, D> ToWolfram for ArrayBase<S, D>
+    where
+        A: ToWolfram,
+        S: Data<Elem = A>,
+        D: Dimension,
+    {
+        /// Nested lists in logical order, packed when every element is a machine number
+        fn to_wolfram(&self) -> WolframValue {
+            let items: Vec<WolframValue> = self.iter().map(|v| v.to_wolfram()).collect();
+            if self.ndim() == 0 {
+                return items.into_iter().next().unwrap_or(WolframValue::Skip);
+            }
+            let machine = items.iter().all(is_machine_number);
+            match nest(self.shape(), &items) {
+                WolframValue::Function(_, rows) if machine => WolframValue::PackedArray(rows),
+                list => list,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "sprs")]
+mod sprs {
+    use crate::{objects::SparseArray, FromWolfram, FromWolframError, ToWolfram, WolframValue};
+    use sprs::{CsMat, CsMatBase, CsVec, CsVecBase, SpIndex};
+    use std::ops::Deref;
+
+    fn zero() -> WolframValue {
+        0.to_wolfram()
+    }
+
+    /// Dimensions, row pointers, 0-based column indices and values
+    type Compressed<N> = (Vec<usize>, Vec<usize>, Vec<usize>, Vec<N>);
+
+    /// Sparse arrays with default `0` and one column index per value
+    fn parts<N: FromWolfram>(value: &WolframValue, rank: usize) -> Result<Compressed<N>, FromWolframError> {
+        let error = || FromWolframError { expected: "SparseArray with default 0", found: value.clone() };
+        let array = SparseArray::from_wolfram(value)?;
+        let zero_default = f64::from_wolfram(&array.default).ok() == Some(0.0);
+        if array.dimensions.len() != rank || !zero_default || array.column_indices.iter().any(|c| c.len() != 1 || c[0] == 0) {
+            return Err(error());
+        }
+        let indices = array.column_indices.iter().map(|c| c[0] - 1).collect();
+        let values = array.values.iter().map(N::from_wolfram).collect::<Result<_, _>>()?;
+        Ok((array.dimensions, array.row_pointers, indices, values))
+    }
+
+    impl<N, I, Iptr, IptrStorage, IndStorage, DataStorage> ToWolfram for CsMatBase<N, I, IptrStorage, IndStorage, DataStorage, Iptr>
+    where
+        N: ToWolfram,
+        I: SpIndex,
+        Iptr: SpIndex,
+        IptrStorage: Deref<Target = [Iptr]>,
+        IndStorage: Deref<Target = [I]>,
+        DataStorage: Deref<Target = [N]>,
+    {
+        /// `SparseArray[Automatic, {rows, cols}, 0, ...]`, compressed sparse columns are stored by row
+        fn to_wolfram(&self) -> WolframValue {
+            let rows = self.rows();
+            let (row_pointers, column_indices, values) = if self.is_csr() {
+                let row_pointers = self.indptr().to_proper().iter().map(|p| p.index()).collect();
+                let column_indices = self.indices().iter().map(|c| vec![c.index() + 1]).collect();
+                (row_pointers, column_indices, self.data().iter().map(|v| v.to_wolfram()).collect())
+            }
+            else {
+                // counting sort by row, columns are visited in order so every row stays sorted
+                let mut row_pointers = vec![0; rows + 1];
+                for row in self.indices() {
+                    row_pointers[row.index() + 1] += 1
+                }
+                for i in 0..rows {
+                    row_pointers[i + 1] += row_pointers[i]
+                }
+                let mut next = row_pointers.clone();
+                let mut column_indices = vec![vec![]; self.nnz()];
+                let mut values = vec![WolframValue::Skip; self.nnz()];
+                for (col, vector) in self.outer_iterator().enumerate() {
+                    for (row, v) in vector.iter() {
+                        column_indices[next[row]] = vec![col + 1];
+                        values[next[row]] = v.to_wolfram();
+                        next[row] += 1;
+                    }
+                }
+                (row_pointers, column_indices, values)
+            };
+            SparseArray { dimensions: vec![rows, self.cols()], default: zero(), row_pointers, column_indices, values }.to_wolfram()
+        }
+    }
+
+    impl<N, I, IStorage, DStorage> ToWolfram for CsVecBase<IStorage, DStorage, N, I>
+    where
+        N: ToWolfram,
+        I: SpIndex,
+        IStorage: Deref<Target = [I]>,
+        DStorage: Deref<Target = [N]>,
+    {
+        /// `SparseArray[Automatic, {dim}, 0, ...]`
+        fn to_wolfram(&self) -> WolframValue {
+            let column_indices = self.indices().iter().map(|i| vec![i.index() + 1]).collect();
+            let values = self.data().iter().map(|v| v.to_wolfram()).collect();
+            SparseArray { dimensions: vec![self.dim()], default: zero(), row_pointers: vec![0, self.nnz()], column_indices, values }.to_wolfram()
+        }
+    }
+
+    impl<N: FromWolfram> FromWolfram for CsMat<N> {
+        /// A matrix `SparseArray` with default `0`, in compressed sparse row storage
+        fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+            let (dimensions, row_pointers, indices, values) = parts(value, 2)?;
+            CsMat::try_new((dimensions[0], dimensions[1]), row_pointers, indices, values)
+                .map_err(|_| FromWolframError { expected: "SparseArray with sorted column indices", found: value.clone() })
+        }
+    }
+
+    impl<N: FromWolfram> FromWolfram for CsVec<N> {
+        /// A vector `SparseArray` with default `0`
+        fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+            let (dimensions, _, indices, values) = parts(value, 1)?;
+            CsVec::try_new(dimensions[0], indices, values)
+                .map_err(|_| FromWolframError { expected: "SparseArray with sorted indices", found: value.clone() })
+        }
+    }
 }
 
 #[cfg(feature = "numpy")]
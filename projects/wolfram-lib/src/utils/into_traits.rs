@@ -0,0 +1,186 @@
+use super::canonical::integer_to_f64;
+use crate::{FromWolfram, FromWolframError, WolframValue};
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+use core::convert::TryFrom;
+#[cfg(feature = "bigint")]
+use num::{BigInt, ToPrimitive};
+#[cfg(feature = "std")]
+use std::{collections::HashMap, hash::Hash};
+
+fn error<T>(expected: &'static str, found: &WolframValue) -> Result<T, FromWolframError> {
+    Err(FromWolframError { expected, found: found.clone() })
+}
+
+/// Elements of `{...}`, packed or not
+fn list_items<'a>(value: &'a WolframValue, expected: &'static str) -> Result<&'a [WolframValue], FromWolframError> {
+    match value {
+        WolframValue::Function(head, args) if **head == WolframValue::symbol("List") => Ok(args),
+        WolframValue::PackedArray(items) => Ok(items),
+        _ => error(expected, value),
+    }
+}
+
+impl FromWolfram for WolframValue {
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        Ok(value.clone())
+    }
+}
+
+impl FromWolfram for bool {
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        match value {
+            WolframValue::Symbol(s) if s == "True" || s == "System`True" => Ok(true),
+            WolframValue::Symbol(s) if s == "False" || s == "System`False" => Ok(false),
+            _ => error("True or False", value),
+        }
+    }
+}
+
+impl FromWolfram for String {
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        match value {
+            WolframValue::String(s) => Ok(s.clone()),
+            _ => error("String", value),
+        }
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl FromWolfram for BigInt {
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        match value {
+            WolframValue::Integer8(n) => Ok(BigInt::from(*n)),
+            WolframValue::Integer16(n) => Ok(BigInt::from(*n)),
+            WolframValue::Integer32(n) => Ok(BigInt::from(*n)),
+            WolframValue::Integer64(n) => Ok(BigInt::from(*n)),
+            WolframValue::BigInteger(n) => Ok(n.clone()),
+            _ => error("Integer", value),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty => $expected:expr),* $(,)?) => {$(
+        impl FromWolfram for $t {
+            fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+                let n = match value {
+                    WolframValue::Integer8(n) => Some(*n as i128),
+                    WolframValue::Integer16(n) => Some(*n as i128),
+                    WolframValue::Integer32(n) => Some(*n as i128),
+                    WolframValue::Integer64(n) => Some(*n as i128),
+                    #[cfg(feature = "bigint")]
+                    WolframValue::BigInteger(n) => n.to_i128(),
+                    _ => None,
+                };
+                match n.and_then(|n| <$t>::try_from(n).ok()) {
+                    Some(n) => Ok(n),
+                    None => error($expected, value),
+                }
+            }
+        }
+    )*};
+}
+
+from_integer! {
+    i8 => "Integer in i8 range",
+    i16 => "Integer in i16 range",
+    i32 => "Integer in i32 range",
+    i64 => "Integer in i64 range",
+    isize => "Integer in isize range",
+    u8 => "Integer in u8 range",
+    u16 => "Integer in u16 range",
+    u32 => "Integer in u32 range",
+    u64 => "Integer in u64 range",
+    usize => "Integer in usize range",
+}
+
+impl FromWolfram for f64 {
+    /// Integers and arbitrary precision reals such as `1.5`20.*^-3` are rounded to the nearest float
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        match value {
+            WolframValue::Decimal64(d) => Ok(f64::from_le_bytes(*d)),
+            WolframValue::BigDecimal(s) => match big_decimal(s) {
+                Some(n) => Ok(n),
+                None => error("Real or Integer", value),
+            },
+            _ => match value.integer_value().and_then(integer_to_f64) {
+                Some(n) => Ok(n),
+                None => error("Real or Integer", value),
+            },
+        }
+    }
+}
+
+/// The mantissa before the precision mark, scaled by the exponent after `*^`
+fn big_decimal(s: &str) -> Option<f64> {
+    let (mantissa, exponent) = match s.split_once("*^") {
+        Some((m, e)) => (m, e),
+        None => (s, "0"),
+    };
+    let mantissa = mantissa.split('`').next()?;
+    format!("{}e{}", mantissa, exponent).parse().ok()
+}
+
+impl FromWolfram for f32 {
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        f64::from_wolfram(value).map(|n| n as f32)
+    }
+}
+
+impl<T: FromWolfram> FromWolfram for Option<T> {
+    /// `None` for the symbol `None`
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        match value {
+            WolframValue::Symbol(s) if s == "None" || s == "System`None" => Ok(None),
+            _ => T::from_wolfram(value).map(Some),
+        }
+    }
+}
+
+impl<T: FromWolfram> FromWolfram for Vec<T> {
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        list_items(value, "List")?.iter().map(T::from_wolfram).collect()
+    }
+}
+
+impl<K: FromWolfram + Ord, V: FromWolfram> FromWolfram for BTreeMap<K, V> {
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        match value {
+            WolframValue::Association(dict) => dict.iter().map(|(k, (_, v))| Ok((K::from_wolfram(k)?, V::from_wolfram(v)?))).collect(),
+            _ => error("Association", value),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: FromWolfram + Eq + Hash, V: FromWolfram> FromWolfram for HashMap<K, V> {
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        match value {
+            WolframValue::Association(dict) => dict.iter().map(|(k, (_, v))| Ok((K::from_wolfram(k)?, V::from_wolfram(v)?))).collect(),
+            _ => error("Association", value),
+        }
+    }
+}
+
+macro_rules! from_tuple {
+    ($($n:expr => ($($t:ident $v:ident),*)),* $(,)?) => {$(
+        impl<$($t: FromWolfram),*> FromWolfram for ($($t,)*) {
+            /// A list of exactly this length
+            fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+                match list_items(value, concat!("List of length ", $n))? {
+                    [$($v),*] => Ok(($($t::from_wolfram($v)?,)*)),
+                    _ => error(concat!("List of length ", $n), value),
+                }
+            }
+        }
+    )*};
+}
+
+from_tuple! {
+    0 => (),
+    1 => (A a),
+    2 => (A a, B b),
+    3 => (A a, B b, C c),
+    4 => (A a, B b, C c, D d),
+    5 => (A a, B b, C c, D d, E e),
+}
@@ -1,18 +1,65 @@
+mod canonical;
+#[cfg(feature = "arrow")]
+mod columnar;
+#[cfg(feature = "polars")]
+mod dataframe;
+#[cfg(feature = "compression")]
+mod compression;
+mod decoding;
 mod encoding;
 mod from_traits;
 mod from_traits_extension;
+mod into_traits;
+mod parts;
+#[cfg(feature = "image")]
+mod raster;
 mod systems;
+#[cfg(any(feature = "arrow", feature = "polars"))]
+mod table;
+#[cfg(feature = "csv")]
+mod tabular;
+mod varint;
 
 use crate::{ToWolfram, WolframValue};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+#[allow(unused_imports)]
 pub use from_traits_extension::*;
 
+pub use canonical::{canonical_order, normalized_symbol_name};
+#[cfg(feature = "arrow")]
+pub use columnar::{column, parse_arrow, record_batch_columns, to_record_batch, write_arrow};
+#[cfg(feature = "parquet")]
+pub use columnar::{parse_parquet, write_parquet};
+#[cfg(feature = "polars")]
+pub use dataframe::data_frame_columns;
+pub(crate) use canonical::integer_to_value;
+#[cfg(feature = "compression")]
+pub use compression::CompressOptions;
+#[cfg(feature = "compression")]
+pub(crate) use decoding::decode_prefix;
+#[cfg(feature = "compression")]
+pub use decoding::inflate;
+#[cfg(feature = "std")]
+pub(crate) use decoding::decode_body;
+pub(crate) use decoding::{array_to_owned, array_token, array_type, big_integer};
+pub use decoding::DecodeLimits;
+pub use encoding::{EncodeOptions, WXFWrite};
+#[cfg(feature = "bigint")]
 use num::BigInt;
+#[cfg(feature = "std")]
+pub(crate) use parts::{part_specs, position};
+pub use parts::PartSpec;
+#[cfg(feature = "image")]
+pub use raster::ImageChannel;
 pub use systems::SYSTEM_SYMBOLS;
+#[cfg(feature = "csv")]
+pub use tabular::{parse_csv, CsvOptions, CsvOutput};
 
 impl WolframValue {
     pub fn symbol(s: impl Into<String>) -> WolframValue {
         WolframValue::Symbol(s.into())
     }
+    #[cfg(feature = "bigint")]
     pub fn integer(i: impl Into<BigInt>) -> WolframValue {
         WolframValue::BigInteger(i.into())
     }
@@ -25,10 +72,55 @@ impl WolframValue {
         let head = WolframValue::symbol("List");
         WolframValue::Function(Box::new(head), v)
     }
-    pub fn new_numeric_array() {
-        unimplemented!()
+    /// `NumericArray[{...}, type]`, written as an array of `type` such as `"UnsignedInteger8"`, encoding fails if an element does not fit it
+    pub fn new_numeric_array(items: Vec<WolframValue>, element_type: &str) -> WolframValue {
+        let head = WolframValue::symbol("NumericArray");
+        WolframValue::Function(Box::new(head), vec![WolframValue::list(items), WolframValue::String(element_type.into())])
+    }
+    /// `<|key -> value, ...|>` in the given order.
+    ///
+    /// `WolframValue::Association` sorts its keys, so entries out of order are kept as `Association[key -> value, ...]`,
+    /// which Mathematica evaluates to the same association and [`WolframValue::get`] reads the same way.
+    pub fn association_in_order(entries: Vec<(WolframValue, WolframValue)>) -> WolframValue {
+        if entries.windows(2).all(|w| w[0].0 < w[1].0) {
+            return WolframValue::Association(entries.into_iter().map(|(k, v)| (k, (WolframValue::Rule, v))).collect());
+        }
+        let rules = entries.into_iter().map(|(k, v)| WolframValue::Function(Box::new(WolframValue::symbol("Rule")), vec![k, v]));
+        WolframValue::Function(Box::new(WolframValue::symbol("Association")), rules.collect())
+    }
+    /// Key and value of `key -> value` or `key :> value`
+    pub(crate) fn as_rule(&self) -> Option<(&WolframValue, &WolframValue)> {
+        match self {
+            WolframValue::Function(head, args) => match (head.as_ref(), args.as_slice()) {
+                (WolframValue::Symbol(s), [k, v]) if matches!(normalized_symbol_name(s).as_str(), "Rule" | "RuleDelayed") => Some((k, v)),
+                _ => None,
+            },
+            _ => None,
+        }
     }
     pub fn new_packed_array() {
         unimplemented!()
     }
 }
+
+/// Integers and reals of the element types of a packed array
+pub fn is_machine_number(value: &WolframValue) -> bool {
+    matches!(
+        value,
+        WolframValue::Integer8(_) | WolframValue::Integer16(_) | WolframValue::Integer32(_) | WolframValue::Integer64(_) | WolframValue::Decimal64(_)
+    )
+}
+
+/// Elements of `{...}`, packed or not, and of `NumericArray[{...}, type]` as the decoder reads it
+#[cfg(any(feature = "arrow", feature = "polars", feature = "image"))]
+pub(crate) fn array_items(value: &WolframValue) -> Option<&[WolframValue]> {
+    match value {
+        WolframValue::PackedArray(items) | WolframValue::NumericArray(items) => Some(items),
+        WolframValue::Function(head, args) => match &**head {
+            WolframValue::Symbol(s) if s == "List" => Some(args),
+            WolframValue::Symbol(s) if s == "NumericArray" && !args.is_empty() => array_items(&args[0]),
+            _ => None,
+        },
+        _ => None,
+    }
+}
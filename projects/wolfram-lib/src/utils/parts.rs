@@ -0,0 +1,257 @@
+use crate::{PartError, ToWolfram, WolframValue};
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+
+/// One level of a `Part` specification
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartSpec {
+    /// 1-based position, negative counts from the end, `0` is the head
+    Index(isize),
+    /// Key of an `Association`
+    Key(WolframValue),
+    /// Every element at this level
+    All,
+}
+
+impl Display for PartSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PartSpec::Index(i) => write!(f, "{}", i),
+            PartSpec::Key(k) => write!(f, "{}", k),
+            PartSpec::All => write!(f, "All"),
+        }
+    }
+}
+
+impl From<i32> for PartSpec {
+    fn from(i: i32) -> Self {
+        PartSpec::Index(i as isize)
+    }
+}
+
+impl From<isize> for PartSpec {
+    fn from(i: isize) -> Self {
+        PartSpec::Index(i)
+    }
+}
+
+impl From<usize> for PartSpec {
+    fn from(i: usize) -> Self {
+        PartSpec::Index(i as isize)
+    }
+}
+
+impl From<&str> for PartSpec {
+    fn from(s: &str) -> Self {
+        PartSpec::Key(s.to_wolfram())
+    }
+}
+
+impl From<String> for PartSpec {
+    fn from(s: String) -> Self {
+        PartSpec::Key(s.to_wolfram())
+    }
+}
+
+impl From<WolframValue> for PartSpec {
+    fn from(v: WolframValue) -> Self {
+        PartSpec::Key(v)
+    }
+}
+
+impl WolframValue {
+    /// `Head[expr]`
+    pub fn head(&self) -> WolframValue {
+        match self {
+            WolframValue::Function(head, _) => head.as_ref().clone(),
+            WolframValue::String(_) => WolframValue::symbol("String"),
+            WolframValue::Bytes(_) => WolframValue::symbol("ByteArray"),
+            WolframValue::Symbol(_) | WolframValue::Skip | WolframValue::Rule | WolframValue::RuleDelayed => WolframValue::symbol("Symbol"),
+            WolframValue::Integer8(_)
+            | WolframValue::Integer16(_)
+            | WolframValue::Integer32(_)
+            | WolframValue::Integer64(_) => WolframValue::symbol("Integer"),
+            #[cfg(feature = "bigint")]
+            WolframValue::BigInteger(_) => WolframValue::symbol("Integer"),
+            WolframValue::Decimal64(_) | WolframValue::BigDecimal(_) => WolframValue::symbol("Real"),
+            WolframValue::PackedArray(_) => WolframValue::symbol("List"),
+            WolframValue::NumericArray(_) => WolframValue::symbol("NumericArray"),
+            WolframValue::Association(_) => WolframValue::symbol("Association"),
+        }
+    }
+    /// Arguments of a function or items of an array, atoms and associations have none
+    pub fn args(&self) -> &[WolframValue] {
+        match self {
+            WolframValue::Function(_, args) | WolframValue::PackedArray(args) | WolframValue::NumericArray(args) => args,
+            _ => &[],
+        }
+    }
+    pub fn args_mut(&mut self) -> &mut [WolframValue] {
+        match self {
+            WolframValue::Function(_, args) | WolframValue::PackedArray(args) | WolframValue::NumericArray(args) => args,
+            _ => &mut [],
+        }
+    }
+    /// `Length[expr]`
+    pub fn length(&self) -> usize {
+        match self {
+            WolframValue::Association(dict) => dict.len(),
+            _ => self.args().len(),
+        }
+    }
+    /// `Lookup[assoc, key]`
+    pub fn get(&self, key: impl ToWolfram) -> Result<&WolframValue, PartError> {
+        self.part_step(&PartSpec::Key(key.to_wolfram()), 1)
+    }
+    pub fn get_mut(&mut self, key: impl ToWolfram) -> Result<&mut WolframValue, PartError> {
+        self.part_step_mut(&PartSpec::Key(key.to_wolfram()), 1)
+    }
+    /// `Part[expr, i, j, ...]`, spans like `All` are rejected, see [`WolframValue::extract`]
+    ///
+    /// Packed and numeric arrays do not store their head, `0` on them only works with `extract`.
+    pub fn part<P: Into<PartSpec> + Clone>(&self, path: &[P]) -> Result<&WolframValue, PartError> {
+        let mut this = self;
+        for (depth, spec) in part_specs(path).iter().enumerate() {
+            this = this.part_step(spec, depth + 1)?;
+        }
+        Ok(this)
+    }
+    pub fn part_mut<P: Into<PartSpec> + Clone>(&mut self, path: &[P]) -> Result<&mut WolframValue, PartError> {
+        let mut this = self;
+        for (depth, spec) in part_specs(path).iter().enumerate() {
+            this = this.part_step_mut(spec, depth + 1)?;
+        }
+        Ok(this)
+    }
+    /// `Part[expr, i, All, ...]`, returns a new expression so that spans can be gathered
+    pub fn extract<P: Into<PartSpec> + Clone>(&self, path: &[P]) -> Result<WolframValue, PartError> {
+        self.extract_at(&part_specs(path), 1)
+    }
+    /// `expr[[i, All, ...]] = value`
+    pub fn set_part<P: Into<PartSpec> + Clone>(&mut self, path: &[P], value: impl ToWolfram) -> Result<(), PartError> {
+        self.set_part_at(&part_specs(path), &value.to_wolfram(), 1)
+    }
+
+    pub(crate) fn part_step(&self, spec: &PartSpec, depth: usize) -> Result<&WolframValue, PartError> {
+        match (self, spec) {
+            (_, PartSpec::All) => Err(PartError::Span { depth }),
+            (WolframValue::Function(head, _), PartSpec::Index(0)) => Ok(head),
+            (WolframValue::Function(_, args), PartSpec::Index(index))
+            | (WolframValue::PackedArray(args), PartSpec::Index(index))
+            | (WolframValue::NumericArray(args), PartSpec::Index(index)) => {
+                let i = position(*index, args.len(), depth)?;
+                Ok(&args[i])
+            }
+            (WolframValue::Association(dict), PartSpec::Index(index)) => {
+                let i = position(*index, dict.len(), depth)?;
+                Ok(dict.values().nth(i).map(|(_, v)| v).unwrap())
+            }
+            (WolframValue::Association(dict), PartSpec::Key(key)) => match dict.get(key) {
+                Some((_, v)) => Ok(v),
+                None => Err(PartError::KeyAbsent { depth, key: key.clone() }),
+            },
+            // a list of rules or `Association[rules]`, as `Part` reads `{a -> 1}[["a"]]`
+            (WolframValue::Function(_, args), PartSpec::Key(key)) => match args.iter().filter_map(WolframValue::as_rule).find(|(k, _)| *k == key) {
+                Some((_, v)) => Ok(v),
+                None => Err(PartError::KeyAbsent { depth, key: key.clone() }),
+            },
+            (WolframValue::PackedArray(_) | WolframValue::NumericArray(_), PartSpec::Key(key)) => {
+                Err(PartError::KeyAbsent { depth, key: key.clone() })
+            }
+            _ => Err(PartError::Atomic { depth, value: self.clone() }),
+        }
+    }
+    fn part_step_mut(&mut self, spec: &PartSpec, depth: usize) -> Result<&mut WolframValue, PartError> {
+        match (self, spec) {
+            (_, PartSpec::All) => Err(PartError::Span { depth }),
+            (WolframValue::Function(head, _), PartSpec::Index(0)) => Ok(head),
+            (WolframValue::Function(_, args), PartSpec::Index(index))
+            | (WolframValue::PackedArray(args), PartSpec::Index(index))
+            | (WolframValue::NumericArray(args), PartSpec::Index(index)) => {
+                let i = position(*index, args.len(), depth)?;
+                Ok(&mut args[i])
+            }
+            (WolframValue::Association(dict), PartSpec::Index(index)) => {
+                let i = position(*index, dict.len(), depth)?;
+                Ok(dict.values_mut().nth(i).map(|(_, v)| v).unwrap())
+            }
+            (WolframValue::Association(dict), PartSpec::Key(key)) => match dict.get_mut(key) {
+                Some((_, v)) => Ok(v),
+                None => Err(PartError::KeyAbsent { depth, key: key.clone() }),
+            },
+            (WolframValue::Function(_, args), PartSpec::Key(key)) => {
+                let found = args.iter_mut().find(|rule| matches!(rule.as_rule(), Some((k, _)) if k == key));
+                match found {
+                    Some(WolframValue::Function(_, rule)) => Ok(&mut rule[1]),
+                    _ => Err(PartError::KeyAbsent { depth, key: key.clone() }),
+                }
+            }
+            (WolframValue::PackedArray(_) | WolframValue::NumericArray(_), PartSpec::Key(key)) => {
+                Err(PartError::KeyAbsent { depth, key: key.clone() })
+            }
+            (this, _) => Err(PartError::Atomic { depth, value: this.clone() }),
+        }
+    }
+    fn extract_at(&self, path: &[PartSpec], depth: usize) -> Result<WolframValue, PartError> {
+        let (spec, rest) = match path.split_first() {
+            Some(s) => s,
+            None => return Ok(self.clone()),
+        };
+        match (self, spec) {
+            (WolframValue::Function(head, args), PartSpec::All) => {
+                let args = args.iter().map(|v| v.extract_at(rest, depth + 1)).collect::<Result<_, _>>()?;
+                Ok(WolframValue::Function(head.clone(), args))
+            }
+            (WolframValue::PackedArray(items), PartSpec::All) => {
+                Ok(WolframValue::PackedArray(items.iter().map(|v| v.extract_at(rest, depth + 1)).collect::<Result<_, _>>()?))
+            }
+            (WolframValue::NumericArray(items), PartSpec::All) => {
+                Ok(WolframValue::NumericArray(items.iter().map(|v| v.extract_at(rest, depth + 1)).collect::<Result<_, _>>()?))
+            }
+            (WolframValue::Association(dict), PartSpec::All) => {
+                let mut out = dict.clone();
+                for (_, v) in out.values_mut() {
+                    *v = v.extract_at(rest, depth + 1)?
+                }
+                Ok(WolframValue::Association(out))
+            }
+            (_, PartSpec::All) => Err(PartError::Atomic { depth, value: self.clone() }),
+            (_, PartSpec::Index(0)) => self.head().extract_at(rest, depth + 1),
+            _ => self.part_step(spec, depth)?.extract_at(rest, depth + 1),
+        }
+    }
+    fn set_part_at(&mut self, path: &[PartSpec], value: &WolframValue, depth: usize) -> Result<(), PartError> {
+        let (spec, rest) = match path.split_first() {
+            Some(s) => s,
+            None => {
+                *self = value.clone();
+                return Ok(());
+            }
+        };
+        match (self, spec) {
+            (WolframValue::Function(_, args) | WolframValue::PackedArray(args) | WolframValue::NumericArray(args), PartSpec::All) => {
+                args.iter_mut().try_for_each(|v| v.set_part_at(rest, value, depth + 1))
+            }
+            (WolframValue::Association(dict), PartSpec::All) => dict.values_mut().try_for_each(|(_, v)| v.set_part_at(rest, value, depth + 1)),
+            (this, PartSpec::All) => Err(PartError::Atomic { depth, value: this.clone() }),
+            (this, _) => this.part_step_mut(spec, depth)?.set_part_at(rest, value, depth + 1),
+        }
+    }
+}
+
+pub(crate) fn part_specs<P: Into<PartSpec> + Clone>(path: &[P]) -> Vec<PartSpec> {
+    path.iter().cloned().map(Into::into).collect()
+}
+
+/// Map a 1-based, possibly negative Wolfram index onto a 0-based offset
+pub(crate) fn position(index: isize, length: usize, depth: usize) -> Result<usize, PartError> {
+    let i = match index {
+        i if i > 0 => Some(i as usize - 1),
+        i if i < 0 => length.checked_sub(i.unsigned_abs()),
+        _ => None,
+    };
+    match i {
+        Some(i) if i < length => Ok(i),
+        _ => Err(PartError::OutOfRange { depth, index, length }),
+    }
+}
@@ -0,0 +1,204 @@
+use super::{array_items, decoding::nest, normalized_symbol_name};
+use crate::{FromWolfram, FromWolframError, ToWolfram, WolframValue};
+use image::{buffer::ConvertBuffer, DynamicImage, ImageBuffer, Luma, LumaA, Pixel, Primitive};
+use std::{convert::TryFrom, ops::Deref};
+
+/// Subpixel types of an `Image`, `u8` is `"Byte"`, `u16` is `"Bit16"` and `f32` is `"Real32"`
+pub trait ImageChannel: Primitive + ToWolfram + FromWolfram {
+    const IMAGE_TYPE: &'static str;
+    /// Type of the `NumericArray` holding the subpixels
+    const ARRAY_TYPE: &'static str;
+}
+
+impl ImageChannel for u8 {
+    const IMAGE_TYPE: &'static str = "Byte";
+    const ARRAY_TYPE: &'static str = "UnsignedInteger8";
+}
+
+impl ImageChannel for u16 {
+    const IMAGE_TYPE: &'static str = "Bit16";
+    const ARRAY_TYPE: &'static str = "UnsignedInteger16";
+}
+
+impl ImageChannel for f32 {
+    const IMAGE_TYPE: &'static str = "Real32";
+    const ARRAY_TYPE: &'static str = "Real32";
+}
+
+fn rule(key: &str, value: WolframValue) -> WolframValue {
+    WolframValue::Function(Box::new(WolframValue::symbol("Rule")), vec![WolframValue::symbol(key), value])
+}
+
+impl<P, C> ToWolfram for ImageBuffer<P, C>
+where
+    P: Pixel,
+    P::Subpixel: ImageChannel,
+    C: Deref<Target = [P::Subpixel]>,
+{
+    /// `Image[NumericArray[{{pixel, ...}, ...}, array type], type, ColorSpace -> "Grayscale" | "RGB"]`, alpha is the last channel
+    fn to_wolfram(&self) -> WolframValue {
+        let (width, height) = self.dimensions();
+        let channels = P::CHANNEL_COUNT as usize;
+        let raw = &self.as_raw()[..width as usize * height as usize * channels];
+        let pixels: Vec<WolframValue> = raw
+            .chunks_exact(channels)
+            .map(|c| match c {
+                [v] => v.to_wolfram(),
+                _ => WolframValue::list(c.iter().map(|v| v.to_wolfram()).collect()),
+            })
+            .collect();
+        let rows = match nest(&[height as usize, width as usize], &pixels) {
+            WolframValue::Function(_, rows) => rows,
+            _ => vec![],
+        };
+        let space = if channels <= 2 { "Grayscale" } else { "RGB" };
+        let data = WolframValue::new_numeric_array(rows, P::Subpixel::ARRAY_TYPE);
+        let args = vec![data, P::Subpixel::IMAGE_TYPE.to_wolfram(), rule("ColorSpace", space.to_wolfram())];
+        WolframValue::Function(Box::new(WolframValue::symbol("Image")), args)
+    }
+}
+
+impl ToWolfram for DynamicImage {
+    /// Images with other pixel types are converted to 32-bit RGBA
+    fn to_wolfram(&self) -> WolframValue {
+        match self {
+            DynamicImage::ImageLuma8(image) => image.to_wolfram(),
+            DynamicImage::ImageLumaA8(image) => image.to_wolfram(),
+            DynamicImage::ImageRgb8(image) => image.to_wolfram(),
+            DynamicImage::ImageRgba8(image) => image.to_wolfram(),
+            DynamicImage::ImageLuma16(image) => image.to_wolfram(),
+            DynamicImage::ImageLumaA16(image) => image.to_wolfram(),
+            DynamicImage::ImageRgb16(image) => image.to_wolfram(),
+            DynamicImage::ImageRgba16(image) => image.to_wolfram(),
+            DynamicImage::ImageRgb32F(image) => image.to_wolfram(),
+            DynamicImage::ImageRgba32F(image) => image.to_wolfram(),
+            _ => self.to_rgba32f().to_wolfram(),
+        }
+    }
+}
+
+/// Type, width, height, channel count and the subpixels of `Image[data, type, options]` in row-major order
+struct Raster<'a> {
+    kind: String,
+    width: u32,
+    height: u32,
+    channels: usize,
+    values: Vec<&'a WolframValue>,
+}
+
+/// Subpixels of a rectangular array of `dims`
+fn flatten<'a>(value: &'a WolframValue, dims: &[usize], out: &mut Vec<&'a WolframValue>) -> bool {
+    match (dims.split_first(), array_items(value)) {
+        (None, None) => {
+            out.push(value);
+            true
+        }
+        (Some((n, rest)), Some(items)) => items.len() == *n && items.iter().all(|v| flatten(v, rest, out)),
+        _ => false,
+    }
+}
+
+impl<'a> Raster<'a> {
+    fn parse(value: &'a WolframValue) -> Option<Self> {
+        let args = match value {
+            WolframValue::Function(head, args) if **head == WolframValue::symbol("Image") && !args.is_empty() => args,
+            _ => return None,
+        };
+        let (kind, options) = match args.get(1) {
+            Some(WolframValue::String(kind)) => (kind.as_str(), &args[2..]),
+            _ => ("Real32", &args[1..]),
+        };
+        let mut grayscale = None;
+        for option in options {
+            let (key, value) = match option {
+                WolframValue::Function(head, kv) if **head == WolframValue::symbol("Rule") && kv.len() == 2 => (&kv[0], &kv[1]),
+                _ => return None,
+            };
+            let key = match key {
+                WolframValue::Symbol(s) => normalized_symbol_name(s),
+                _ => return None,
+            };
+            match (key.as_str(), value) {
+                ("ColorSpace", WolframValue::String(s)) if s == "Grayscale" || s == "RGB" => grayscale = Some(s == "Grayscale"),
+                ("ColorSpace", WolframValue::Symbol(s)) if normalized_symbol_name(s) == "Automatic" => {}
+                ("Interleaving", WolframValue::Symbol(s)) if normalized_symbol_name(s) == "True" => {}
+                ("ColorSpace", _) | ("Interleaving", _) => return None,
+                _ => {}
+            }
+        }
+        let mut dims = vec![];
+        let mut level = &args[0];
+        while let Some(items) = array_items(level) {
+            dims.push(items.len());
+            match items.first() {
+                Some(first) => level = first,
+                None => break,
+            }
+        }
+        let channels = match dims.as_slice() {
+            [_, _] => 1,
+            [_, _, c] => *c,
+            _ => return None,
+        };
+        let mut values = Vec::with_capacity(dims.iter().product());
+        if !flatten(&args[0], &dims, &mut values) || grayscale.is_some_and(|g| g != (channels <= 2)) {
+            return None;
+        }
+        let kind = match kind {
+            "Real" | "Real64" => "Real32",
+            other => other,
+        };
+        Some(Self { kind: kind.to_string(), width: u32::try_from(dims[1]).ok()?, height: u32::try_from(dims[0]).ok()?, channels, values })
+    }
+}
+
+fn image_error(value: &WolframValue) -> FromWolframError {
+    FromWolframError { expected: "Image[data, \"Byte\" | \"Bit16\" | \"Real32\"] with grayscale or RGB channels", found: value.clone() }
+}
+
+impl<P> FromWolfram for ImageBuffer<P, Vec<P::Subpixel>>
+where
+    P: Pixel,
+    P::Subpixel: ImageChannel,
+{
+    /// An image with the type of the subpixels and as many channels as the pixel
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        let raster = Raster::parse(value).ok_or_else(|| image_error(value))?;
+        image_buffer(raster, value)
+    }
+}
+
+fn image_buffer<P>(raster: Raster, value: &WolframValue) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, FromWolframError>
+where
+    P: Pixel,
+    P::Subpixel: ImageChannel,
+{
+    if raster.kind != P::Subpixel::IMAGE_TYPE || raster.channels != P::CHANNEL_COUNT as usize {
+        return Err(image_error(value));
+    }
+    let data = raster.values.into_iter().map(P::Subpixel::from_wolfram).collect::<Result<Vec<_>, _>>()?;
+    ImageBuffer::from_raw(raster.width, raster.height, data).ok_or_else(|| image_error(value))
+}
+
+impl FromWolfram for DynamicImage {
+    /// The pixel type follows the image type and channel count, grayscale `"Real32"` images become RGB
+    fn from_wolfram(value: &WolframValue) -> Result<Self, FromWolframError> {
+        let raster = Raster::parse(value).ok_or_else(|| image_error(value))?;
+        let image = match (raster.kind.as_str(), raster.channels) {
+            ("Byte", 1) => DynamicImage::ImageLuma8(image_buffer(raster, value)?),
+            ("Byte", 2) => DynamicImage::ImageLumaA8(image_buffer(raster, value)?),
+            ("Byte", 3) => DynamicImage::ImageRgb8(image_buffer(raster, value)?),
+            ("Byte", 4) => DynamicImage::ImageRgba8(image_buffer(raster, value)?),
+            ("Bit16", 1) => DynamicImage::ImageLuma16(image_buffer(raster, value)?),
+            ("Bit16", 2) => DynamicImage::ImageLumaA16(image_buffer(raster, value)?),
+            ("Bit16", 3) => DynamicImage::ImageRgb16(image_buffer(raster, value)?),
+            ("Bit16", 4) => DynamicImage::ImageRgba16(image_buffer(raster, value)?),
+            ("Real32", 1) => DynamicImage::ImageRgb32F(image_buffer::<Luma<f32>>(raster, value)?.convert()),
+            ("Real32", 2) => DynamicImage::ImageRgba32F(image_buffer::<LumaA<f32>>(raster, value)?.convert()),
+            ("Real32", 3) => DynamicImage::ImageRgb32F(image_buffer(raster, value)?),
+            ("Real32", 4) => DynamicImage::ImageRgba32F(image_buffer(raster, value)?),
+            _ => return Err(image_error(value)),
+        };
+        Ok(image)
+    }
+}
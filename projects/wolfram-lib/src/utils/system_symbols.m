@@ -6,9 +6,11 @@ SetDirectory@NotebookDirectory[];
 (*LoadJavaClass["java.awt.Toolkit", AllowShortContext -> False];*)
 
 (*CopyAsUTF8[s_String] := JavaBlock[java`awt`Toolkit`getDefaultToolkit[]@getSystemClipboard[]@setContents[#, #]&@JavaNew["java.awt.datatransfer.StringSelection", s]];*)
-systems = Names["System`*"];
+(*binary_search compares UTF-8 bytes, and Sort would put shorter lists first, so pad with -1 to compare element by element*)
+codes = ToCharacterCode[#, "UTF8"]& /@ Names["System`*"];
+systems = FromCharacterCode[#, "UTF8"]& /@ SortBy[codes, PadRight[#, Max[Length /@ codes], -1]&];
 out = TemplateApply[
-	"/// `3`\npub const SYSTEM_SYMBOLS: [&str; `2`] = [\"`1`\"];",
+	"/// `3`\n/// Sorted by bytes for `binary_search`\npub static SYSTEM_SYMBOLS: [&str; `2`] = [\"`1`\"];",
 	{StringRiffle[systems, "\",\""], Length@systems, $Version}
 ];
 (*% // CopyAsUTF8*)
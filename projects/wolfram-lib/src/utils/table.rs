@@ -0,0 +1,142 @@
+//! Pieces shared by the `arrow` and `polars` conversions
+use super::{array_items, normalized_symbol_name};
+use crate::{FromWolfram, ToWolfram, WolframValue};
+use std::collections::BTreeSet;
+
+pub(crate) fn missing() -> WolframValue {
+    WolframValue::function("Missing", vec!["NotAvailable"])
+}
+
+/// `None`, `Null` and `Missing[...]`
+pub(crate) fn is_missing(value: &WolframValue) -> bool {
+    match value {
+        WolframValue::Symbol(s) => s == "None" || s == "Null",
+        WolframValue::Function(head, _) => **head == WolframValue::symbol("Missing"),
+        _ => false,
+    }
+}
+
+/// `FromUnixTime[t, TimeZone -> tz]` for `value` ticks of `1 / per_second` seconds, naive times are read in UTC
+pub(crate) fn unix_time(value: i64, per_second: i64, tz: Option<&str>) -> WolframValue {
+    let seconds = if value % per_second == 0 { (value / per_second).to_wolfram() } else { (value as f64 / per_second as f64).to_wolfram() };
+    let zone = match tz {
+        Some(tz) => tz.to_wolfram(),
+        None => 0.to_wolfram(),
+    };
+    let option = WolframValue::Function(Box::new(WolframValue::symbol("Rule")), vec![WolframValue::symbol("TimeZone"), zone]);
+    WolframValue::Function(Box::new(WolframValue::symbol("FromUnixTime")), vec![seconds, option])
+}
+
+/// `DateObject["yyyy-mm-dd"]` of the days since 1970-01-01 in the proleptic Gregorian calendar
+#[cfg(feature = "polars")]
+pub(crate) fn date(days: i64) -> WolframValue {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    crate::objects::date_object(&format!("{:04}-{:02}-{:02}", year, month, day))
+}
+
+/// `Dataset[{<|"column" -> value, ...|>, ...}]` with the columns in the given order
+pub(crate) fn dataset(names: &[WolframValue], columns: &[Vec<WolframValue>], rows: usize) -> WolframValue {
+    let rows = (0..rows).map(|i| {
+        let entries = names.iter().zip(columns.iter()).map(|(name, c)| (name.clone(), c[i].clone()));
+        WolframValue::association_in_order(entries.collect())
+    });
+    WolframValue::Function(Box::new(WolframValue::symbol("Dataset")), vec![WolframValue::list(rows.collect())])
+}
+
+/// Narrowest type that holds every non-missing cell of a column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CellType {
+    Null,
+    Integer,
+    Real,
+    Boolean,
+    Bytes,
+    /// Strings, other expressions are kept as their `InputForm`
+    Text,
+}
+
+pub(crate) fn cell_type(cells: &[Option<&WolframValue>]) -> CellType {
+    let values: Vec<&WolframValue> = cells.iter().flatten().copied().collect();
+    let all = |check: fn(&WolframValue) -> bool| values.iter().all(|v| check(v));
+    if values.is_empty() {
+        CellType::Null
+    }
+    else if all(|v| i64::from_wolfram(v).is_ok()) {
+        CellType::Integer
+    }
+    else if all(|v| f64::from_wolfram(v).is_ok()) {
+        CellType::Real
+    }
+    else if all(|v| bool::from_wolfram(v).is_ok()) {
+        CellType::Boolean
+    }
+    else if all(|v| matches!(v, WolframValue::Bytes(_))) {
+        CellType::Bytes
+    }
+    else {
+        CellType::Text
+    }
+}
+
+pub(crate) fn text(value: &WolframValue) -> String {
+    match value {
+        WolframValue::String(s) => s.clone(),
+        _ => value.to_string(),
+    }
+}
+
+/// Entries of an association in either form of [`WolframValue::association_in_order`]
+pub(crate) fn association_entries(value: &WolframValue) -> Option<Vec<(&WolframValue, &WolframValue)>> {
+    match value {
+        WolframValue::Association(dict) => Some(dict.iter().map(|(k, (_, v))| (k, v)).collect()),
+        WolframValue::Function(head, rules) if matches!(head.as_ref(), WolframValue::Symbol(s) if normalized_symbol_name(s) == "Association") => {
+            rules.iter().map(WolframValue::as_rule).collect()
+        }
+        _ => None,
+    }
+}
+
+/// Cells that are not missing
+pub(crate) fn present(cells: &[WolframValue]) -> Vec<Option<&WolframValue>> {
+    cells.iter().map(|v| Some(v).filter(|v| !is_missing(v))).collect()
+}
+
+/// Name and cells of a column, missing cells are `None`
+pub(crate) type Column<'a> = (String, Vec<Option<&'a WolframValue>>);
+
+/// Columns of `Dataset[rows]`, a list of associations or an association of columns
+///
+/// Columns are in the order of the association, or in the order keys first appear in the rows.
+pub(crate) fn table_columns(value: &WolframValue) -> Result<Vec<Column<'_>>, String> {
+    let value = match value {
+        WolframValue::Function(head, args) if **head == WolframValue::symbol("Dataset") && !args.is_empty() => &args[0],
+        _ => value,
+    };
+    if let Some(entries) = association_entries(value) {
+        if entries.iter().all(|(_, v)| array_items(v).is_some()) {
+            return Ok(entries.into_iter().map(|(k, v)| (text(k), present(array_items(v).unwrap_or_default()))).collect());
+        }
+    }
+    let rows = array_items(value).ok_or_else(|| format!("{} is not tabular", value))?;
+    let mut dicts = vec![];
+    for row in rows {
+        match association_entries(row) {
+            Some(entries) => dicts.push(entries),
+            None => return Err(format!("row {} is not an association", row)),
+        }
+    }
+    let mut seen = BTreeSet::new();
+    let keys: Vec<&WolframValue> = dicts.iter().flatten().map(|(k, _)| *k).filter(|k| seen.insert(*k)).collect();
+    // the last entry of a key wins, as in `Association`
+    let column = |key: &WolframValue| dicts.iter().map(|d| d.iter().rev().find(|(k, _)| *k == key).map(|(_, v)| *v).filter(|v| !is_missing(v))).collect();
+    Ok(keys.into_iter().map(|k| (text(k), column(k))).collect())
+}
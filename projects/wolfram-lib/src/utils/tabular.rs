@@ -0,0 +1,259 @@
+use crate::{objects::date_object, ToWolfram, WolframValue};
+use csv::{ReaderBuilder, Trim};
+
+/// Shape of the expression returned by [`parse_csv`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvOutput {
+    /// `Dataset[{<|"a" -> 1, ...|>, ...}]`, or a dataset of lists without header
+    Dataset,
+    /// `{{"a", ...}, {1, ...}, ...}` with the header as first row
+    Rows,
+    /// `<|"a" -> {1, ...}, ...|>`, or a list of columns without header, machine number columns are packed
+    Columns,
+}
+
+/// How CSV or TSV text is read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    /// Whether the first row holds column names, `None` guesses from its cells
+    pub header: Option<bool>,
+    pub output: CsvOutput,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self { delimiter: b',', header: None, output: CsvOutput::Dataset }
+    }
+}
+
+impl CsvOptions {
+    /// Tab separated values
+    pub fn tsv() -> Self {
+        Self { delimiter: b'\t', ..Self::default() }
+    }
+}
+
+/// Type of a column, the narrowest that holds every non-empty cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Missing,
+    Integer,
+    Real,
+    Boolean,
+    Date,
+    String,
+}
+
+/// Digits with an optional sign, of any length
+fn is_digits(cell: &str) -> bool {
+    let digits = cell.strip_prefix(['+', '-'].as_ref()).unwrap_or(cell);
+    !digits.is_empty() && digits.bytes().all(|c| c.is_ascii_digit())
+}
+
+/// Integers beyond `i64` need the `bigint` feature, otherwise they stay text
+fn is_integer(cell: &str) -> bool {
+    is_digits(cell) && (cfg!(feature = "bigint") || cell.parse::<i64>().is_ok())
+}
+
+fn integer(cell: &str) -> WolframValue {
+    match cell.parse::<i64>() {
+        Ok(i) => i.to_wolfram(),
+        #[cfg(feature = "bigint")]
+        Err(_) => cell.parse::<num::BigInt>().map(WolframValue::BigInteger).unwrap_or_else(|_| cell.to_wolfram()),
+        #[cfg(not(feature = "bigint"))]
+        Err(_) => cell.to_wolfram(),
+    }
+}
+
+fn is_real(cell: &str) -> bool {
+    cell.bytes().any(|c| c.is_ascii_digit())
+        && cell.bytes().all(|c| c.is_ascii_digit() || b"+-.eE".contains(&c))
+        && cell.parse::<f64>().is_ok()
+}
+
+fn is_boolean(cell: &str) -> bool {
+    cell.eq_ignore_ascii_case("true") || cell.eq_ignore_ascii_case("false")
+}
+
+fn digits(range: &[u8]) -> bool {
+    range.iter().all(u8::is_ascii_digit)
+}
+
+/// `hh:mm`, `hh:mm:ss` or `hh:mm:ss.fff`
+fn is_clock(time: &[u8]) -> bool {
+    let seconds = match time.get(5) {
+        None => true,
+        Some(b':') => match time.get(8) {
+            None => time.len() == 8 && digits(&time[6..]),
+            Some(b'.') => time.len() > 9 && digits(&time[6..8]) && digits(&time[9..]),
+            Some(_) => false,
+        },
+        Some(_) => false,
+    };
+    time.len() >= 5 && digits(&time[..2]) && time[2] == b':' && digits(&time[3..5]) && seconds
+}
+
+/// A clock time, optionally followed by `Z` or an offset as in `12:00:00+01:00`
+fn is_time(time: &[u8]) -> bool {
+    let offset = time.len().checked_sub(6).filter(|&i| matches!(time[i], b'+' | b'-') && is_clock(&time[i + 1..]));
+    match (offset, time.split_last()) {
+        (Some(i), _) => is_clock(&time[..i]),
+        (None, Some((b'Z', clock))) => is_clock(clock),
+        _ => is_clock(time),
+    }
+}
+
+/// `yyyy-mm-dd`, optionally followed by a time as in `2020-01-31T12:00:00`
+fn is_date(cell: &str) -> bool {
+    let cell = cell.as_bytes();
+    let date = cell.get(..10).unwrap_or_default();
+    date.len() == 10
+        && digits(&date[..4])
+        && date[4] == b'-'
+        && digits(&date[5..7])
+        && date[7] == b'-'
+        && digits(&date[8..])
+        && match cell.get(10) {
+            None => true,
+            Some(b'T') | Some(b' ') => is_time(&cell[11..]),
+            Some(_) => false,
+        }
+}
+
+fn kind(cell: &str) -> Column {
+    if cell.is_empty() {
+        Column::Missing
+    }
+    else if is_integer(cell) {
+        Column::Integer
+    }
+    else if is_digits(cell) {
+        Column::String
+    }
+    else if is_real(cell) {
+        Column::Real
+    }
+    else if is_boolean(cell) {
+        Column::Boolean
+    }
+    else if is_date(cell) {
+        Column::Date
+    }
+    else {
+        Column::String
+    }
+}
+
+fn infer(cells: &[&str]) -> Column {
+    cells.iter().map(|c| kind(c)).fold(Column::Missing, |a, b| match (a, b) {
+        (Column::Missing, b) => b,
+        (a, Column::Missing) => a,
+        (a, b) if a == b => a,
+        (Column::Integer, Column::Real) | (Column::Real, Column::Integer) => Column::Real,
+        _ => Column::String,
+    })
+}
+
+fn cell(column: Column, cell: &str) -> WolframValue {
+    if cell.is_empty() {
+        return WolframValue::function("Missing", vec!["NotAvailable"]);
+    }
+    match column {
+        Column::Integer => integer(cell),
+        Column::Real => cell.parse::<f64>().unwrap_or_default().to_wolfram(),
+        Column::Boolean => cell.eq_ignore_ascii_case("true").to_wolfram(),
+        Column::Date => date_object(cell),
+        Column::Missing | Column::String => cell.to_wolfram(),
+    }
+}
+
+/// Non-empty text cells that are not numbers, booleans or dates, each different
+fn looks_like_header(row: &[String]) -> bool {
+    let mut names: Vec<&str> = row.iter().map(|s| s.as_str()).collect();
+    if names.iter().any(|c| c.is_empty() || kind(c) != Column::String) {
+        return false;
+    }
+    names.sort_unstable();
+    names.windows(2).all(|w| w[0] != w[1])
+}
+
+/// Column names of the header, missing names are the column position and repeated names get a suffix
+fn unique_names(header: &[String], width: usize) -> Vec<WolframValue> {
+    let mut names: Vec<WolframValue> = Vec::with_capacity(width);
+    for i in 0..width {
+        let name = match header.get(i) {
+            Some(name) => {
+                let mut unique = name.to_wolfram();
+                let mut n = 0;
+                while names.contains(&unique) {
+                    n += 1;
+                    unique = format!("{}.{}", name, n).to_wolfram();
+                }
+                unique
+            }
+            None => (i + 1).to_wolfram(),
+        };
+        names.push(name);
+    }
+    names
+}
+
+/// `Import[input, "CSV"]` with a type inferred for every column
+///
+/// Integers, reals, `true`/`false` and `yyyy-mm-dd` dates are converted when every non-empty
+/// cell of the column has that form, empty cells become `Missing["NotAvailable"]`.
+/// A repeated column name gets a suffix, `a`, `a.1`, `a.2`, so that no column is lost.
+/// Columns keep the order of the file, see [`WolframValue::association_in_order`].
+pub fn parse_csv(input: &str, options: &CsvOptions) -> Result<WolframValue, csv::Error> {
+    let mut reader =
+        ReaderBuilder::new().delimiter(options.delimiter).has_headers(false).flexible(true).trim(Trim::All).from_reader(input.as_bytes());
+    let mut rows = vec![];
+    for record in reader.records() {
+        rows.push(record?.iter().map(String::from).collect::<Vec<_>>());
+    }
+    let header = match (options.header, rows.first()) {
+        (Some(true), Some(_)) => Some(rows.remove(0)),
+        (None, Some(first)) if rows.len() > 1 && looks_like_header(first) => Some(rows.remove(0)),
+        _ => None,
+    };
+    let width = rows.iter().chain(header.iter()).map(|r| r.len()).max().unwrap_or(0);
+    let columns: Vec<Column> = (0..width)
+        .map(|i| {
+            let cells: Vec<&str> = rows.iter().map(|r| r.get(i).map_or("", |c| c.as_str())).collect();
+            infer(&cells)
+        })
+        .collect();
+    let names: Vec<WolframValue> = match &header {
+        Some(header) => unique_names(header, width),
+        None => vec![],
+    };
+    let row = |r: &Vec<String>| -> Vec<WolframValue> { columns.iter().enumerate().map(|(i, c)| cell(*c, r.get(i).map_or("", |s| s))).collect() };
+    let out = match options.output {
+        CsvOutput::Dataset => {
+            let records = rows.iter().map(|r| match header {
+                Some(_) => WolframValue::association_in_order(names.iter().cloned().zip(row(r)).collect()),
+                None => WolframValue::list(row(r)),
+            });
+            WolframValue::Function(Box::new(WolframValue::symbol("Dataset")), vec![WolframValue::list(records.collect())])
+        }
+        CsvOutput::Rows => {
+            let mut out: Vec<WolframValue> = header.iter().map(|_| WolframValue::list(names.clone())).collect();
+            out.extend(rows.iter().map(|r| WolframValue::list(row(r))));
+            WolframValue::list(out)
+        }
+        CsvOutput::Columns => {
+            let columns = columns.iter().enumerate().map(|(i, c)| {
+                let cells: Vec<WolframValue> = rows.iter().map(|r| cell(*c, r.get(i).map_or("", |s| s))).collect();
+                let packed = matches!(c, Column::Integer | Column::Real)
+                    && rows.iter().all(|r| r.get(i).is_some_and(|s| !s.is_empty() && (*c == Column::Real || s.parse::<i64>().is_ok())));
+                if packed && !cells.is_empty() { WolframValue::PackedArray(cells) } else { WolframValue::list(cells) }
+            });
+            match header {
+                Some(_) => WolframValue::association_in_order(names.into_iter().zip(columns).collect()),
+                None => WolframValue::list(columns.collect()),
+            }
+        }
+    };
+    Ok(out)
+}
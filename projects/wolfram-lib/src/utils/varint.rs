@@ -0,0 +1,30 @@
+//! Lengths as unsigned LEB128, at most 10 bytes for a `usize`
+
+/// Write `n` at the start of `buffer` and return the number of bytes
+pub(crate) fn encode_varint(mut n: usize, buffer: &mut [u8]) -> usize {
+    let mut i = 0;
+    while n >= 0x80 {
+        buffer[i] = (n as u8) | 0x80;
+        n >>= 7;
+        i += 1;
+    }
+    buffer[i] = n as u8;
+    i + 1
+}
+
+/// Value and length of the varint at the start of `input`, `None` if it is cut off or overflows
+pub(crate) fn decode_varint(input: &[u8]) -> Option<(usize, usize)> {
+    let mut n = 0usize;
+    for (i, byte) in input.iter().enumerate().take(10) {
+        let bits = (*byte & 0x7F) as usize;
+        let shift = 7 * i as u32;
+        if shift >= usize::BITS || (shift > 0 && bits >> (usize::BITS - shift) != 0) {
+            return None;
+        }
+        n |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Some((n, i + 1));
+        }
+    }
+    None
+}
@@ -0,0 +1,70 @@
+use crate::WolframValue;
+use alloc::{vec, vec::Vec};
+
+/// Parents before children, starting with the expression itself
+pub struct PreOrder<'a> {
+    stack: Vec<&'a WolframValue>,
+}
+
+/// Children before parents, ending with the expression itself
+pub struct PostOrder<'a> {
+    stack: Vec<(&'a WolframValue, bool)>,
+}
+
+/// Subexpressions at exactly one level, like `Level[expr, {n}]`
+pub struct Level<'a> {
+    level: usize,
+    stack: Vec<(usize, &'a WolframValue)>,
+}
+
+impl<'a> Iterator for PreOrder<'a> {
+    type Item = &'a WolframValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let this = self.stack.pop()?;
+        self.stack.extend(this.children().into_iter().rev());
+        Some(this)
+    }
+}
+
+impl<'a> Iterator for PostOrder<'a> {
+    type Item = &'a WolframValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (this, expanded) = self.stack.pop()?;
+            if expanded || this.children().is_empty() {
+                return Some(this);
+            }
+            self.stack.push((this, true));
+            self.stack.extend(this.children().into_iter().rev().map(|v| (v, false)));
+        }
+    }
+}
+
+impl<'a> Iterator for Level<'a> {
+    type Item = &'a WolframValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (level, this) = self.stack.pop()?;
+            if level == self.level {
+                return Some(this);
+            }
+            self.stack.extend(this.children().into_iter().rev().map(|v| (level + 1, v)));
+        }
+    }
+}
+
+impl WolframValue {
+    pub fn pre_order(&self) -> PreOrder<'_> {
+        PreOrder { stack: vec![self] }
+    }
+    pub fn post_order(&self) -> PostOrder<'_> {
+        PostOrder { stack: vec![(self, false)] }
+    }
+    /// `Level[expr, {n}]`, level 0 is the expression itself
+    pub fn level(&self, n: usize) -> Level<'_> {
+        Level { level: n, stack: vec![(0, self)] }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::WolframValue;
+use core::mem::size_of;
+
+impl WolframValue {
+    /// `LeafCount[expr]`, associations count as `Association[Rule[k, v], ...]` and arrays as lists
+    pub fn leaf_count(&self) -> usize {
+        match self {
+            WolframValue::Function(head, args) => head.leaf_count() + args.iter().map(|v| v.leaf_count()).sum::<usize>(),
+            WolframValue::PackedArray(items) | WolframValue::NumericArray(items) => 1 + items.iter().map(|v| v.leaf_count()).sum::<usize>(),
+            WolframValue::Association(dict) => 1 + dict.iter().map(|(k, (_, v))| 1 + k.leaf_count() + v.leaf_count()).sum::<usize>(),
+            _ => 1,
+        }
+    }
+    /// `Depth[expr]`, heads are not counted
+    pub fn depth(&self) -> usize {
+        1 + self.children().into_iter().map(|v| v.depth()).max().unwrap_or(0)
+    }
+    /// Approximate in-memory size, the enum itself plus every heap buffer it owns
+    pub fn byte_count(&self) -> usize {
+        let heap = match self {
+            WolframValue::Function(head, args) => head.byte_count() + args.iter().map(|v| v.byte_count()).sum::<usize>(),
+            WolframValue::String(s) | WolframValue::Symbol(s) | WolframValue::BigDecimal(s) => s.len(),
+            WolframValue::Bytes(b) => b.len(),
+            #[cfg(feature = "bigint")]
+            WolframValue::BigInteger(i) => (i.bits() as usize).div_ceil(8),
+            WolframValue::PackedArray(v) | WolframValue::NumericArray(v) => v.iter().map(|v| v.byte_count()).sum(),
+            WolframValue::Association(dict) => dict.iter().map(|(k, (r, v))| k.byte_count() + r.byte_count() + v.byte_count()).sum(),
+            _ => 0,
+        };
+        size_of::<WolframValue>() + heap
+    }
+}
@@ -0,0 +1,102 @@
+mod iter;
+mod metrics;
+
+use crate::WolframValue;
+use alloc::{boxed::Box, vec, vec::Vec};
+
+pub use self::iter::{Level, PostOrder, PreOrder};
+
+/// Read-only traversal, override `visit` and call [`walk`] to keep descending
+pub trait Visitor {
+    fn visit(&mut self, value: &WolframValue) {
+        walk(self, value)
+    }
+}
+
+/// In-place traversal, override `visit_mut` and call [`walk_mut`] to keep descending
+pub trait VisitorMut {
+    fn visit_mut(&mut self, value: &mut WolframValue) {
+        walk_mut(self, value)
+    }
+}
+
+/// Bottom-up rebuilding traversal, override `fold` and call [`fold_children`] to keep descending
+pub trait Fold {
+    fn fold(&mut self, value: WolframValue) -> WolframValue {
+        fold_children(self, value)
+    }
+}
+
+/// Visit the head and arguments of a function, the items of an array, or the keys and values of an association
+pub fn walk<V: Visitor + ?Sized>(visitor: &mut V, value: &WolframValue) {
+    match value {
+        WolframValue::Function(head, args) => {
+            visitor.visit(head);
+            for v in args {
+                visitor.visit(v)
+            }
+        }
+        WolframValue::PackedArray(items) | WolframValue::NumericArray(items) => {
+            for v in items {
+                visitor.visit(v)
+            }
+        }
+        WolframValue::Association(dict) => {
+            for (k, (_, v)) in dict {
+                visitor.visit(k);
+                visitor.visit(v)
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Association keys are immutable in place, only the values are visited
+pub fn walk_mut<V: VisitorMut + ?Sized>(visitor: &mut V, value: &mut WolframValue) {
+    match value {
+        WolframValue::Function(head, args) => {
+            visitor.visit_mut(head);
+            for v in args {
+                visitor.visit_mut(v)
+            }
+        }
+        WolframValue::PackedArray(items) | WolframValue::NumericArray(items) => {
+            for v in items {
+                visitor.visit_mut(v)
+            }
+        }
+        WolframValue::Association(dict) => {
+            for (_, v) in dict.values_mut() {
+                visitor.visit_mut(v)
+            }
+        }
+        _ => (),
+    }
+}
+
+pub fn fold_children<F: Fold + ?Sized>(folder: &mut F, value: WolframValue) -> WolframValue {
+    match value {
+        WolframValue::Function(head, args) => {
+            let head = folder.fold(*head);
+            WolframValue::Function(Box::new(head), args.into_iter().map(|v| folder.fold(v)).collect())
+        }
+        WolframValue::PackedArray(items) => WolframValue::PackedArray(items.into_iter().map(|v| folder.fold(v)).collect()),
+        WolframValue::NumericArray(items) => WolframValue::NumericArray(items.into_iter().map(|v| folder.fold(v)).collect()),
+        WolframValue::Association(dict) => {
+            let map = dict.into_iter().map(|(k, (r, v))| (folder.fold(k), (r, folder.fold(v))));
+            WolframValue::Association(map.collect())
+        }
+        _ => value,
+    }
+}
+
+impl WolframValue {
+    /// Subexpressions in `Level` order, heads and association keys are skipped
+    pub(crate) fn children(&self) -> Vec<&WolframValue> {
+        match self {
+            WolframValue::Function(_, args) | WolframValue::PackedArray(args) | WolframValue::NumericArray(args) => args.iter().collect(),
+            WolframValue::Association(dict) => dict.values().map(|(_, v)| v).collect(),
+            _ => vec![],
+        }
+    }
+}
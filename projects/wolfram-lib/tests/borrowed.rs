@@ -0,0 +1,79 @@
+use wolfram_wxf::{utils::inflate, wl, DecodeError, DecodeLimit, DecodeLimits, ToWolfram, WolframValue, WolframValueRef};
+
+fn within(input: &[u8], slice: &[u8]) -> bool {
+    input.as_ptr_range().contains(&slice.as_ptr())
+}
+
+#[test]
+fn test_fixtures() {
+    let fixtures: [&[u8]; 6] = [
+        include_bytes!("fixtures/association.wxf"),
+        include_bytes!("fixtures/bytes.wxf"),
+        include_bytes!("fixtures/function-curry.wxf"),
+        include_bytes!("fixtures/integer-9223372036854775808.wxf"),
+        include_bytes!("fixtures/string-chinese.wxf"),
+        include_bytes!("fixtures/symbol-system-none.wxf"),
+    ];
+    for input in fixtures.iter() {
+        let borrowed = WolframValueRef::from_bytes(input).unwrap();
+        assert_eq!(borrowed.to_owned(), WolframValue::from_bytes(input).unwrap());
+        assert_eq!(borrowed.to_wolfram(), WolframValue::from(borrowed.clone()));
+    }
+}
+
+#[test]
+fn test_zero_copy() {
+    let input = wl!(f["中文", x, <|"key" -> #(vec![1u8, 2])|>]).to_bytes();
+    let value = WolframValueRef::from_bytes(&input).unwrap();
+    let (head, args) = match &value {
+        WolframValueRef::Function(head, args) => (head, args),
+        _ => unreachable!(),
+    };
+    assert_eq!(**head, WolframValueRef::Symbol("Global`f"));
+    match &args[..] {
+        [WolframValueRef::String(s), WolframValueRef::Symbol(x), WolframValueRef::Association(items)] => {
+            assert_eq!(*s, "中文");
+            assert!(within(&input, s.as_bytes()) && within(&input, x.as_bytes()));
+            let (key, (rule, _)) = &items[0];
+            assert_eq!((key, rule), (&WolframValueRef::String("key"), &WolframValueRef::Rule));
+        }
+        _ => panic!("{:?}", args),
+    }
+}
+
+#[test]
+fn test_arrays() {
+    //Normal@BinarySerialize[Developer`ToPackedArray[{{1, 2}, {3, 4}}]]
+    let packed = [56, 58, 193, 0, 2, 2, 2, 1, 2, 3, 4];
+    match WolframValueRef::from_bytes(&packed).unwrap() {
+        WolframValueRef::PackedArray(a) => {
+            assert_eq!((a.type_name(), a.dimensions.as_slice(), a.data), (Some("Integer8"), &[2, 2][..], &[1, 2, 3, 4][..]));
+            assert!(within(&packed, a.data));
+            assert_eq!(a.len(), 4);
+            assert_eq!(WolframValueRef::PackedArray(a).to_owned(), wl!({{1, 2}, {3, 4}}));
+        }
+        v => panic!("{:?}", v),
+    }
+    //Normal@BinarySerialize[NumericArray[{1, 2}, "UnsignedInteger8"]]
+    let numeric = [56, 58, 194, 16, 1, 2, 1, 2];
+    let value = WolframValueRef::from_bytes(&numeric).unwrap();
+    assert_eq!(value.to_owned(), WolframValue::from_bytes(&numeric).unwrap());
+}
+
+#[test]
+fn test_compressed() {
+    let value = wl!({"a", "b", 3});
+    let solid = value.to_compressed();
+    assert_eq!(WolframValueRef::from_bytes(&solid), Err(DecodeError::Compressed));
+    let input = inflate(&solid, &DecodeLimits::default()).unwrap();
+    assert_eq!(input, value.to_bytes());
+    assert_eq!(WolframValueRef::from_bytes(&input).unwrap().to_owned(), value);
+}
+
+#[test]
+fn test_limits() {
+    let input = vec![0; 100].to_wolfram_bytes();
+    let limits = DecodeLimits { max_elements: 50, ..DecodeLimits::default() };
+    assert!(matches!(WolframValueRef::from_bytes_with(&input, &limits), Err(DecodeError::LimitExceeded { limit: DecodeLimit::Elements, .. })));
+    assert!(matches!(WolframValueRef::from_bytes(b"8:I\x02x1"), Err(DecodeError::InvalidNumber { .. })));
+}
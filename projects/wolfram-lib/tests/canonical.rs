@@ -0,0 +1,80 @@
+use num::rational::Ratio;
+use std::collections::HashMap;
+use wolfram_wxf::{assert_wolfram_eq, utils::{canonical_order, SYSTEM_SYMBOLS}, wl, EncodeOptions, ToWolfram, WolframValue};
+
+fn check(value: impl ToWolfram, fixture: &[u8]) {
+    let value = value.to_wolfram();
+    assert_eq!(value.to_bytes_with(&EncodeOptions::canonical()), fixture, "{}", value);
+    assert_wolfram_eq!(loose: value, WolframValue::from_bytes(fixture).unwrap());
+}
+
+//every fixture is what fixtures/generate.wl writes with BinarySerialize, rerun it to refresh them
+#[test]
+fn test_fixtures() {
+    //0
+    check(0usize, include_bytes!("fixtures/integer-0.wxf"));
+    //-128
+    check(-128i64, include_bytes!("fixtures/integer-neg-128.wxf"));
+    //128
+    check(128isize, include_bytes!("fixtures/integer-128.wxf"));
+    //32768
+    check(WolframValue::Integer64(32768), include_bytes!("fixtures/integer-32768.wxf"));
+    //2147483648
+    check(WolframValue::integer(2147483648u64), include_bytes!("fixtures/integer-2147483648.wxf"));
+    //9223372036854775808
+    check(9223372036854775808u128, include_bytes!("fixtures/integer-9223372036854775808.wxf"));
+    //0.3
+    check(0.3, include_bytes!("fixtures/real-0.3.wxf"));
+    //"中文"
+    check("中文", include_bytes!("fixtures/string-chinese.wxf"));
+    //True
+    check(true, include_bytes!("fixtures/symbol-true.wxf"));
+    //x
+    check(wl!(x), include_bytes!("fixtures/symbol-global.wxf"));
+    //System`None
+    check(WolframValue::symbol("System`None"), include_bytes!("fixtures/symbol-system-none.wxf"));
+    //Sin[1][2][3]
+    check(wl!(Sin[1][2][3]), include_bytes!("fixtures/function-curry.wxf"));
+    //1/2
+    check(Ratio::new_raw(1usize, 2usize), include_bytes!("fixtures/rational.wxf"));
+    //{0}
+    check(vec![0u64], include_bytes!("fixtures/list-integer.wxf"));
+    //ByteArray[{1, 2, 3}]
+    check(WolframValue::Bytes(vec![1, 2, 3]), include_bytes!("fixtures/bytes.wxf"));
+    //<|1 -> 2|>
+    let mut dict = HashMap::new();
+    dict.insert(1usize, 2usize);
+    check(dict, include_bytes!("fixtures/association.wxf"));
+    //KeySort[<|"b" -> 1, "a" -> 2, 1 -> 3|>], BinarySerialize alone keeps insertion order
+    check(wl!(<|"b" -> 1, "a" -> 2, 1 -> 3|>), include_bytes!("fixtures/association-keysort.wxf"));
+}
+
+#[test]
+fn test_default_unchanged() {
+    assert_eq!(WolframValue::Integer64(0).to_bytes(), [56, 58, 76, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(0usize.to_wolfram(), WolframValue::Integer64(0));
+}
+
+#[test]
+fn test_order() {
+    //Sort[{f[x, y], "b", g[z], x, 2, "B", 1.5, "a", 1}]
+    let mut list = wl!({f[x, y], "b", g[z], x, 2, "B", 1.5, "a", 1}).args().to_vec();
+    list.sort_by(canonical_order);
+    assert_eq!(WolframValue::list(list).to_string(), r#"{1,1.5`,2,"a","b","B",x,g[z],f[x,y]}"#);
+}
+
+#[test]
+fn test_system_symbols_sorted() {
+    assert!(SYSTEM_SYMBOLS.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn test_fixtures_generated() {
+    let script = include_str!("fixtures/generate.wl");
+    for entry in std::fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures")).unwrap() {
+        let name = entry.unwrap().file_name().into_string().unwrap();
+        if let Some(stem) = name.strip_suffix(".wxf") {
+            assert!(script.contains(&format!("write[\"{}\"", stem)), "{} is not in generate.wl", name);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "codec")]
+
+use bytes::BytesMut;
+use futures::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::codec::{Decoder, Encoder, Framed, LengthDelimitedCodec};
+use wolfram_wxf::{wl, CompressOptions, DecodeError, DecodeLimit, ToWolfram, WXFCodec, WXFError, WolframValue};
+
+fn messages() -> Vec<WolframValue> {
+    vec![wl!(f[x, 1]), "中文".to_wolfram(), WolframValue::list(vec![2.5.to_wolfram(); 1000]), wl!(<|"a" -> {1, 2}|>)]
+}
+
+/// Echo every frame back through a loopback socket
+async fn echo(client: WXFCodec) -> Vec<WolframValue> {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut framed = Framed::new(socket, WXFCodec::new());
+        while let Some(message) = framed.next().await {
+            framed.send(message.unwrap()).await.unwrap();
+        }
+    });
+    let mut framed = Framed::new(TcpStream::connect(address).await.unwrap(), client);
+    let mut out = vec![];
+    for message in messages() {
+        framed.send(&message).await.unwrap();
+        out.push(framed.next().await.unwrap().unwrap());
+    }
+    drop(framed);
+    server.await.unwrap();
+    out
+}
+
+#[tokio::test]
+async fn test_loopback() {
+    assert_eq!(echo(WXFCodec::new()).await, messages());
+    assert_eq!(echo(WXFCodec::compressed(CompressOptions::fast())).await, messages());
+}
+
+#[tokio::test]
+async fn test_length_delimited() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut framed = Framed::new(socket, LengthDelimitedCodec::new());
+        framed.next().await.unwrap().unwrap()
+    });
+    let mut framed = Framed::new(TcpStream::connect(address).await.unwrap(), WXFCodec::new());
+    framed.send(wl!(f[x])).await.unwrap();
+    assert_eq!(server.await.unwrap()[..], wl!(f[x]).to_bytes()[..]);
+}
+
+#[test]
+fn test_partial() {
+    let mut codec = WXFCodec::new();
+    let mut frames = BytesMut::new();
+    for message in messages() {
+        codec.encode(&message, &mut frames).unwrap();
+    }
+    // one byte at a time
+    let mut src = BytesMut::new();
+    let mut out = vec![];
+    for byte in frames.iter() {
+        src.extend_from_slice(&[*byte]);
+        if let Some(message) = codec.decode(&mut src).unwrap() {
+            out.push(message)
+        }
+    }
+    assert_eq!(out, messages());
+    assert!(src.is_empty());
+}
+
+#[test]
+fn test_limits() {
+    let mut codec = WXFCodec::new();
+    codec.set_max_frame_length(64);
+    let mut dst = BytesMut::new();
+    assert!(codec.encode(wl!(f[x]), &mut dst).is_ok());
+    let length = dst.len();
+    let e = codec.encode(&WolframValue::list(vec![WolframValue::Integer8(1); 100]), &mut dst).unwrap_err();
+    assert!(matches!(e, WXFError::FrameTooLong { length: 210, max: 64 }), "{}", e);
+    assert_eq!(dst.len(), length);
+    let mut src = BytesMut::from(&[0, 0, 1, 0][..]);
+    assert_eq!(codec.decode(&mut src), Err(DecodeError::LimitExceeded { limit: DecodeLimit::FrameLength, offset: 0 }));
+    let mut src = BytesMut::from(&b"\x00\x00\x00\x027:"[..]);
+    assert_eq!(codec.decode(&mut src), Err(DecodeError::InvalidHeader));
+}
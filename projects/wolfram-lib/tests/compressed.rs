@@ -30,3 +30,36 @@ fn test_basic() {
         ]
     );
 }
+
+#[test]
+fn test_options() {
+    use wolfram_wxf::{CompressOptions, DecodeLimits, EncodeOptions, WXFError, WolframValue};
+    let rows: Vec<Vec<String>> = (0..200).map(|i| (0..50).map(|j| format!("cell {} {}", i, j)).collect()).collect();
+    let value = rows.to_wolfram();
+    let limits = DecodeLimits::unlimited();
+    let sizes: Vec<usize> = [0, 1, 6, 9]
+        .iter()
+        .map(|&level| {
+            let solid = rows.to_wolfram_solid_with(&CompressOptions { level, ..CompressOptions::default() });
+            assert_eq!(WolframValue::from_bytes_with(&solid, &limits).unwrap(), value);
+            solid.len()
+        })
+        .collect();
+    assert!(sizes[0] > sizes[1] && sizes[1] > sizes[3]);
+    assert_eq!(rows.to_wolfram_solid_with(&CompressOptions::default()), rows.to_wolfram_solid());
+    // several batches of chunks, the last batch partly filled
+    for threads in [0, 2, 3].iter() {
+        let options = CompressOptions { threads: *threads, chunk_size: 4096, ..CompressOptions::fast() };
+        let solid = value.to_compressed_with(&options);
+        assert_eq!(WolframValue::from_bytes_with(&solid, &limits).unwrap(), value);
+    }
+    let parallel = CompressOptions { chunk_size: 1, ..CompressOptions::parallel() };
+    assert_eq!(WolframValue::from_bytes(&0u8.to_wolfram_solid_with(&parallel)).unwrap(), 0u8.to_wolfram());
+    let canonical = CompressOptions { encode: EncodeOptions::canonical(), ..CompressOptions::default() };
+    assert_eq!(WolframValue::from_bytes(&value.to_compressed_with(&canonical)).unwrap(), value);
+    let invalid = CompressOptions { level: 10, ..CompressOptions::default() };
+    assert!(matches!(value.try_to_compressed_with(&invalid), Err(WXFError::Compression(_))));
+    let mut full = [0u8; 8];
+    let e = value.write_compressed_to(&mut &mut full[..], &CompressOptions::default()).unwrap_err();
+    assert!(matches!(e, WXFError::Io(_)));
+}
@@ -1,5 +1,5 @@
 use std::collections::{BTreeMap, HashMap};
-use wolfram_wxf::{ToWolfram, WolframValue};
+use wolfram_wxf::{objects::SparseArray, FromWolfram, PartError, ToWolfram, WolframValue};
 
 #[test]
 fn test_list() {
@@ -32,3 +32,45 @@ fn test_bytes() {
     assert_eq!(v.to_bytes(), [56, 58, 66, 3, 1, 2, 3]);
     assert_eq!(v.to_string(), "ByteArray[{1,2,3}]");
 }
+
+#[test]
+fn test_ndarray() {
+    let matrix = ndarray::arr2(&[[1, 2, 3], [4, 5, 6]]);
+    //Normal@BinarySerialize[{{1, 2, 3}, {4, 5, 6}}]
+    assert_eq!(matrix.to_wolfram_bytes(), [56, 58, 193, 0, 2, 2, 3, 1, 2, 3, 4, 5, 6]);
+    assert_eq!(matrix.t().to_wolfram_string(), "{{1,4},{2,5},{3,6}}");
+    assert_eq!(ndarray::arr1(&["a", "b"]).to_wolfram(), WolframValue::list(vec!["a".to_wolfram(), "b".to_wolfram()]));
+    assert_eq!(ndarray::Array2::<f64>::zeros((2, 0)).to_wolfram_string(), "{{},{}}");
+}
+
+#[test]
+fn test_sparse_array() {
+    //SparseArray[{{1, 2} -> 3, {2, 1} -> 4}, {2, 3}]
+    let entries = vec![(vec![2, 1], 4.to_wolfram()), (vec![1, 2], 3.to_wolfram())];
+    let matrix = SparseArray::from_positions(vec![2, 3], 0.to_wolfram(), entries).unwrap();
+    assert_eq!(matrix.to_wolfram_string(), "SparseArray[Automatic,{2,3},0,{1,{{0,1,2},{{2},{1}}},{3,4}}]");
+    assert_eq!(matrix.get(&[2, 1]), Some(&4.to_wolfram()));
+    assert_eq!(matrix.get(&[2, 2]), Some(&0.to_wolfram()));
+    assert_eq!(matrix.get(&[3, 1]), None);
+    assert_eq!(SparseArray::from_wolfram(&matrix.to_wolfram()), Ok(matrix));
+    //SparseArray[{3 -> "a"}, 4, None]
+    let vector = SparseArray::from_positions(vec![4], WolframValue::symbol("None"), vec![(vec![3], "a".to_wolfram())]).unwrap();
+    assert_eq!(vector.to_wolfram_string(), "SparseArray[Automatic,{4},None,{1,{{0,1},{{3}}},{\"a\"}}]");
+    assert!(SparseArray::from_wolfram(&WolframValue::list(vec![])).is_err());
+}
+
+#[test]
+fn test_sparse_array_positions() {
+    let at = |position: Vec<usize>| SparseArray::from_positions(vec![2, 3], 0.to_wolfram(), vec![(position, 1.to_wolfram())]);
+    assert_eq!(at(vec![2, 4]), Err(PartError::OutOfRange { depth: 2, index: 4, length: 3 }));
+    assert_eq!(at(vec![0, 1]), Err(PartError::OutOfRange { depth: 1, index: 0, length: 2 }));
+    assert_eq!(at(vec![1]), Err(PartError::Rank { indices: 1, rank: 2 }));
+    assert_eq!(at(vec![1, 1, 1]), Err(PartError::Rank { indices: 3, rank: 2 }));
+    //column indices outside the dimensions or of the wrong length
+    let matrix = SparseArray::from_positions(vec![2, 3], 0.to_wolfram(), vec![(vec![1, 2], 3.to_wolfram())]).unwrap();
+    for columns in [vec![4], vec![0], vec![1, 1]].iter() {
+        let invalid = SparseArray { column_indices: vec![columns.clone()], ..matrix.clone() };
+        assert!(SparseArray::from_wolfram(&invalid.to_wolfram()).is_err(), "{:?}", columns);
+    }
+    assert_eq!(SparseArray::from_wolfram(&matrix.to_wolfram()), Ok(matrix));
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use wolfram_wxf::{wl, DecodeError, DecodeLimit, DecodeLimits, ToWolfram, WolframValue};
+
+#[test]
+fn test_round_trip() {
+    let mut dict = HashMap::new();
+    dict.insert(0, "zero");
+    dict.insert(1, "first");
+    let values = vec![
+        wl!(Sin[1][2][3]),
+        wl!({-1, 128, 32768, 2147483648, 9223372036854775808, 0.5, "中文", None}),
+        wl!(<|"a" -> f[x, y], 1 :> {}|>),
+        dict.to_wolfram(),
+        WolframValue::Bytes(vec![1, 2, 3]),
+    ];
+    for v in values {
+        assert_eq!(WolframValue::from_bytes(&v.to_bytes()), Ok(v.clone()));
+        assert_eq!(WolframValue::from_bytes(&v.to_compressed()), Ok(v));
+    }
+}
+
+#[test]
+fn test_arrays() {
+    //Normal@BinarySerialize[Developer`ToPackedArray[{{1, 2}, {3, 4}}]]
+    let packed = [56, 58, 193, 0, 2, 2, 2, 1, 2, 3, 4];
+    assert_eq!(WolframValue::from_bytes(&packed), Ok(wl!({{1, 2}, {3, 4}})));
+    //Normal@BinarySerialize[NumericArray[{1, 2}, "UnsignedInteger8"]]
+    let numeric = [56, 58, 194, 16, 1, 2, 1, 2];
+    assert_eq!(WolframValue::from_bytes(&numeric).unwrap().to_string(), r#"NumericArray[{1,2},"UnsignedInteger8"]"#);
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(WolframValue::from_bytes(b"7:C\x00"), Err(DecodeError::InvalidHeader));
+    assert_eq!(WolframValue::from_bytes(b"8:S\x05ab"), Err(DecodeError::UnexpectedEnd { offset: 4 }));
+    assert_eq!(WolframValue::from_bytes(b"8:Z"), Err(DecodeError::InvalidToken { token: b'Z', offset: 0 }));
+    assert_eq!(WolframValue::from_bytes(b"8:C\x00\x00"), Err(DecodeError::TrailingBytes { offset: 2 }));
+    assert!(matches!(WolframValue::from_bytes(b"8C:\x00\x01"), Err(DecodeError::Decompress(_))));
+}
+
+#[test]
+fn test_limits() {
+    let limited = |limits: DecodeLimits, v: &WolframValue| match WolframValue::from_bytes_with(&v.to_compressed(), &limits) {
+        Err(DecodeError::LimitExceeded { limit, .. }) => Some(limit),
+        _ => None,
+    };
+    let mut deep = wl!(x);
+    for _ in 0..1100 {
+        deep = WolframValue::function("f", vec![deep]);
+    }
+    assert_eq!(limited(DecodeLimits::default(), &deep), Some(DecodeLimit::Depth));
+    assert_eq!(limited(DecodeLimits { max_depth: 3, ..DecodeLimits::unlimited() }, &wl!(f[g[x]])), None);
+    assert_eq!(limited(DecodeLimits { max_depth: 2, ..DecodeLimits::unlimited() }, &wl!(f[g[x]])), Some(DecodeLimit::Depth));
+    let list = vec![0; 100].to_wolfram();
+    assert_eq!(limited(DecodeLimits { max_elements: 101, ..DecodeLimits::unlimited() }, &list), Some(DecodeLimit::Elements));
+    assert_eq!(limited(DecodeLimits { max_elements: 102, ..DecodeLimits::unlimited() }, &list), None);
+    assert_eq!(limited(DecodeLimits { max_length: 2, ..DecodeLimits::unlimited() }, &wl!("abc")), Some(DecodeLimit::Length));
+    let big = WolframValue::integer(u128::MAX);
+    assert_eq!(limited(DecodeLimits { max_big_integer_digits: 20, ..DecodeLimits::unlimited() }, &big), Some(DecodeLimit::BigIntegerDigits));
+    //zip bomb: zeros inflate to a thousand times their compressed size
+    let bomb = WolframValue::Bytes(vec![0; 1 << 18]);
+    assert_eq!(limited(DecodeLimits { max_decompressed_size: 1 << 12, ..DecodeLimits::unlimited() }, &bomb), Some(DecodeLimit::DecompressedSize));
+    //2^40 empty rows in 13 bytes
+    let rows = b"8:\xc1\x00\x02\x80\x80\x80\x80\x80\x20\x00";
+    assert_eq!(WolframValue::from_bytes(rows), Err(DecodeError::LimitExceeded { limit: DecodeLimit::Elements, offset: 1 }));
+    //a varint claiming an exabyte long string
+    let huge = b"8:S\xff\xff\xff\xff\xff\xff\xff\xff\x0f";
+    assert!(matches!(WolframValue::from_bytes(huge), Err(DecodeError::UnexpectedEnd { .. })));
+}
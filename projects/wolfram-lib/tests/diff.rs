@@ -0,0 +1,63 @@
+use wolfram_wxf::{assert_wolfram_eq, diff::DiffOptions, wl, PartSpec, ToWolfram, WolframValue};
+
+#[test]
+fn test_diff() {
+    let left = wl!({1, f[2, <|"key" -> 3|>], x});
+    let right = wl!({1, f[2, <|"key" -> 4, "new" -> 5|>]});
+    let diff = left.diff(&right);
+    assert_eq!(diff.differences.len(), 3);
+    assert_eq!(diff.differences[0].path, vec![PartSpec::Index(2), PartSpec::Index(2), PartSpec::Key("key".to_wolfram())]);
+    assert_eq!(diff.to_string(), "{2, 2, \"key\"}: 3 => 4\n{2, 2, \"new\"}: <absent> => 5\n{3}: x => <absent>\n");
+    assert!(left.diff(&left).is_empty());
+    //different heads and lengths are reported as a whole
+    assert_eq!(wl!(f[1]).diff(&wl!(g[1, 2])).to_string(), "{}: f[1] => g[1,2]\n");
+    assert_eq!(wl!(f[1]).diff(&wl!(g[2])).to_string(), "{0}: f => g\n{1}: 1 => 2\n");
+}
+
+#[test]
+fn test_association() {
+    assert_eq!(wl!(<|a -> 1|>).diff(&wl!(<|a :> 1|>)).to_string(), "{a}: Rule[a,1] => RuleDelayed[a,1]\n");
+    let left = wl!(<|1 -> x, 2 -> y|>);
+    let right = WolframValue::Association(vec![(WolframValue::Integer64(1), (WolframValue::Rule, wl!(x)))].into_iter().collect());
+    assert_eq!(left.diff(&right).differences.len(), 3);
+    assert_eq!(left.diff_with(&right, &DiffOptions::loose()).to_string(), "{2}: y => <absent>\n");
+}
+
+#[test]
+fn test_assert() {
+    assert_wolfram_eq!(loose: vec![1, 2], wl!({1, 2}));
+    assert_wolfram_eq!(wxf: wl!(f[x]).to_bytes(), wl!(f[x]).to_compressed());
+}
+
+#[test]
+fn test_strict() {
+    assert_eq!(vec![1i32].to_wolfram().diff(&wl!({1})).to_string(), "{1}: 1 (Integer32) => 1 (Integer8)\n");
+    let options = DiffOptions::loose();
+    assert!(WolframValue::symbol("System`None").diff_with(&wl!(None), &options).is_empty());
+    assert!(!WolframValue::symbol("System`None").diff(&wl!(None)).is_empty());
+}
+
+#[test]
+fn test_arrays() {
+    let packed = WolframValue::PackedArray;
+    let diff = packed(vec![wl!(1), wl!(2)]).diff(&packed(vec![wl!(1), wl!(3), wl!(4)]));
+    assert_eq!(diff.to_string(), "{2}: 2 => 3\n{3}: <absent> => 4\n");
+    let numeric = WolframValue::NumericArray(vec![wl!(1), wl!(2)]);
+    let other = WolframValue::NumericArray(vec![wl!(1), WolframValue::Integer64(2)]);
+    assert_eq!(numeric.diff(&other).to_string(), "{2}: 2 (Integer8) => 2 (Integer64)\n");
+    assert!(numeric.diff_with(&other, &DiffOptions::loose()).is_empty());
+    //a packed and an unpacked list differ only in the variant
+    assert_eq!(packed(vec![wl!(1)]).diff(&wl!({1})).to_string(), "{}: {1} (PackedArray) => {1} (Function)\n");
+}
+
+#[test]
+#[should_panic(expected = "{}: 1 (Integer64) => 1 (Integer8)")]
+fn test_assert_wxf_bytes() {
+    assert_wolfram_eq!(wxf: WolframValue::Integer64(1).to_bytes(), WolframValue::Integer8(1).to_bytes());
+}
+
+#[test]
+#[should_panic(expected = "{2}: 2 => 3")]
+fn test_assert_failure() {
+    assert_wolfram_eq!(wl!({1, 2}), wl!({1, 3}));
+}
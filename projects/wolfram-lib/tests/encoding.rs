@@ -1,4 +1,5 @@
 use integer_encoding::VarInt;
+use wolfram_wxf::WolframValue;
 
 /// ```wl
 /// bits = IntegerDigits[9999, 2]
@@ -21,11 +22,64 @@ fn length_encoding(len: usize) -> Vec<u8> {
             break;
         }
     }
-    return buf.to_vec();
+    buf
 }
 
 #[test]
 fn test_var_encoding() {
-    let check = vec![0usize, 1, 64, 127, 128, 255, 256, 1024, 2048].iter().all(|u| u.encode_var_vec() == length_encoding(*u));
-    assert!(check)
+    let check = [0usize, 1, 64, 127, 128, 255, 256, 1024, 2048].iter().all(|u| u.encode_var_vec() == length_encoding(*u));
+    assert!(check);
+    for n in [0usize, 127, 128, 16383, 16384, 70000] {
+        let bytes = WolframValue::Bytes(vec![0; n]).to_bytes();
+        let prefix = length_encoding(n);
+        assert_eq!(bytes[3..3 + prefix.len()], prefix[..]);
+        assert_eq!(WolframValue::from_bytes(&bytes).unwrap(), WolframValue::Bytes(vec![0; n]));
+    }
+}
+
+/// Accepts at most `limit` bytes per call, like a socket
+struct Chunked {
+    out: Vec<u8>,
+    limit: usize,
+}
+
+impl std::io::Write for Chunked {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(self.limit);
+        self.out.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_write_to() {
+    use wolfram_wxf::{wl, EncodeOptions, WolframValue};
+    let long = "x".repeat(300);
+    let values = vec![
+        wl!(f[x, {1, 2, 3}, <|"a" -> 1, b :> 2.5|>]),
+        wl!(#long),
+        WolframValue::integer(u128::MAX),
+        WolframValue::PackedArray(vec![wl!({1, 2}), wl!({3, 40000})]),
+    ];
+    for v in values {
+        for options in [EncodeOptions::default(), EncodeOptions::canonical()].iter() {
+            let expected = v.to_bytes_with(options);
+            let mut sink = Chunked { out: vec![], limit: 1 };
+            v.write_to(&mut sink, options).unwrap();
+            assert_eq!(sink.out, expected);
+            assert_eq!(v.encoded_len_with(options).unwrap(), expected.len());
+        }
+    }
+    assert!(WolframValue::symbol("a b").encoded_len_with(&EncodeOptions::validated()).is_err());
+}
+
+#[test]
+fn test_write_error() {
+    use wolfram_wxf::{wl, EncodeOptions, WXFError};
+    let mut full = [0u8; 4];
+    let e = wl!(f[x]).write_to(&mut &mut full[..], &EncodeOptions::default()).unwrap_err();
+    assert!(matches!(e, WXFError::Io(_)));
 }
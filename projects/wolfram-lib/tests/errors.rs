@@ -0,0 +1,113 @@
+use std::error::Error;
+use wolfram_wxf::{DecodeError, EncodeOptions, ToWolfram, WXFError, WolframValue};
+
+fn packed(items: Vec<WolframValue>) -> WolframValue {
+    WolframValue::PackedArray(items)
+}
+
+#[test]
+fn test_packed_array() {
+    let matrix = packed(vec![vec![1, 2].to_wolfram(), vec![3, 4].to_wolfram()]);
+    //Normal@BinarySerialize[Developer`ToPackedArray[{{1, 2}, {3, 4}}]]
+    assert_eq!(matrix.try_to_bytes().unwrap(), [56, 58, 193, 0, 2, 2, 2, 1, 2, 3, 4]);
+    assert_eq!(matrix.to_string(), "{{1,2},{3,4}}");
+    //Normal@BinarySerialize[Developer`ToPackedArray[{1, 1000}]]
+    assert_eq!(packed(vec![1.to_wolfram(), 1000.to_wolfram()]).try_to_bytes().unwrap(), [56, 58, 193, 1, 1, 2, 1, 0, 232, 3]);
+    //Normal@BinarySerialize[Developer`ToPackedArray[{1., 2}]]
+    let reals = packed(vec![1.0.to_wolfram(), 2.to_wolfram()]).try_to_bytes().unwrap();
+    assert_eq!(reals[..5], [56, 58, 193, 35, 1]);
+    assert_eq!(reals[6..], [1.0f64.to_le_bytes(), 2.0f64.to_le_bytes()].concat()[..]);
+}
+
+#[test]
+fn test_numeric_array() {
+    let array = WolframValue::NumericArray(vec![1.to_wolfram(), (-1).to_wolfram()]);
+    //Normal@BinarySerialize[NumericArray[{1, -1}, "Integer8"]]
+    assert_eq!(array.try_to_bytes().unwrap(), [56, 58, 194, 0, 1, 2, 1, 255]);
+    assert_eq!(array.to_string(), "NumericArray[{1,-1}]");
+    //Normal@BinarySerialize[NumericArray[{200, 1}, "UnsignedInteger8"]]
+    let typed = WolframValue::new_numeric_array(vec![200.to_wolfram(), 1.to_wolfram()], "UnsignedInteger8");
+    assert_eq!(typed.try_to_bytes().unwrap(), [56, 58, 194, 16, 1, 2, 200, 1]);
+    let real = WolframValue::new_numeric_array(vec![0.5.to_wolfram()], "Real32");
+    assert_eq!(real.try_to_bytes().unwrap(), [&[56, 58, 194, 34, 1, 1][..], &0.5f32.to_le_bytes()].concat());
+    //elements outside the type or ragged lists fail
+    let invalid = WolframValue::new_numeric_array(vec![256.to_wolfram()], "UnsignedInteger8");
+    assert!(matches!(invalid.try_to_bytes(), Err(WXFError::UnsupportedValue(_))));
+    let ragged = WolframValue::new_numeric_array(vec![vec![1].to_wolfram(), vec![2, 3].to_wolfram()], "Integer8");
+    assert!(matches!(ragged.try_to_bytes(), Err(WXFError::InvalidArrayShape(_))));
+    //types without a WXF array are written as the expression
+    let complex = WolframValue::new_numeric_array(vec![1.to_wolfram()], "ComplexReal64");
+    assert_eq!(complex.try_to_bytes().unwrap()[2], b'f');
+    //NumericArray[{}, "Real32"] has a type and no elements
+    let empty = WolframValue::new_numeric_array(vec![], "Real32");
+    assert_eq!(empty.try_to_bytes().unwrap(), [56, 58, 194, 34, 1, 0]);
+}
+
+#[test]
+fn test_empty_packed_array() {
+    //Mathematica does not pack lists with an empty dimension, they are written unpacked
+    assert_eq!(packed(vec![]).try_to_bytes().unwrap(), WolframValue::list(vec![]).to_bytes());
+    let rows = packed(vec![WolframValue::list(vec![]), WolframValue::list(vec![])]);
+    assert_eq!(rows.try_to_bytes().unwrap(), b"8:f\x02s\x04Listf\x00s\x04Listf\x00s\x04List");
+    let ragged = packed(vec![WolframValue::list(vec![]), vec![1].to_wolfram()]);
+    assert!(matches!(ragged.try_to_bytes(), Err(WXFError::InvalidArrayShape(_))));
+}
+
+#[test]
+fn test_big_decimal() {
+    //Normal@BinarySerialize[1.5`20]
+    let value = WolframValue::BigDecimal("1.5`20.".to_string());
+    assert_eq!(value.try_to_bytes().unwrap(), [56, 58, 82, 7, 49, 46, 53, 96, 50, 48, 46]);
+}
+
+#[test]
+fn test_invalid_array_shape() {
+    let ragged = packed(vec![vec![1, 2].to_wolfram(), vec![3].to_wolfram()]);
+    assert!(matches!(ragged.try_to_bytes(), Err(WXFError::InvalidArrayShape(_))));
+    let mixed = packed(vec![1.to_wolfram(), vec![2].to_wolfram()]);
+    assert!(matches!(mixed.try_to_compressed(), Err(WXFError::InvalidArrayShape(_))));
+}
+
+#[test]
+fn test_unsupported_value() {
+    let strings = packed(vec!["a".to_wolfram()]);
+    let e = strings.try_to_bytes().unwrap_err();
+    assert!(matches!(e, WXFError::UnsupportedValue(_)));
+    assert_eq!(e.to_string(), "unsupported value: \"a\" is not a machine number");
+    //Skip and bare rules have no token
+    for value in [WolframValue::Skip, WolframValue::Rule, WolframValue::RuleDelayed].iter() {
+        assert!(matches!(value.try_to_bytes(), Err(WXFError::UnsupportedValue(_))), "{:?}", value);
+    }
+    let nested = WolframValue::list(vec![WolframValue::Skip]);
+    assert!(matches!(nested.try_to_bytes(), Err(WXFError::UnsupportedValue(_))));
+}
+
+#[test]
+fn test_invalid_symbol() {
+    for name in ["", "1x", "a b", "Global``x", "x`"].iter() {
+        let e = WolframValue::symbol(*name).try_to_bytes().unwrap_err();
+        assert!(matches!(e, WXFError::InvalidSymbol(_)), "{}", name);
+        assert!(e.source().is_none());
+    }
+    let nested = WolframValue::function("f", vec![WolframValue::symbol("my_var")]);
+    assert!(matches!(nested.try_to_wolfram_bytes(), Err(WXFError::InvalidSymbol(s)) if s == "my_var"));
+    assert!(WolframValue::symbol("Global`$x1").try_to_bytes().is_ok());
+}
+
+#[test]
+fn test_infallible_permissive() {
+    assert_eq!(WolframValue::symbol("my_var").to_bytes(), b"8:s\x0dGlobal`my_var");
+    let options = EncodeOptions { validate_symbols: true, ..EncodeOptions::default() };
+    assert!(matches!(WolframValue::symbol("my_var").try_to_bytes_with(&options), Err(WXFError::InvalidSymbol(_))));
+}
+
+#[test]
+fn test_io_source() {
+    let e = WXFError::from(std::io::Error::new(std::io::ErrorKind::WriteZero, "full"));
+    assert!(e.source().is_some());
+    assert_eq!(e.to_string(), "io error: full");
+    let e = DecodeError::from(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "closed"));
+    assert!(e.source().is_some());
+    assert_eq!(e, e.clone());
+    assert_eq!(e.to_string(), "io error: closed");
+}
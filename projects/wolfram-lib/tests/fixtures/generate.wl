@@ -0,0 +1,22 @@
+(* Writes the fixtures of tests/canonical.rs, run with: wolframscript -file generate.wl *)
+SetDirectory[DirectoryName[$InputFileName]];
+write[name_String, expr_] := Export[name <> ".wxf", Normal[BinarySerialize[expr]], "Byte"];
+
+write["integer-0", 0];
+write["integer-neg-128", -128];
+write["integer-128", 128];
+write["integer-32768", 32768];
+write["integer-2147483648", 2147483648];
+write["integer-9223372036854775808", 9223372036854775808];
+write["real-0.3", 0.3];
+write["string-chinese", "中文"];
+write["symbol-true", True];
+write["symbol-global", Global`x];
+write["symbol-system-none", System`None];
+write["function-curry", Sin[1][2][3]];
+write["rational", 1/2];
+write["list-integer", {0}];
+write["bytes", ByteArray[{1, 2, 3}]];
+write["association", <|1 -> 2|>];
+(* BinarySerialize keeps insertion order, EncodeOptions::canonical writes KeySort order *)
+write["association-keysort", KeySort[<|"b" -> 1, "a" -> 2, 1 -> 3|>]];
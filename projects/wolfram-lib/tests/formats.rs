@@ -0,0 +1,284 @@
+#![cfg(any(feature = "msgpack", feature = "cbor", feature = "csv", feature = "arrow", feature = "polars", feature = "sprs", feature = "image"))]
+
+use wolfram_wxf::ToWolfram;
+#[cfg(any(feature = "msgpack", feature = "cbor", feature = "csv", feature = "arrow", feature = "polars"))]
+use wolfram_wxf::{wl, WolframValue};
+
+#[test]
+#[cfg(feature = "msgpack")]
+fn test_msgpack() {
+    use wolfram_wxf::{
+        utils::{parse_msgpack, to_msgpack},
+        FromWolfram,
+    };
+    // {"a": [1, -2, 1.5], "b": nil, "c": true, "d": bin8 [1, 2]}
+    let input = [
+        0x84, 0xA1, 0x61, 0x93, 0x01, 0xFE, 0xCB, 0x3F, 0xF8, 0, 0, 0, 0, 0, 0, 0xA1, 0x62, 0xC0, 0xA1, 0x63, 0xC3, 0xA1, 0x64, 0xC4, 0x02, 0x01, 0x02,
+    ];
+    let value = parse_msgpack(&input).unwrap();
+    let expected = wl!(<|"a" -> {1, -2, 1.5}, "b" -> None, "c" -> True, "d" -> #(WolframValue::Bytes(vec![1, 2]))|>);
+    assert_eq!(value.to_string(), expected.to_string());
+    assert_eq!(to_msgpack(&value).unwrap(), input);
+    // uint64 18446744073709551615
+    let max = parse_msgpack(&[0xCF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]).unwrap();
+    assert_eq!(max, u64::MAX.to_wolfram());
+    assert_eq!(to_msgpack(&max).unwrap(), [0xCF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+    // fixext1 type 5
+    let ext = parse_msgpack(&[0xD4, 0x05, 0x2A]).unwrap();
+    assert_eq!(ext.to_string(), "MessagePackExtension[5,ByteArray[{42}]]");
+    assert_eq!(to_msgpack(&ext).unwrap(), [0xD4, 0x05, 0x2A]);
+    assert!(to_msgpack(&u128::MAX.to_wolfram()).is_err());
+    assert!(to_msgpack(&wl!(f[x])).is_err());
+    assert!(parse_msgpack(&[0x01, 0x02]).is_err());
+    // symbols with their context as the decoder reads them
+    let list = WolframValue::Function(Box::new(WolframValue::symbol("System`List")), vec![WolframValue::symbol("System`None")]);
+    assert_eq!(to_msgpack(&list).unwrap(), [0x91, 0xC0]);
+    // not an integer at all
+    for atom in [WolframValue::Rule, WolframValue::BigDecimal("1.5`20.".to_string())] {
+        assert_eq!(rmpv::Value::from_wolfram(&atom).unwrap_err().expected, "value with a MessagePack form");
+    }
+}
+
+#[test]
+#[cfg(feature = "cbor")]
+fn test_cbor() {
+    use wolfram_wxf::utils::{parse_cbor, to_cbor};
+    // {"a": [1, -2, 1.5], "b": null, "c": true, "d": h'0102'}
+    let input = [
+        0xA4, 0x61, 0x61, 0x83, 0x01, 0x21, 0xF9, 0x3E, 0x00, 0x61, 0x62, 0xF6, 0x61, 0x63, 0xF5, 0x61, 0x64, 0x42, 0x01, 0x02,
+    ];
+    let value = parse_cbor(&input).unwrap();
+    let expected = wl!(<|"a" -> {1, -2, 1.5}, "b" -> None, "c" -> True, "d" -> #(WolframValue::Bytes(vec![1, 2]))|>);
+    assert_eq!(value.to_string(), expected.to_string());
+    assert_eq!(to_cbor(&value).unwrap(), input);
+    // 2(h'010000000000000000') is 2^64
+    assert!(matches!(parse_cbor(&[0x01, 0x02]), Err(ciborium::de::Error::Semantic(Some(1), _))));
+    let big = [0xC2, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0];
+    assert_eq!(parse_cbor(&big).unwrap(), (u64::MAX as u128 + 1).to_wolfram());
+    assert_eq!(to_cbor(&(u64::MAX as u128 + 1).to_wolfram()).unwrap(), big);
+    // 3(h'01' followed by 16 zeros) is -1 - 2^128
+    let mut negative = vec![0xC3, 0x51, 0x01];
+    negative.extend([0; 16]);
+    let n = parse_cbor(&negative).unwrap();
+    assert_eq!(n.to_string(), "-340282366920938463463374607431768211457");
+    assert_eq!(to_cbor(&n).unwrap(), negative);
+    // 1(1363896240)
+    let tagged = parse_cbor(&[0xC1, 0x1A, 0x51, 0x4B, 0x67, 0xB0]).unwrap();
+    assert_eq!(tagged.to_string(), "CBORTag[1,1363896240]");
+    assert_eq!(to_cbor(&tagged).unwrap(), [0xC1, 0x1A, 0x51, 0x4B, 0x67, 0xB0]);
+    assert!(to_cbor(&wl!(f[x])).is_err());
+    // symbols with their context as the decoder reads them
+    let list = WolframValue::Function(Box::new(WolframValue::symbol("System`List")), vec![WolframValue::symbol("System`None")]);
+    assert_eq!(to_cbor(&list).unwrap(), [0x81, 0xF6]);
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn test_csv() {
+    use wolfram_wxf::{assert_wolfram_eq, utils::{parse_csv, CsvOptions, CsvOutput}};
+    let input = "name,age,score,member,joined\nada,36,1.5,true,1851-01-01\nbob,,2,FALSE,\n";
+    let dataset = parse_csv(input, &CsvOptions::default()).unwrap();
+    // columns keep the order of the file
+    assert_eq!(
+        dataset.to_string(),
+        "Dataset[{\
+         Association[Rule[\"name\",\"ada\"],Rule[\"age\",36],Rule[\"score\",1.5`],Rule[\"member\",True],Rule[\"joined\",DateObject[\"1851-01-01\"]]],\
+         Association[Rule[\"name\",\"bob\"],Rule[\"age\",Missing[\"NotAvailable\"]],Rule[\"score\",2`],Rule[\"member\",False],Rule[\"joined\",Missing[\"NotAvailable\"]]]}]"
+    );
+    assert_eq!(dataset.part(&[1, 1]).unwrap().get("age").unwrap(), &36i64.to_wolfram());
+    let rows = parse_csv(input, &CsvOptions { output: CsvOutput::Rows, ..CsvOptions::default() }).unwrap();
+    assert_eq!(rows.to_string().matches('{').count(), 4);
+    let columns = parse_csv("x\ty\n1\ta\n2\tb\n", &CsvOptions { output: CsvOutput::Columns, ..CsvOptions::tsv() }).unwrap();
+    let expected = wl!(<|"x" -> #(WolframValue::PackedArray(vec![1i64.to_wolfram(), 2i64.to_wolfram()])), "y" -> {"a", "b"}|>);
+    assert_eq!(columns, expected);
+    // a first row of numbers is data
+    let headless = parse_csv("1,2\n3,4\n", &CsvOptions { output: CsvOutput::Rows, ..CsvOptions::default() }).unwrap();
+    assert_eq!(headless.to_string(), "{{1,2},{3,4}}");
+    let forced = parse_csv("a,b\nc,d\n", &CsvOptions { header: Some(false), output: CsvOutput::Rows, ..CsvOptions::default() }).unwrap();
+    assert_eq!(forced.to_string(), "{{\"a\",\"b\"},{\"c\",\"d\"}}");
+    // repeated names keep every column
+    let repeated = parse_csv("a,a,b\n1,2,3\n", &CsvOptions { header: Some(true), output: CsvOutput::Columns, ..CsvOptions::default() }).unwrap();
+    assert_eq!(repeated.to_string(), "<|\"a\"->{1},\"a.1\"->{2},\"b\"->{3}|>");
+    let unsorted = parse_csv("b,a\n1,2\n", &CsvOptions { output: CsvOutput::Columns, header: Some(true), ..CsvOptions::default() }).unwrap();
+    assert_eq!(unsorted.to_string(), "Association[Rule[\"b\",{1}],Rule[\"a\",{2}]]");
+    // integers beyond i64 stay exact
+    let big = parse_csv("n\n99999999999999999999\n1\n", &CsvOptions { output: CsvOutput::Columns, ..CsvOptions::default() }).unwrap();
+    assert_wolfram_eq!(loose: big, wl!(<|"n" -> {99999999999999999999, 1}|>));
+    assert!(big.try_to_bytes().is_ok());
+    // only a time may follow a date
+    let dates = parse_csv("2020-01-31T12:00:00Z\n2020-01-31 anything\n2020-02-01 08:30+01:00\n", &CsvOptions { output: CsvOutput::Rows, ..CsvOptions::default() }).unwrap();
+    assert_eq!(dates.to_string(), "{{\"2020-01-31T12:00:00Z\"},{\"2020-01-31 anything\"},{\"2020-02-01 08:30+01:00\"}}");
+    let dates = parse_csv("2020-01-31T12:00:00.5\n2020-02-01\n", &CsvOptions { output: CsvOutput::Rows, ..CsvOptions::default() }).unwrap();
+    assert_eq!(dates.to_string(), "{{DateObject[\"2020-01-31T12:00:00.5\"]},{DateObject[\"2020-02-01\"]}}");
+}
+
+#[test]
+#[cfg(feature = "arrow")]
+fn test_arrow() {
+    use arrow::{
+        array::{ArrayRef, Decimal128Array, DictionaryArray, Float64Array, Int32Array, ListArray, RecordBatch, StringArray, StructArray, TimestampMillisecondArray},
+        datatypes::{DataType, Field, Int32Type, Int8Type},
+    };
+    use std::sync::Arc;
+    use wolfram_wxf::utils::{parse_arrow, record_batch_columns, to_record_batch, write_arrow};
+
+    let tags: DictionaryArray<Int8Type> = vec!["x", "y", "x"].into_iter().collect();
+    let point = StructArray::from(vec![(
+        Arc::new(Field::new("z", DataType::Int32, false)),
+        Arc::new(Int32Array::from(vec![7, 8, 9])) as ArrayRef,
+    )]);
+    let batch = RecordBatch::try_from_iter(vec![
+        ("id", Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])) as ArrayRef),
+        ("score", Arc::new(Float64Array::from(vec![0.5, 1.5, 2.5])) as ArrayRef),
+        ("name", Arc::new(StringArray::from(vec!["a", "b", "c"])) as ArrayRef),
+        ("at", Arc::new(TimestampMillisecondArray::from(vec![0, 1500, 2000]).with_timezone("UTC")) as ArrayRef),
+        ("tag", Arc::new(tags) as ArrayRef),
+        ("items", Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![Some(vec![Some(1)]), Some(vec![]), None])) as ArrayRef),
+        ("point", Arc::new(point) as ArrayRef),
+    ])
+    .unwrap();
+    let dataset = batch.to_wolfram();
+    // columns keep the schema order
+    let first = "Association[Rule[\"id\",1],Rule[\"score\",0.5`],Rule[\"name\",\"a\"],Rule[\"at\",FromUnixTime[0,Rule[TimeZone,\"UTC\"]]],\
+                 Rule[\"tag\",\"x\"],Rule[\"items\",{1}],Rule[\"point\",<|\"z\"->7|>]]";
+    let second = "Association[Rule[\"id\",Missing[\"NotAvailable\"]],Rule[\"score\",1.5`],Rule[\"name\",\"b\"],\
+                  Rule[\"at\",FromUnixTime[1.5`,Rule[TimeZone,\"UTC\"]]],Rule[\"tag\",\"y\"],Rule[\"items\",{}],Rule[\"point\",<|\"z\"->8|>]]";
+    assert!(dataset.to_string().starts_with(&format!("Dataset[{{{},{},", first, second)));
+    assert!(dataset.to_string().contains("Rule[\"items\",Missing[\"NotAvailable\"]]"));
+    let names = |batch: &RecordBatch| batch.schema().fields().iter().map(|f| f.name().clone()).collect::<Vec<_>>();
+    assert_eq!(names(&to_record_batch(&dataset).unwrap()), names(&batch));
+    assert_eq!(names(&to_record_batch(&record_batch_columns(&batch)).unwrap()), names(&batch));
+
+    let columns = record_batch_columns(&batch);
+    let expected = WolframValue::NumericArray(vec![0.5.to_wolfram(), 1.5.to_wolfram(), 2.5.to_wolfram()]);
+    assert_eq!(columns.get("score").unwrap(), &expected);
+    assert_eq!(columns.get("id").unwrap().to_string(), "{1,Missing[\"NotAvailable\"],3}");
+
+    let table = wl!({<|"a" -> 1, "b" -> "x"|>, <|"a" -> 2.5, "c" -> True|>});
+    let batch = to_record_batch(&table).unwrap();
+    assert_eq!(batch.schema().fields().iter().map(|f| f.data_type().clone()).collect::<Vec<_>>(), [
+        DataType::Float64,
+        DataType::Utf8,
+        DataType::Boolean
+    ]);
+    let mut bytes = vec![];
+    write_arrow(&table, &mut bytes).unwrap();
+    let expected = "Dataset[{<|\"a\"->1`,\"b\"->\"x\",\"c\"->Missing[\"NotAvailable\"]|>,<|\"a\"->2.5`,\"b\"->Missing[\"NotAvailable\"],\"c\"->True|>}]";
+    assert_eq!(parse_arrow(&bytes).unwrap().to_string(), expected);
+    let columns = wl!(<|"x" -> {1, 2}, "y" -> {"p", None}|>);
+    assert_eq!(to_record_batch(&columns).unwrap().num_rows(), 2);
+    assert!(to_record_batch(&wl!({1, 2})).is_err());
+
+    // numeric columns come back from bytes as `NumericArray[list, type]`
+    let batch = RecordBatch::try_from_iter(vec![("score", Arc::new(Float64Array::from(vec![0.5, 1.5])) as ArrayRef)]).unwrap();
+    let decoded = WolframValue::from_bytes(&record_batch_columns(&batch).to_bytes()).unwrap();
+    assert!(decoded.to_string().contains("NumericArray[{0.5`,1.5`},\"Real64\"]"));
+    assert_eq!(to_record_batch(&decoded).unwrap().columns(), batch.columns());
+
+    // decimals keep their digits
+    let decimals = Decimal128Array::from(vec![Some(12345), Some(-5), None]).with_precision_and_scale(10, 2).unwrap();
+    let batch = RecordBatch::try_from_iter(vec![("price", Arc::new(decimals) as ArrayRef)]).unwrap();
+    assert_eq!(record_batch_columns(&batch).to_string(), "<|\"price\"->{123.45`10.,-0.05`10.,Missing[\"NotAvailable\"]}|>");
+}
+
+#[test]
+#[cfg(feature = "parquet")]
+fn test_parquet() {
+    use wolfram_wxf::utils::{parse_parquet, write_parquet};
+    let table = wl!(Dataset[{<|"a" -> 1, "b" -> "x"|>, <|"a" -> 2, "b" -> "y"|>}]);
+    let mut bytes = vec![];
+    write_parquet(&table, &mut bytes).unwrap();
+    assert_eq!(parse_parquet(&bytes).unwrap().to_string(), table.to_string());
+}
+
+#[test]
+#[cfg(feature = "polars")]
+fn test_polars() {
+    use polars::prelude::*;
+    use wolfram_wxf::{utils::data_frame_columns, FromWolfram};
+
+    let frame = df!(
+        "id" => [1i64, 2, 3],
+        "score" => [Some(0.5), None, Some(2.5)],
+        "name" => ["a", "b", "c"],
+    )
+    .unwrap();
+    let dataset = "Dataset[{\
+        Association[Rule[\"id\",1],Rule[\"score\",0.5`],Rule[\"name\",\"a\"]],\
+        Association[Rule[\"id\",2],Rule[\"score\",Missing[\"NotAvailable\"]],Rule[\"name\",\"b\"]],\
+        Association[Rule[\"id\",3],Rule[\"score\",2.5`],Rule[\"name\",\"c\"]]}]";
+    assert_eq!(frame.to_wolfram().to_string(), dataset);
+    let columns = data_frame_columns(&frame);
+    let ids = WolframValue::PackedArray(vec![1i64.to_wolfram(), 2i64.to_wolfram(), 3i64.to_wolfram()]);
+    let expected = "Association[Rule[\"id\",{1,2,3}],Rule[\"score\",{0.5`,Missing[\"NotAvailable\"],2.5`}],Rule[\"name\",{\"a\",\"b\",\"c\"}]]";
+    assert_eq!(columns.to_string(), expected);
+    assert_eq!(columns.get("id").unwrap(), &ids);
+
+    let mut chunked = Series::new("n".into(), [Some(1i32), None]);
+    chunked.append(&Series::new("n".into(), [3i32])).unwrap();
+    assert_eq!((chunked.n_chunks(), chunked.to_wolfram().to_string()), (2, "{1,Missing[\"NotAvailable\"],3}".to_string()));
+    let dates = Series::new("day".into(), [0i32, 19000]).cast(&DataType::Date).unwrap();
+    assert_eq!(dates.to_wolfram().to_string(), "{DateObject[\"1970-01-01\"],DateObject[\"2022-01-08\"]}");
+
+    // columns keep their order both ways
+    let back = DataFrame::from_wolfram(&frame.to_wolfram()).unwrap();
+    assert_eq!(back.get_column_names(), ["id", "score", "name"]);
+    assert_eq!(back.column("score").unwrap().null_count(), 1);
+    assert_eq!(back.to_wolfram().to_string(), dataset);
+    assert_eq!(DataFrame::from_wolfram(&columns).unwrap().get_column_names(), ["id", "score", "name"]);
+    let series = Series::from_wolfram(&wl!({1, None, 3})).unwrap();
+    assert_eq!((series.dtype(), series.null_count()), (&DataType::Int64, 1));
+    assert!(DataFrame::from_wolfram(&wl!(f[x])).is_err());
+}
+
+#[test]
+#[cfg(feature = "sprs")]
+fn test_sprs() {
+    use sprs::{CsMat, CsVec};
+    use wolfram_wxf::FromWolfram;
+
+    let matrix = CsMat::new((2, 3), vec![0, 1, 2], vec![1, 0], vec![3.0, 4.0]);
+    let sparse = "SparseArray[Automatic,{2,3},0,{1,{{0,1,2},{{2},{1}}},{3`,4`}}]";
+    assert_eq!(matrix.to_wolfram().to_string(), sparse);
+    assert_eq!(matrix.to_csc().to_wolfram().to_string(), sparse);
+    assert_eq!(CsMat::<f64>::from_wolfram(&matrix.to_wolfram()), Ok(matrix));
+    // several values in a row, and a slice whose row pointers do not start at 0
+    let matrix = CsMat::new((3, 3), vec![0, 2, 2, 4], vec![0, 2, 0, 1], vec![1i64, 2, 3, 4]);
+    assert_eq!(matrix.to_csc().to_wolfram(), matrix.to_wolfram());
+    assert_eq!(CsMat::<i64>::from_wolfram(&matrix.to_csc().to_wolfram()), Ok(matrix.clone()));
+    assert_eq!(matrix.slice_outer(2..3).to_wolfram().to_string(), "SparseArray[Automatic,{1,3},0,{1,{{0,2},{{1},{2}}},{3,4}}]");
+
+    let vector = CsVec::new(5, vec![1, 4], vec![7i64, 8]);
+    assert_eq!(vector.to_wolfram().to_string(), "SparseArray[Automatic,{5},0,{1,{{0,2},{{2},{5}}},{7,8}}]");
+    assert_eq!(CsVec::<i64>::from_wolfram(&vector.to_wolfram()), Ok(vector.clone()));
+    assert!(CsMat::<i64>::from_wolfram(&vector.to_wolfram()).is_err());
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn test_image() {
+    use image::{DynamicImage, GrayImage, ImageBuffer, Luma, Rgb, RgbImage, Rgba};
+    use wolfram_wxf::{FromWolfram, WolframValue};
+
+    let gray = GrayImage::from_raw(2, 1, vec![0, 255]).unwrap();
+    assert_eq!(gray.to_wolfram().to_string(), "Image[NumericArray[{{0,255}},\"UnsignedInteger8\"],\"Byte\",Rule[ColorSpace,\"Grayscale\"]]");
+    let rgba = ImageBuffer::<Rgba<u16>, _>::from_raw(1, 1, vec![1u16, 2, 3, 65535]).unwrap();
+    assert_eq!(rgba.to_wolfram().to_string(), "Image[NumericArray[{{{1,2,3,65535}}},\"UnsignedInteger16\"],\"Bit16\",Rule[ColorSpace,\"RGB\"]]");
+    let real = ImageBuffer::<Luma<f32>, _>::from_raw(1, 2, vec![0.5f32, 1.0]).unwrap();
+    assert_eq!(real.to_wolfram().to_string(), "Image[NumericArray[{{0.5`},{1`}},\"Real32\"],\"Real32\",Rule[ColorSpace,\"Grayscale\"]]");
+
+    let rgb = RgbImage::from_raw(2, 2, (0..12).collect()).unwrap();
+    let decoded = WolframValue::from_bytes(&rgb.to_wolfram().to_bytes()).unwrap();
+    assert_eq!(RgbImage::from_wolfram(&decoded), Ok(rgb.clone()));
+    assert_eq!(DynamicImage::from_wolfram(&decoded), Ok(DynamicImage::ImageRgb8(rgb.clone())));
+    assert_eq!(ImageBuffer::<Luma<f32>, _>::from_wolfram(&real.to_wolfram()), Ok(real.clone()));
+    assert_eq!(DynamicImage::from_wolfram(&real.to_wolfram()).unwrap().as_rgb32f().unwrap().get_pixel(0, 1), &Rgb([1.0, 1.0, 1.0]));
+    assert!(GrayImage::from_wolfram(&rgb.to_wolfram()).is_err());
+    assert!(DynamicImage::from_wolfram(&WolframValue::function("Image", vec![1])).is_err());
+    // one byte per subpixel, as BinarySerialize writes a "Byte" image
+    let bytes = gray.to_wolfram().to_bytes();
+    assert!(bytes.windows(7).any(|w| w == [0xC2, 0x10, 2, 1, 2, 0, 255]));
+    let decoded = WolframValue::from_bytes(&bytes).unwrap();
+    assert_eq!(GrayImage::from_wolfram(&decoded), Ok(gray));
+    assert_eq!(WolframValue::from_bytes(&rgba.to_wolfram().to_bytes()).unwrap().to_string(), rgba.to_wolfram().to_string());
+}
@@ -0,0 +1,37 @@
+use std::collections::BTreeMap;
+use wolfram_wxf::{wl, FromWolfram, FromWolframError, ToWolfram, WolframValue};
+
+fn round_trip<T: FromWolfram + ToWolfram + PartialEq + std::fmt::Debug>(value: T) {
+    assert_eq!(T::from_wolfram(&value.to_wolfram()), Ok(value));
+}
+
+#[test]
+fn test_atoms() {
+    round_trip(true);
+    round_trip(-5i8);
+    round_trip(1u64 << 40);
+    round_trip(2.5f64);
+    round_trip("中文".to_string());
+    assert_eq!(u8::from_wolfram(&300.to_wolfram()), Err(FromWolframError { expected: "Integer in u8 range", found: 300.to_wolfram() }));
+    assert_eq!(f64::from_wolfram(&3.to_wolfram()), Ok(3.0));
+    //N[15/10, 20] and N[15/10, 20]*10^-3
+    assert_eq!(f64::from_wolfram(&WolframValue::BigDecimal("1.5`20.".to_string())), Ok(1.5));
+    assert_eq!(f64::from_wolfram(&WolframValue::BigDecimal("1.5`20.*^-3".to_string())), Ok(0.0015));
+    assert!(f64::from_wolfram(&WolframValue::BigDecimal("x`20.".to_string())).is_err());
+    assert_eq!(u64::from_wolfram(&WolframValue::integer(u64::MAX)), Ok(u64::MAX));
+    assert!(bool::from_wolfram(&wl!(x)).is_err());
+}
+
+#[test]
+fn test_containers() {
+    round_trip(vec![vec![1i32, 2], vec![]]);
+    let mut dict = BTreeMap::new();
+    dict.insert("a".to_string(), vec![1.5f64]);
+    round_trip(dict);
+    assert_eq!(<(i64, String)>::from_wolfram(&wl!({1, "a"})), Ok((1, "a".to_string())));
+    assert_eq!(<()>::from_wolfram(&wl!({})), Ok(()));
+    assert!(<(i64,)>::from_wolfram(&wl!({1, 2})).is_err());
+    assert_eq!(Option::<i64>::from_wolfram(&wl!(None)), Ok(None));
+    assert_eq!(Vec::<f64>::from_wolfram(&WolframValue::PackedArray(vec![1.0.to_wolfram()])), Ok(vec![1.0]));
+    assert!(Vec::<i64>::from_wolfram(&wl!(f[1])).is_err());
+}
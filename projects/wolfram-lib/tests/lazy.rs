@@ -0,0 +1,124 @@
+use std::{
+    collections::BTreeMap,
+    io::{BufReader, Cursor, Read, Seek, SeekFrom},
+};
+use wolfram_wxf::{wl, DecodeError, DecodeLimit, DecodeLimits, LazyError, LazyReader, PartError, PartSpec, ToWolfram, WolframValue};
+
+/// Counts the bytes actually read and the seeks
+struct Counting<R> {
+    inner: R,
+    read: usize,
+    seeks: usize,
+}
+
+impl<R: Read> Read for Counting<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read += n;
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for Counting<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.seeks += 1;
+        self.inner.seek(pos)
+    }
+}
+
+fn dataset() -> WolframValue {
+    let mut dict = BTreeMap::new();
+    for i in 0..100 {
+        let column = WolframValue::PackedArray(vec![(i as f64).to_wolfram(); 2000]);
+        dict.insert(format!("key{}", i), WolframValue::list(vec![format!("row {}", i).to_wolfram(), column]));
+    }
+    dict.to_wolfram()
+}
+
+#[test]
+fn test_get() {
+    let value = dataset();
+    let bytes = value.to_bytes();
+    let reader = LazyReader::new(Counting { inner: Cursor::new(&bytes), read: 0, seeks: 0 }).unwrap();
+    assert_eq!(reader.length(), 100);
+    assert_eq!(reader.keys().len(), 100);
+    let scanned = reader.into_inner().read;
+    assert!(scanned < bytes.len() / 10, "{} of {}", scanned, bytes.len());
+
+    // packed arrays come back as lists
+    let decoded = WolframValue::from_bytes(&bytes).unwrap();
+    let mut reader = LazyReader::new(Cursor::new(&bytes)).unwrap();
+    assert_eq!(reader.get("key42").unwrap(), *decoded.get("key42").unwrap());
+    assert_eq!(reader.part(&[PartSpec::from("key7"), PartSpec::from(1)]).unwrap(), "row 7".to_wolfram());
+    assert_eq!(reader.part(&[PartSpec::from("key7"), PartSpec::from(2), PartSpec::from(-1)]).unwrap(), 7.0.to_wolfram());
+    assert_eq!(reader.part(&[1, 1]).unwrap(), value.part(&[1, 1]).unwrap().clone());
+    assert_eq!(reader.part(&[1, 2, 3]).unwrap(), decoded.part(&[1, 2, 3]).unwrap().clone());
+    assert!(matches!(reader.get("missing"), Err(LazyError::Part(PartError::KeyAbsent { depth: 1, .. }))));
+    assert_eq!(reader.value().unwrap(), decoded);
+}
+
+#[test]
+fn test_index_keys() {
+    // keys are decoded while scanning, indexing does not seek per entry
+    let dict: BTreeMap<String, i32> = (0..1000).map(|i| (format!("key{}", i), i)).collect();
+    let bytes = dict.to_wolfram().to_bytes();
+    let mut reader = LazyReader::new(Counting { inner: Cursor::new(&bytes), read: 0, seeks: 0 }).unwrap();
+    assert_eq!(reader.get("key500").unwrap(), 500.to_wolfram());
+    let reader = reader.into_inner();
+    assert!(reader.seeks < 10, "{} seeks", reader.seeks);
+    // the file once, then the bytes of 500
+    assert!(reader.read < bytes.len() + 10, "{} of {}", reader.read, bytes.len());
+}
+
+#[test]
+fn test_part() {
+    let value = wl!(f[{1, 2, {x, "y"}}, <|a -> g[z], 1 -> 2|>, h]);
+    let bytes = value.to_bytes();
+    for depth in [1, 2, 5].iter() {
+        let mut reader = LazyReader::with_depth(BufReader::new(Cursor::new(&bytes)), *depth).unwrap();
+        assert_eq!(reader.length(), 3);
+        assert_eq!(reader.part(&[0]).unwrap(), wl!(f));
+        assert_eq!(reader.part(&[1, 3, 2]).unwrap(), wl!("y"));
+        assert_eq!(reader.part(&[1, -1, 0]).unwrap(), wl!(List));
+        assert_eq!(reader.part(&[PartSpec::from(2), PartSpec::Key(wl!(a)), PartSpec::from(1)]).unwrap(), wl!(z));
+        assert_eq!(reader.part(&[2, 2]).unwrap(), value.part(&[2, 2]).unwrap().clone());
+        assert_eq!(reader.part::<i32>(&[]).unwrap(), value);
+        assert!(matches!(reader.part(&[4]), Err(LazyError::Part(PartError::OutOfRange { depth: 1, index: 4, length: 3 }))));
+        assert!(matches!(reader.part(&[3, 1]), Err(LazyError::Part(PartError::Atomic { depth: 2, .. }))));
+        assert!(matches!(reader.part(&[PartSpec::All]), Err(LazyError::Part(PartError::Span { depth: 1 }))));
+    }
+}
+
+#[test]
+fn test_arrays() {
+    //Normal@BinarySerialize[{Developer`ToPackedArray[{{1, 2}, {3, 4}}]}]
+    let bytes = [56, 58, 102, 1, 115, 4, 76, 105, 115, 116, 193, 0, 2, 2, 2, 1, 2, 3, 4];
+    let mut reader = LazyReader::new(Cursor::new(&bytes[..])).unwrap();
+    assert_eq!(reader.part(&[1, 2, 1]).unwrap(), wl!(3));
+    assert_eq!(reader.part(&[1]).unwrap(), wl!({{1, 2}, {3, 4}}));
+}
+
+#[test]
+fn test_errors() {
+    let open = |bytes: &[u8]| LazyReader::new(Cursor::new(bytes.to_vec())).err().unwrap();
+    assert!(matches!(open(&wl!(x).to_compressed()), LazyError::Decode(DecodeError::Compressed)));
+    assert!(matches!(open(b"7:C\x00"), LazyError::Decode(DecodeError::InvalidHeader)));
+    assert!(matches!(open(b"8:f\x02s\x01gC"), LazyError::Decode(DecodeError::UnexpectedEnd { offset: 8 })));
+    assert!(matches!(open(b"8:C\x00\x00"), LazyError::Decode(DecodeError::TrailingBytes { offset: 4 })));
+    assert!(matches!(open(b"8:S\xff\xff\xff\xff\x0f"), LazyError::Decode(DecodeError::UnexpectedEnd { .. })));
+    // tokens are only checked once a part is decoded
+    let mut reader = LazyReader::new(Cursor::new(b"8:f\x01s\x01f-".to_vec())).unwrap();
+    assert!(matches!(reader.part(&[1]), Err(LazyError::Decode(DecodeError::InvalidToken { token: b'-', offset: 7 }))));
+}
+
+#[test]
+fn test_limits() {
+    let bytes = wl!(<|f[g[h]] -> 1, a -> {"long string"}|>).to_bytes();
+    let open = |depth, limits| LazyReader::with_limits(Cursor::new(&bytes), depth, limits);
+    // keys are decoded while indexing, with the given limits
+    let shallow = DecodeLimits { max_depth: 2, ..DecodeLimits::default() };
+    assert!(matches!(open(1, shallow).err(), Some(LazyError::Decode(DecodeError::LimitExceeded { limit: DecodeLimit::Depth, .. }))));
+    let short = DecodeLimits { max_length: 8, ..DecodeLimits::default() };
+    let mut reader = open(1, short).unwrap();
+    assert!(matches!(reader.get(wl!(a)), Err(LazyError::Decode(DecodeError::LimitExceeded { limit: DecodeLimit::Length, .. }))));
+}
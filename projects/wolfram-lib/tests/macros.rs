@@ -0,0 +1,35 @@
+use std::collections::BTreeMap;
+use wolfram_wxf::{wl, ToWolfram, WolframValue};
+
+#[test]
+fn test_function() {
+    let v = WolframValue::Function(Box::new(WolframValue::symbol("Sin")), vec![WolframValue::Integer8(1)]);
+    assert_eq!(wl!(Sin[1]), v);
+    assert_eq!(wl!(Sin[1][2][3]).to_string(), "Sin[1][2][3]");
+    assert_eq!(wl!(f[]).to_string(), "f[]");
+    let max = 10u8;
+    assert_eq!(wl!(Plot[Sin[x], {x, 0, #max}]).to_string(), "Plot[Sin[x],{x,0,10}]");
+    assert_eq!(wl!(x -> #(max + 1)).to_string(), "Rule[x,11]");
+}
+
+#[test]
+fn test_literal() {
+    //Normal@BinarySerialize[{-1, 128, 32768, 2147483648, 9223372036854775808}]
+    assert_eq!(wl!({-1, 128, 32768, 2147483648, 9223372036854775808}).to_wolfram_bytes(), [
+        56, 58, 102, 5, 115, 4, 76, 105, 115, 116, 67, 255, 106, 128, 0, 105, 0, 128, 0, 0, 76, 0, 0, 0, 128, 0, 0, 0, 0, 73, 19, 57, 50, 50,
+        51, 51, 55, 50, 48, 51, 54, 56, 53, 52, 55, 55, 53, 56, 48, 56
+    ]);
+    assert_eq!(wl!({"s", 0.5, -0.5, True}).to_string(), "{\"s\",0.5`,-0.5`,True}");
+    assert_eq!(wl!(-128), WolframValue::Integer8(-128));
+    assert_eq!(wl!(b"\x01\x02"), WolframValue::Bytes(vec![1, 2]));
+}
+
+#[test]
+fn test_association() {
+    let v = "value";
+    let mut dict = BTreeMap::new();
+    dict.insert("a", v);
+    assert_eq!(wl!(<|"a" -> #v|>), dict.to_wolfram());
+    assert_eq!(wl!(<||>).to_string(), "<||>");
+    assert_eq!(wl!(<|"a" -> <|"b" :> 1|>, "c" -> (x -> y)|>).to_string(), r#"<|"a"-><|"b":>1|>,"c"->Rule[x,y]|>"#);
+}
@@ -1,8 +1,22 @@
+mod borrowed;
+mod canonical;
+mod codec;
 mod compressed;
 mod containers;
+mod decoding;
+mod diff;
 mod encoding;
+mod errors;
+mod formats;
+mod from_wolfram;
 mod functions;
+mod lazy;
+mod macros;
 mod numbers;
+mod parts;
+mod patterns;
+mod stream;
+mod visitor;
 
 use wolfram_wxf::{ToWolfram, WolframValue};
 
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use wolfram_wxf::{PartError, PartSpec, ToWolfram, WolframValue};
+
+fn matrix() -> WolframValue {
+    vec![vec![1, 2, 3], vec![4, 5, 6]].to_wolfram()
+}
+
+#[test]
+fn test_part() {
+    let m = matrix();
+    assert_eq!(m.head(), WolframValue::symbol("List"));
+    assert_eq!(m.length(), 2);
+    //{{1,2,3},{4,5,6}}[[2, 1]]
+    assert_eq!(m.part(&[2, 1]).unwrap().to_string(), "4");
+    //{{1,2,3},{4,5,6}}[[-1, -1]]
+    assert_eq!(m.part(&[-1, -1]).unwrap().to_string(), "6");
+    //{{1,2,3},{4,5,6}}[[1, 0]]
+    assert_eq!(m.part(&[1, 0]).unwrap().to_string(), "List");
+    assert_eq!(m.part(&[3]), Err(PartError::OutOfRange { depth: 1, index: 3, length: 2 }));
+    assert_eq!(m.part(&[1, -4]), Err(PartError::OutOfRange { depth: 2, index: -4, length: 3 }));
+    assert_eq!(m.part(&[1, 1, 1]), Err(PartError::Atomic { depth: 3, value: 1.to_wolfram() }));
+    assert_eq!(m.part(&[PartSpec::All]), Err(PartError::Span { depth: 1 }));
+}
+
+#[test]
+fn test_extract() {
+    let m = matrix();
+    //{{1,2,3},{4,5,6}}[[All, 2]]
+    assert_eq!(m.extract(&[PartSpec::All, PartSpec::Index(2)]).unwrap().to_string(), "{2,5}");
+    //{{1,2,3},{4,5,6}}[[0]]
+    assert_eq!(m.extract(&[0, 0]).unwrap().to_string(), "Symbol");
+    //Sin[1][[0]]
+    let v = WolframValue::function("Sin", vec![1]);
+    assert_eq!(v.extract(&[0]).unwrap().to_string(), "Sin");
+    assert_eq!(v.args(), [1.to_wolfram()]);
+}
+
+#[test]
+fn test_lookup() {
+    let mut dict = BTreeMap::new();
+    dict.insert("a", vec![1, 2]);
+    dict.insert("b", vec![3]);
+    let v = dict.to_wolfram();
+    //Lookup[<|"a"->{1,2},"b"->{3}|>, "b"]
+    assert_eq!(v.get("b").unwrap().to_string(), "{3}");
+    //<|"a"->{1,2},"b"->{3}|>[["a", 2]]
+    assert_eq!(v.part(&[PartSpec::from("a"), PartSpec::from(2)]).unwrap().to_string(), "2");
+    //<|"a"->{1,2},"b"->{3}|>[[2]]
+    assert_eq!(v.part(&[2]).unwrap().to_string(), "{3}");
+    assert_eq!(v.get("c"), Err(PartError::KeyAbsent { depth: 1, key: "c".to_wolfram() }));
+    //<|"a"->{1,2},"b"->{3}|>[[All, 1]]
+    assert_eq!(v.extract(&[PartSpec::All, PartSpec::Index(1)]).unwrap().to_string(), r#"<|"a"->1,"b"->3|>"#);
+    //{"b" -> 1, "a" -> 2}[["a"]], also the form of an association kept in order
+    let mut ordered = WolframValue::association_in_order(vec![("b".to_wolfram(), 1.to_wolfram()), ("a".to_wolfram(), 2.to_wolfram())]);
+    assert_eq!(ordered.to_string(), r#"Association[Rule["b",1],Rule["a",2]]"#);
+    assert_eq!(ordered.get("a").unwrap(), &2.to_wolfram());
+    *ordered.get_mut("b").unwrap() = 3.to_wolfram();
+    assert_eq!(ordered.get("b").unwrap(), &3.to_wolfram());
+    assert!(matches!(ordered.get("c"), Err(PartError::KeyAbsent { .. })));
+}
+
+#[test]
+fn test_mutation() {
+    let mut m = matrix();
+    *m.part_mut(&[1, 1]).unwrap() = 0.to_wolfram();
+    assert_eq!(m.to_string(), "{{0,2,3},{4,5,6}}");
+    m.set_part(&[PartSpec::All, PartSpec::Index(-1)], 9).unwrap();
+    assert_eq!(m.to_string(), "{{0,2,9},{4,5,9}}");
+    m.set_part(&[2, 0], WolframValue::symbol("f")).unwrap();
+    assert_eq!(m.to_string(), "{{0,2,9},f[4,5,9]}");
+    assert!(m.set_part(&[3], 0).is_err());
+
+    let mut dict = BTreeMap::new();
+    dict.insert("a", 1);
+    let mut v = dict.to_wolfram();
+    *v.get_mut("a").unwrap() = 2.to_wolfram();
+    assert_eq!(v.to_string(), r#"<|"a"->2|>"#);
+}
+
+#[test]
+fn test_packed() {
+    let m = WolframValue::PackedArray(vec![vec![1, 2].to_wolfram(), vec![3, 4].to_wolfram()]);
+    assert_eq!(m.head(), WolframValue::symbol("List"));
+    assert_eq!(m.length(), 2);
+    assert_eq!(m.part(&[2, 1]).unwrap().to_string(), "3");
+    assert_eq!(m.args().len(), 2);
+    assert_eq!(m.extract(&[PartSpec::All, PartSpec::Index(2)]).unwrap().to_string(), "{2,4}");
+    assert_eq!(m.part(&[3]), Err(PartError::OutOfRange { depth: 1, index: 3, length: 2 }));
+    let mut m = m;
+    m.set_part(&[1, 1], 0).unwrap();
+    assert_eq!(m.to_string(), "{{0,2},{3,4}}");
+}
@@ -0,0 +1,119 @@
+use wolfram_wxf::{
+    patterns::{Pattern, Rule},
+    wl, WolframValue,
+};
+
+#[test]
+fn test_match() {
+    //MatchQ[f[1, "a"], f[_Integer, _]]
+    let p = Pattern::function("f", vec![Pattern::blank_head("Integer"), Pattern::blank()]);
+    assert!(wl!(f[1, "a"]).matches(&p));
+    assert!(!wl!(f["a", 1]).matches(&p));
+    assert!(!wl!(f[1]).matches(&p));
+    //MatchQ[f[1, 1], f[x_, x_]]
+    let p = Pattern::from(wl!(f[Pattern[x, Blank[]], Pattern[x, Blank[]]]));
+    assert!(wl!(f[1, 1]).matches(&p));
+    assert!(!wl!(f[1, 2]).matches(&p));
+    //MatchQ[{1, 2, 3}, {__, 3}]
+    let p = Pattern::from(wl!({BlankSequence[], 3}));
+    assert!(wl!({1, 2, 3}).matches(&p));
+    assert!(!wl!({3}).matches(&p));
+    assert!(wl!({3}).matches(&Pattern::from(wl!({BlankNullSequence[], 3}))));
+}
+
+#[test]
+fn test_condition() {
+    //Cases[{1, -2, 3, "a"}, x_Integer /; x > 0]
+    let p = Pattern::named("x", Pattern::blank_head("Integer")).condition(|b| match b["x"] {
+        WolframValue::Integer8(i) => i > 0,
+        _ => false,
+    });
+    assert_eq!(wl!({1, -2, 3, "a"}).cases(&p), vec![wl!(1), wl!(3)]);
+    assert_eq!(wl!(<|"a" -> 1, "b" -> -1|>).cases(&p), vec![wl!(1)]);
+}
+
+#[test]
+fn test_replace() {
+    let rules = [Rule::new(wl!(f[Pattern[x, Blank[]]]), wl!(g[x, x]))];
+    //Replace[f[1], f[x_] -> g[x, x]]
+    assert_eq!(wl!(f[1]).replace(&rules).to_string(), "g[1,1]");
+    //Replace[{f[1]}, f[x_] -> g[x, x]]
+    assert_eq!(wl!({f[1]}).replace(&rules).to_string(), "{f[1]}");
+    //{f[1], <|"k" -> f[2]|>} /. f[x_] -> g[x, x]
+    assert_eq!(wl!({f[1], <|"k" -> f[2]|>}).replace_all(&rules).to_string(), r#"{g[1,1],<|"k"->g[2,2]|>}"#);
+    //Plot[x, PlotRange -> All, Axes -> False] /. _Rule -> Sequence[]
+    let strip = Rule::new(Pattern::blank_head("Rule"), wl!(Sequence[]));
+    assert_eq!(wl!(Plot[x, PlotRange -> All, Axes -> False]).replace_all(&[strip]).to_string(), "Plot[x]");
+    //f[1, 2, 3] /. f[a_, b__] :> g[b, a]
+    let rotate = Rule::new(wl!(f[Pattern[a, Blank[]], Pattern[b, BlankSequence[]]]), wl!(g[b, a]));
+    assert_eq!(wl!(f[1, 2, 3]).replace_all(&[rotate]).to_string(), "g[2,3,1]");
+}
+
+#[test]
+fn test_replace_repeated() {
+    //f[f[f[x]]] //. f[y_] :> y
+    let unwrap = [Rule::delayed(wl!(f[Pattern[y, Blank[]]]), |b| b["y"].clone())];
+    assert_eq!(wl!(f[f[f[x]]]).replace_all(&unwrap).to_string(), "f[f[x]]");
+    assert_eq!(wl!(f[f[f[x]]]).replace_repeated(&unwrap).to_string(), "x");
+    //Sin[x] /. Sin -> Cos
+    let rename = Rule::new(wl!(Sin), wl!(Cos));
+    assert_eq!(wl!(Sin[Sin[x]]).replace_repeated(&[rename]).to_string(), "Cos[Cos[x]]");
+}
+
+#[test]
+fn test_threads() {
+    let positive = Pattern::blank().condition(|_| true);
+    let unwrap = Rule::delayed(wl!(f[Pattern[y, Blank[]]]), |b| b["y"].clone());
+    let out = std::thread::spawn(move || (wl!(f[1]).matches(&positive), wl!(f[1]).replace(&[unwrap]))).join().unwrap();
+    assert_eq!(out, (true, wl!(1)));
+}
+
+#[test]
+fn test_literal_normal_form() {
+    //MatchQ[1, 1] whatever the integer width
+    assert!(WolframValue::Integer64(1).matches(&Pattern::Literal(wl!(1))));
+    assert!(!WolframValue::Integer64(2).matches(&Pattern::Literal(wl!(1))));
+    //MatchQ[System`None, None]
+    assert!(WolframValue::symbol("System`None").matches(&Pattern::Literal(WolframValue::symbol("None"))));
+    assert!(wl!(f[1]).matches(&Pattern::Literal(WolframValue::function("Global`f", vec![WolframValue::Integer64(1)]))));
+    let packed = WolframValue::PackedArray(vec![WolframValue::Integer64(1), WolframValue::Integer64(2)]);
+    assert!(packed.matches(&Pattern::Literal(wl!({1, 2}))));
+    //MatchQ[1, _System`Integer]
+    assert!(wl!(1).matches(&Pattern::Blank(Some(WolframValue::symbol("System`Integer")))));
+}
+
+#[test]
+fn test_packed_array() {
+    //MatchQ[Developer`ToPackedArray[{1, 2, 3}], {x_, __}]
+    let packed = WolframValue::PackedArray(vec![wl!(1), wl!(2), wl!(3)]);
+    let p = Pattern::function("List", vec![Pattern::named("x", Pattern::blank()), Pattern::sequence()]);
+    assert_eq!(p.match_value(&packed, &Default::default()).unwrap()["x"], wl!(1));
+    assert!(packed.matches(&Pattern::from(wl!({BlankSequence[Integer]}))));
+    assert!(!packed.matches(&Pattern::from(wl!({Blank[], Blank[]}))));
+    //Developer`ToPackedArray[{1, 2, 3}] /. 2 -> x
+    let rules = [Rule::new(wl!(2), wl!(x))];
+    assert_eq!(packed.replace_all(&rules), wl!({1, x, 3}));
+    assert_eq!(packed.replace_all(&[Rule::new(wl!(5), wl!(x))]), packed);
+    let numeric = WolframValue::NumericArray(vec![wl!(1), wl!(2)]);
+    assert_eq!(numeric.replace_all(&[Rule::new(wl!(2), wl!(3))]), WolframValue::NumericArray(vec![wl!(1), wl!(3)]));
+    //f[2] /. f[x_] -> Developer`ToPackedArray[{x, 1}]
+    let template = Rule::new(wl!(f[Pattern[x, Blank[]]]), WolframValue::PackedArray(vec![wl!(x), wl!(1)]));
+    assert_eq!(wl!(f[2]).replace(&[template]), wl!({2, 1}));
+}
+
+#[test]
+fn test_association() {
+    //MatchQ[<|"a" -> 1, "b" -> "s"|>, <|"b" -> _String, "a" -> x_Integer|>]
+    let p = Pattern::from(wl!(<|"b" -> Blank[String], "a" -> Pattern[x, Blank[Integer]]|>));
+    let value = wl!(<|"a" -> 1, "b" -> "s"|>);
+    assert_eq!(p.match_value(&value, &Default::default()).unwrap()["x"], wl!(1));
+    assert!(!wl!(<|"a" -> 1|>).matches(&p));
+    assert!(!wl!(<|"a" -> "s", "b" -> "s"|>).matches(&p));
+    //MatchQ[<|1 -> 2, 3 -> 4|>, <|k_ -> _, k_ -> _|>] fails, the same name captures one key
+    let k = Pattern::named("k", Pattern::blank());
+    let p = Pattern::association(vec![(k.clone(), Pattern::blank()), (k, Pattern::blank())]);
+    assert!(!wl!(<|1 -> 2, 3 -> 4|>).matches(&p));
+    //Cases[{<|"a" -> 1|>, <|"a" -> 2, "b" -> 3|>}, <|"a" -> _|>]
+    let p = Pattern::association(vec![(Pattern::Literal(wl!("a")), Pattern::blank())]);
+    assert_eq!(wl!({<|"a" -> 1|>, <|"a" -> 2, "b" -> 3|>}).cases(&p), vec![wl!(<|"a" -> 1|>)]);
+}
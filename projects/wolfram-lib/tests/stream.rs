@@ -0,0 +1,116 @@
+use std::io::Read;
+use wolfram_wxf::{wl, CompressOptions, DecodeError, DecodeLimit, StreamReader, StreamWriter, ToWolfram, WolframValue};
+
+/// Delivers at most `size` bytes per read, like a slow pipe
+struct Chunked<'a> {
+    input: &'a [u8],
+    size: usize,
+}
+
+impl<'a> Read for Chunked<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.size.min(buf.len()).min(self.input.len());
+        buf[..n].copy_from_slice(&self.input[..n]);
+        self.input = &self.input[n..];
+        Ok(n)
+    }
+}
+
+fn records() -> Vec<WolframValue> {
+    vec![wl!(f[x, 1]), "中文".to_wolfram(), WolframValue::list(vec![2.5.to_wolfram(); 300]), wl!(<|"a" -> 1|>)]
+}
+
+fn read_all(input: &[u8], size: usize) -> Vec<Result<WolframValue, DecodeError>> {
+    StreamReader::new(Chunked { input, size }).collect()
+}
+
+#[test]
+fn test_round_trip() {
+    let mut plain = StreamWriter::new(vec![]);
+    let mut compressed = StreamWriter::compressed(vec![], CompressOptions::fast());
+    for value in records() {
+        plain.write(&value).unwrap();
+        compressed.write(&value).unwrap();
+    }
+    let plain = plain.into_inner();
+    let compressed = compressed.into_inner();
+    assert!(plain.starts_with(&wl!(f[x, 1]).to_bytes()));
+    assert!(compressed.starts_with(b"8C:"));
+    for input in [&plain, &compressed] {
+        for size in [1, 7, 1 << 20] {
+            let out: Vec<_> = read_all(input, size).into_iter().map(|v| v.unwrap()).collect();
+            assert_eq!(out, records());
+        }
+    }
+    assert!(read_all(b"", 1).is_empty());
+}
+
+#[test]
+fn test_large_record() {
+    let value = WolframValue::Bytes((0..1 << 20).map(|i| i as u8).collect());
+    let mut bytes = value.to_bytes();
+    bytes.extend(wl!(x).to_bytes());
+    let out: Vec<_> = read_all(&bytes, 1 << 16).into_iter().map(|v| v.unwrap()).collect();
+    assert_eq!(out, vec![value, wl!(x)]);
+}
+
+#[test]
+fn test_mixed() {
+    let mut writer = StreamWriter::new(vec![]);
+    writer.write(&wl!(a)).unwrap();
+    let mut bytes = writer.into_inner();
+    bytes.extend(wl!(b).to_compressed());
+    bytes.extend(wl!(c).to_bytes());
+    let out: Vec<_> = read_all(&bytes, 3).into_iter().map(|v| v.unwrap()).collect();
+    assert_eq!(out, vec![wl!(a), wl!(b), wl!(c)]);
+}
+
+#[test]
+fn test_recover() {
+    let mut bytes = wl!(a).to_bytes();
+    // invalid token where the symbol starts
+    bytes.extend(b"8:f\x01\x00s\x01g");
+    bytes.extend(b"garbage");
+    bytes.extend(wl!(b).to_compressed());
+    // corrupted deflate stream
+    bytes.extend(b"8C:\x78\xDA\xFF\xFF\xFF\xFF");
+    bytes.extend(wl!(c).to_bytes());
+    for size in [1, 1 << 20] {
+        let out = read_all(&bytes, size);
+        assert_eq!(out.len(), 5);
+        assert_eq!(out[0], Ok(wl!(a)));
+        assert!(matches!(out[1], Err(DecodeError::InvalidToken { token: 0, .. })));
+        assert_eq!(out[2], Ok(wl!(b)));
+        assert!(matches!(out[3], Err(DecodeError::Decompress(_))));
+        assert_eq!(out[4], Ok(wl!(c)));
+    }
+}
+
+#[test]
+fn test_corrupt_length() {
+    let mut bytes = wl!(a).to_bytes();
+    // a string claiming 2^28 bytes
+    bytes.extend(b"8:S\x80\x80\x80\x80\x01abc");
+    bytes.extend(wl!(b).to_bytes());
+    bytes.extend(wl!(c).to_compressed());
+    // like a pipe that stays open, the input never ends
+    let mut reader = StreamReader::new(Chunked { input: &bytes, size: 7 }.chain(std::io::repeat(0)));
+    reader.set_max_record_size(1 << 10);
+    let out: Vec<_> = reader.take(4).collect();
+    assert_eq!(out[0], Ok(wl!(a)));
+    assert!(matches!(out[1], Err(DecodeError::LimitExceeded { limit: DecodeLimit::RecordSize, .. })));
+    assert_eq!(out[2..], [Ok(wl!(b)), Ok(wl!(c))]);
+}
+
+#[test]
+fn test_truncated() {
+    let mut bytes = wl!(f[x]).to_bytes();
+    bytes.extend(&wl!(g["long string"]).to_bytes()[..10]);
+    let out = read_all(&bytes, 4);
+    assert_eq!(out.len(), 2);
+    assert_eq!(out[0], Ok(wl!(f[x])));
+    assert!(matches!(out[1], Err(DecodeError::UnexpectedEnd { .. })));
+    // a cut off header is reported once
+    let out = read_all(b"8", 1);
+    assert!(matches!(out[..], [Err(DecodeError::UnexpectedEnd { .. })]));
+}
@@ -0,0 +1,90 @@
+use wolfram_wxf::{
+    visitor::{fold_children, walk, walk_mut, Fold, Visitor, VisitorMut},
+    wl, WolframValue,
+};
+
+#[derive(Default)]
+struct Symbols(Vec<String>);
+
+impl Visitor for Symbols {
+    fn visit(&mut self, value: &WolframValue) {
+        if let WolframValue::Symbol(s) = value {
+            self.0.push(s.clone())
+        }
+        walk(self, value)
+    }
+}
+
+struct Negate;
+
+impl VisitorMut for Negate {
+    fn visit_mut(&mut self, value: &mut WolframValue) {
+        match value {
+            WolframValue::Integer8(i) => *i = -*i,
+            _ => walk_mut(self, value),
+        }
+    }
+}
+
+struct Rename;
+
+impl Fold for Rename {
+    fn fold(&mut self, value: WolframValue) -> WolframValue {
+        match value {
+            WolframValue::Symbol(s) if s == "Sin" => WolframValue::symbol("Cos"),
+            _ => fold_children(self, value),
+        }
+    }
+}
+
+#[test]
+fn test_traits() {
+    let mut symbols = Symbols::default();
+    symbols.visit(&wl!(f[x, <|"a" -> g[y]|>]));
+    assert_eq!(symbols.0, ["f", "x", "g", "y"]);
+
+    let mut v = wl!({1, f[2], <|"a" -> 3|>});
+    Negate.visit_mut(&mut v);
+    assert_eq!(v.to_string(), r#"{-1,f[-2],<|"a"->-3|>}"#);
+
+    assert_eq!(Rename.fold(wl!(Sin[Sin[x]])).to_string(), "Cos[Cos[x]]");
+}
+
+#[test]
+fn test_iterators() {
+    let v = wl!(f[g[1, 2], 3]);
+    let pre: Vec<String> = v.pre_order().map(|v| v.to_string()).collect();
+    assert_eq!(pre, ["f[g[1,2],3]", "g[1,2]", "1", "2", "3"]);
+    let post: Vec<String> = v.post_order().map(|v| v.to_string()).collect();
+    assert_eq!(post, ["1", "2", "g[1,2]", "3", "f[g[1,2],3]"]);
+    //Level[f[g[1, 2], 3], {2}]
+    let level: Vec<String> = v.level(2).map(|v| v.to_string()).collect();
+    assert_eq!(level, ["1", "2"]);
+    assert_eq!(v.level(0).count(), 1);
+    assert_eq!(v.level(3).count(), 0);
+}
+
+#[test]
+fn test_metrics() {
+    //LeafCount[f[g[1, 2], 3]]
+    assert_eq!(wl!(f[g[1, 2], 3]).leaf_count(), 5);
+    //LeafCount[<|"a" -> 1|>]
+    assert_eq!(wl!(<|"a" -> 1|>).leaf_count(), 4);
+    //Depth[f[g[1, 2], 3]]
+    assert_eq!(wl!(f[g[1, 2], 3]).depth(), 3);
+    assert_eq!(wl!(f[]).depth(), 1);
+    assert_eq!(wl!(x).depth(), 1);
+    assert!(wl!("abc").byte_count() > wl!("").byte_count());
+}
+
+#[test]
+fn test_packed() {
+    use wolfram_wxf::ToWolfram;
+    //Depth[{{1, 2}, {3, 4}}], LeafCount[{{1, 2}, {3, 4}}]
+    let m = WolframValue::PackedArray(vec![vec![1, 2].to_wolfram(), vec![3, 4].to_wolfram()]);
+    assert_eq!((m.depth(), m.leaf_count()), (3, 7));
+    assert_eq!(m.pre_order().count(), 7);
+    let mut symbols = Symbols::default();
+    symbols.visit(&WolframValue::NumericArray(vec![wl!(x)]));
+    assert_eq!(symbols.0, vec!["x"]);
+}
@@ -0,0 +1,87 @@
+#ifndef WOLFRAM_LIBRARYLINK_H
+#define WOLFRAM_LIBRARYLINK_H
+
+/* Generated by cbindgen from src/lib.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * `LIBRARY_NO_ERROR`
+ */
+#define WXF_NO_ERROR 0
+
+/**
+ * `LIBRARY_TYPE_ERROR`, the input could not be read as the expected arguments
+ */
+#define WXF_TYPE_ERROR 1
+
+/**
+ * `LIBRARY_FUNCTION_ERROR`, the function failed or panicked
+ */
+#define WXF_FUNCTION_ERROR 6
+
+/**
+ * Bytes allocated by Rust
+ */
+typedef struct WXFBytes {
+  uint8_t *data;
+  uintptr_t len;
+} WXFBytes;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Release the output of any function of this library
+ *
+ * # Safety
+ *
+ * `bytes` must come from this library and must not be used afterwards.
+ */
+void wxf_bytes_free(struct WXFBytes bytes);
+
+/**
+ * `ImportString[json, "RawJSON"]`, objects become associations
+ *
+ * # Safety
+ *
+ * `input` must point to `length` readable bytes, `output` to a writable `WXFBytes`.
+ */
+int wxf_from_json(const uint8_t *input, uintptr_t length, struct WXFBytes *output);
+
+/**
+ * A YAML document, several documents become a list
+ *
+ * # Safety
+ *
+ * `input` must point to `length` readable bytes, `output` to a writable `WXFBytes`.
+ */
+int wxf_from_yaml(const uint8_t *input, uintptr_t length, struct WXFBytes *output);
+
+/**
+ * A Python pickle, dicts become associations
+ *
+ * # Safety
+ *
+ * `input` must point to `length` readable bytes, `output` to a writable `WXFBytes`.
+ */
+int wxf_from_pickle(const uint8_t *input, uintptr_t length, struct WXFBytes *output);
+
+/**
+ * Re-encode WXF input as `8C:`, for results kept in memory or written to disk
+ *
+ * # Safety
+ *
+ * `input` must point to `length` readable bytes, `output` to a writable `WXFBytes`.
+ */
+int wxf_compress(const uint8_t *input, uintptr_t length, struct WXFBytes *output);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* WOLFRAM_LIBRARYLINK_H */
@@ -0,0 +1,66 @@
+/* LibraryLink functions around the plain C ABI of wolfram_librarylink.h
+ *
+ * Compile with the headers in $InstallationDirectory/SystemFiles/IncludeFiles/C
+ * and link against the Rust library:
+ *
+ *     cc -shared -fPIC -I"$WOLFRAM_INCLUDE" -Iinclude shim/wolfram_librarylink.c \
+ *         -Ltarget/release -lwolfram_librarylink -o wxf_link.so
+ *
+ * Every function takes a ByteArray and returns one, failures are a serialized Failure:
+ *
+ *     LibraryFunctionLoad["wxf_link", "wxf_from_json_link", {{ByteArray, "Constant"}}, ByteArray]
+ */
+#include <string.h>
+
+#include "WolframLibrary.h"
+#include "WolframNumericArrayLibrary.h"
+#include "wolfram_librarylink.h"
+
+typedef int (*wxf_function)(const uint8_t *input, uintptr_t length, struct WXFBytes *output);
+
+DLLEXPORT mint WolframLibrary_getVersion(void) {
+    return WolframLibraryVersion;
+}
+
+DLLEXPORT int WolframLibrary_initialize(WolframLibraryData libData) {
+    return LIBRARY_NO_ERROR;
+}
+
+DLLEXPORT void WolframLibrary_uninitialize(WolframLibraryData libData) {
+}
+
+/* Call `f` on the bytes of the argument and return its output as a ByteArray */
+static int invoke(wxf_function f, WolframLibraryData libData, mint Argc, MArgument *Args, MArgument Res) {
+    WolframNumericArrayLibrary_Functions arrays = libData->numericarrayLibraryFunctions;
+    MNumericArray input, output;
+    struct WXFBytes bytes = {NULL, 0};
+    mint length;
+    if (Argc != 1) {
+        return LIBRARY_FUNCTION_ERROR;
+    }
+    input = MArgument_getMNumericArray(Args[0]);
+    if (arrays->MNumericArray_getType(input) != MNumericArray_Type_UBit8) {
+        return LIBRARY_TYPE_ERROR;
+    }
+    /* the status is in the output as well, a Failure reaches the kernel instead of LibraryFunctionError */
+    f((const uint8_t *)arrays->MNumericArray_getData(input), (uintptr_t)arrays->MNumericArray_getFlattenedLength(input), &bytes);
+    length = (mint)bytes.len;
+    if (arrays->MNumericArray_new(MNumericArray_Type_UBit8, 1, &length, &output) != LIBRARY_NO_ERROR) {
+        wxf_bytes_free(bytes);
+        return LIBRARY_MEMORY_ERROR;
+    }
+    memcpy(arrays->MNumericArray_getData(output), bytes.data, bytes.len);
+    wxf_bytes_free(bytes);
+    MArgument_setMNumericArray(Res, output);
+    return LIBRARY_NO_ERROR;
+}
+
+#define LINK(name)                                                                                  \
+    DLLEXPORT int name##_link(WolframLibraryData libData, mint Argc, MArgument *Args, MArgument Res) { \
+        return invoke(name, libData, Argc, Args, Res);                                              \
+    }
+
+LINK(wxf_from_json)
+LINK(wxf_from_yaml)
+LINK(wxf_from_pickle)
+LINK(wxf_compress)
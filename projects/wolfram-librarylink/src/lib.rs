@@ -0,0 +1,173 @@
+//! WXF conversions behind a plain C ABI, for `ForeignFunctionLoad` and LibraryLink wrappers
+//!
+//! Every function takes the bytes of a `ByteArray` and fills a [`WXFBytes`] with the result,
+//! which must be released with [`wxf_bytes_free`]. The status codes are those of LibraryLink,
+//! on failure the output holds `Failure["RustError", <|...|>]`.
+//!
+//! The C declarations are in `include/wolfram_librarylink.h`. These are not LibraryLink functions,
+//! `shim/wolfram_librarylink.c` wraps them as `LibraryFunctionLoad[lib, "wxf_from_json_link", {ByteArray}, ByteArray]`.
+
+use std::{
+    os::raw::c_int,
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr, slice,
+};
+use wolfram_wxf::{
+    utils::{parse_json, parse_pickle, parse_yaml},
+    wl, FromWolfram, ToWolfram, WolframValue,
+};
+
+/// `LIBRARY_NO_ERROR`
+pub const WXF_NO_ERROR: c_int = 0;
+/// `LIBRARY_TYPE_ERROR`, the input could not be read as the expected arguments
+pub const WXF_TYPE_ERROR: c_int = 1;
+/// `LIBRARY_FUNCTION_ERROR`, the function failed or panicked
+pub const WXF_FUNCTION_ERROR: c_int = 6;
+
+/// Bytes allocated by Rust
+#[repr(C)]
+#[derive(Debug)]
+pub struct WXFBytes {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl WXFBytes {
+    fn new(bytes: Vec<u8>) -> Self {
+        let len = bytes.len();
+        Self { data: Box::into_raw(bytes.into_boxed_slice()) as *mut u8, len }
+    }
+    /// Nothing allocated
+    pub fn empty() -> Self {
+        Self { data: ptr::null_mut(), len: 0 }
+    }
+    /// # Safety
+    ///
+    /// Must have been filled by this library and not freed yet
+    pub unsafe fn as_slice(&self) -> &[u8] {
+        if self.data.is_null() { &[] } else { slice::from_raw_parts(self.data, self.len) }
+    }
+}
+
+/// Release the output of any function of this library
+///
+/// # Safety
+///
+/// `bytes` must come from this library and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn wxf_bytes_free(bytes: WXFBytes) {
+    if !bytes.data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(bytes.data, bytes.len)))
+    }
+}
+
+/// Run `f` on the raw input and write the WXF of its result, panics are caught
+///
+/// # Safety
+///
+/// `input` must point to `length` readable bytes, or be null with `length` zero,
+/// `output` must point to a writable [`WXFBytes`].
+pub unsafe fn convert<F>(input: *const u8, length: usize, output: *mut WXFBytes, f: F) -> c_int
+where
+    F: FnOnce(&[u8]) -> Result<WolframValue, (c_int, String)>,
+{
+    convert_bytes(input, length, output, |input| f(input)?.try_to_bytes().map_err(|e| (WXF_FUNCTION_ERROR, e.to_string())))
+}
+
+/// The same as [`convert`] for a result that is already encoded
+unsafe fn convert_bytes<F>(input: *const u8, length: usize, output: *mut WXFBytes, f: F) -> c_int
+where
+    F: FnOnce(&[u8]) -> Result<Vec<u8>, (c_int, String)>,
+{
+    let input = if input.is_null() { &[][..] } else { slice::from_raw_parts(input, length) };
+    let result = catch_unwind(AssertUnwindSafe(|| f(input)));
+    let (status, bytes) = match result {
+        Ok(Ok(bytes)) => (WXF_NO_ERROR, bytes),
+        Ok(Err((status, message))) => (status, failure(&message)),
+        Err(e) => {
+            let message = e.downcast_ref::<&str>().map(|s| s.to_string()).or_else(|| e.downcast_ref::<String>().cloned());
+            (WXF_FUNCTION_ERROR, failure(&message.unwrap_or_else(|| "function panicked".to_string())))
+        }
+    };
+    output.write(WXFBytes::new(bytes));
+    status
+}
+
+/// Decode the WXF input as the arguments of `f` and write the WXF of its result
+///
+/// A function of two integers takes `{a, b}` and is written as
+///
+/// ```
+/// # use wolfram_librarylink::{call, WXFBytes};
+/// #[no_mangle]
+/// pub unsafe extern "C" fn add(input: *const u8, length: usize, output: *mut WXFBytes) -> i32 {
+///     call(input, length, output, |(a, b): (i64, i64)| Ok(a + b))
+/// }
+/// ```
+///
+/// # Safety
+///
+/// The same as [`convert`].
+pub unsafe fn call<A, R, F>(input: *const u8, length: usize, output: *mut WXFBytes, f: F) -> c_int
+where
+    A: FromWolfram,
+    R: ToWolfram,
+    F: FnOnce(A) -> Result<R, String>,
+{
+    convert(input, length, output, |input| {
+        let value = WolframValue::from_bytes(input).map_err(|e| (WXF_TYPE_ERROR, e.to_string()))?;
+        let args = A::from_wolfram(&value).map_err(|e| (WXF_TYPE_ERROR, e.to_string()))?;
+        f(args).map(|r| r.to_wolfram()).map_err(|e| (WXF_FUNCTION_ERROR, e))
+    })
+}
+
+fn failure(message: &str) -> Vec<u8> {
+    wl!(Failure["RustError", <|"MessageTemplate" -> #message, "MessageParameters" -> <||>|>]).to_bytes()
+}
+
+fn text(input: &[u8]) -> Result<&str, (c_int, String)> {
+    std::str::from_utf8(input).map_err(|e| (WXF_TYPE_ERROR, e.to_string()))
+}
+
+/// `ImportString[json, "RawJSON"]`, objects become associations
+///
+/// # Safety
+///
+/// `input` must point to `length` readable bytes, `output` to a writable `WXFBytes`.
+#[no_mangle]
+pub unsafe extern "C" fn wxf_from_json(input: *const u8, length: usize, output: *mut WXFBytes) -> c_int {
+    convert(input, length, output, |input| parse_json(text(input)?).map_err(|e| (WXF_TYPE_ERROR, e.to_string())))
+}
+
+/// A YAML document, several documents become a list
+///
+/// # Safety
+///
+/// `input` must point to `length` readable bytes, `output` to a writable `WXFBytes`.
+#[no_mangle]
+pub unsafe extern "C" fn wxf_from_yaml(input: *const u8, length: usize, output: *mut WXFBytes) -> c_int {
+    convert(input, length, output, |input| parse_yaml(text(input)?).map_err(|e| (WXF_TYPE_ERROR, e.to_string())))
+}
+
+/// A Python pickle, dicts become associations
+///
+/// # Safety
+///
+/// `input` must point to `length` readable bytes, `output` to a writable `WXFBytes`.
+#[no_mangle]
+pub unsafe extern "C" fn wxf_from_pickle(input: *const u8, length: usize, output: *mut WXFBytes) -> c_int {
+    convert(input, length, output, |input| parse_pickle(input).map_err(|e| (WXF_TYPE_ERROR, e.to_string())))
+}
+
+/// Re-encode WXF input as `8C:`, for results kept in memory or written to disk
+///
+/// # Safety
+///
+/// `input` must point to `length` readable bytes, `output` to a writable `WXFBytes`.
+#[no_mangle]
+pub unsafe extern "C" fn wxf_compress(input: *const u8, length: usize, output: *mut WXFBytes) -> c_int {
+    convert_bytes(input, length, output, |input| {
+        let value = WolframValue::from_bytes(input).map_err(|e| (WXF_TYPE_ERROR, e.to_string()))?;
+        value.try_to_compressed().map_err(|e| (WXF_FUNCTION_ERROR, e.to_string()))
+    })
+}
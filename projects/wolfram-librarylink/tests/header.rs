@@ -0,0 +1,23 @@
+use cbindgen::{Builder, Language};
+use std::{env, fs, path::Path};
+
+/// The checked in header matches the exported functions, `UPDATE_HEADER=1` rewrites it
+#[test]
+fn test_header() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let path = root.join("include/wolfram_librarylink.h");
+    let mut header = vec![];
+    Builder::new()
+        .with_src(root.join("src/lib.rs"))
+        .with_language(Language::C)
+        .with_include_guard("WOLFRAM_LIBRARYLINK_H")
+        .with_autogen_warning("/* Generated by cbindgen from src/lib.rs, do not edit */")
+        .with_cpp_compat(true)
+        .generate()
+        .unwrap()
+        .write(&mut header);
+    if env::var_os("UPDATE_HEADER").is_some() {
+        fs::write(&path, &header).unwrap();
+    }
+    assert_eq!(String::from_utf8(header).unwrap(), fs::read_to_string(&path).unwrap_or_default(), "run with UPDATE_HEADER=1");
+}
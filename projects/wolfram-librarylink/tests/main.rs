@@ -0,0 +1,88 @@
+mod header;
+
+use wolfram_librarylink::*;
+use wolfram_wxf::{wl, FromWolfram, ToWolfram, WolframValue};
+
+/// Call through the C ABI and copy the output
+fn invoke(f: unsafe extern "C" fn(*const u8, usize, *mut WXFBytes) -> i32, input: &[u8]) -> (i32, WolframValue) {
+    let mut output = WXFBytes::empty();
+    unsafe {
+        let status = f(input.as_ptr(), input.len(), &mut output);
+        let value = WolframValue::from_bytes(output.as_slice()).unwrap();
+        wxf_bytes_free(output);
+        (status, value)
+    }
+}
+
+fn is_failure(value: &WolframValue) -> bool {
+    value.head() == WolframValue::symbol("Failure")
+}
+
+#[no_mangle]
+unsafe extern "C" fn test_add(input: *const u8, length: usize, output: *mut WXFBytes) -> i32 {
+    call(input, length, output, |(a, b): (i64, i64)| a.checked_add(b).ok_or_else(|| "overflow".to_string()))
+}
+
+#[no_mangle]
+unsafe extern "C" fn test_lengths(input: *const u8, length: usize, output: *mut WXFBytes) -> i32 {
+    call(input, length, output, |(words,): (Vec<String>,)| {
+        if words.is_empty() {
+            panic!("no words")
+        }
+        Ok(words.iter().map(|w| w.chars().count()).collect::<Vec<_>>())
+    })
+}
+
+#[test]
+fn test_call() {
+    assert_eq!(invoke(test_add, &wl!({1, 41}).to_bytes()), (WXF_NO_ERROR, 42i64.to_wolfram()));
+    let (status, value) = invoke(test_lengths, &wl!({{"a", "中文"}}).to_bytes());
+    assert_eq!(status, WXF_NO_ERROR);
+    assert_eq!(Vec::<usize>::from_wolfram(&value), Ok(vec![1, 2]));
+    // wrong arguments, a failing and a panicking function
+    for (f, input, expected) in [
+        (test_add as unsafe extern "C" fn(_, _, _) -> _, wl!({1, "2"}).to_bytes(), WXF_TYPE_ERROR),
+        (test_add, wl!({1}).to_bytes(), WXF_TYPE_ERROR),
+        (test_add, b"not wxf".to_vec(), WXF_TYPE_ERROR),
+        (test_add, wl!({9223372036854775807, 1}).to_bytes(), WXF_FUNCTION_ERROR),
+        (test_lengths, wl!({{}}).to_bytes(), WXF_FUNCTION_ERROR),
+    ] {
+        let (status, value) = invoke(f, &input);
+        assert_eq!(status, expected);
+        assert!(is_failure(&value), "{}", value);
+    }
+}
+
+#[test]
+fn test_formats() {
+    let (status, value) = invoke(wxf_from_json, br#"{"a": [1, 2.5, null], "b": true}"#);
+    assert_eq!(status, WXF_NO_ERROR);
+    assert_eq!(value.to_string(), r#"<|"a"->{1,2.5`,None},"b"->True|>"#);
+    let (status, value) = invoke(wxf_from_yaml, b"a:\n  - x\n  - 2\n");
+    assert_eq!(status, WXF_NO_ERROR);
+    assert_eq!(value.to_string(), r#"<|"a"->{"x",2}|>"#);
+    // pickle.dumps({"a": 1}, protocol=2)
+    let (status, value) = invoke(wxf_from_pickle, b"\x80\x02}q\x00X\x01\x00\x00\x00aq\x01K\x01s.");
+    assert_eq!(status, WXF_NO_ERROR);
+    assert_eq!(value.to_string(), r#"<|"a"->1|>"#);
+    for f in [wxf_from_json as unsafe extern "C" fn(_, _, _) -> _, wxf_from_yaml, wxf_from_pickle] {
+        let (status, value) = invoke(f, b"\xff{[");
+        assert_eq!(status, WXF_TYPE_ERROR);
+        assert!(is_failure(&value));
+    }
+}
+
+#[test]
+fn test_compress() {
+    let value = wl!(f[x, {1, 2, 3}, "text"]);
+    let (status, compressed) = invoke(wxf_compress, &value.to_bytes());
+    assert_eq!((status, compressed), (WXF_NO_ERROR, value));
+    let (status, value) = invoke(wxf_compress, b"8:");
+    assert_eq!(status, WXF_TYPE_ERROR);
+    assert!(is_failure(&value));
+    // values without a WXF form are a function error
+    let (status, value) = invoke(wxf_compress, b"8:s\x03a b");
+    assert_eq!(status, WXF_FUNCTION_ERROR);
+    assert!(is_failure(&value));
+    unsafe { wxf_bytes_free(WXFBytes::empty()) }
+}
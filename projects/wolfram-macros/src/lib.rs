@@ -0,0 +1,40 @@
+mod parser;
+
+use proc_macro::TokenStream;
+
+/// Build a `WolframValue` from Wolfram Language syntax
+///
+/// ```
+/// use wolfram_wxf::wl;
+/// let max = 10;
+/// assert_eq!(wl!(Plot[Sin[x], {x, 0, #max}]).to_string(), "Plot[Sin[x],{x,0,10}]");
+/// assert_eq!(wl!(<|"a" -> #(max + 1), "b" :> f[-1.5]|>).to_string(), r#"<|"a"->11,"b":>f[-1.5`]|>"#);
+/// assert_eq!(wl!({x -> 1.5e3, <|a :> 1|>}), wl!({x -> 1500.0, <|a :> 1|>}));
+/// ```
+///
+/// - `f[x, y]` functions, `{x, y}` lists, `<|k -> v|>` associations
+/// - `x -> y` and `x :> y` rules outside of associations
+/// - string, integer and real literals, other identifiers are symbols
+/// - `#name` and `#(expr)` insert any `ToWolfram` value
+///
+/// Integers beyond `i64` need the `bigint` feature of `wolfram_wxf`.
+/// Rules are written without spaces and reals must fit `f64`:
+///
+/// ```compile_fail
+/// wolfram_wxf::wl!(x - > y);
+/// ```
+///
+/// ```compile_fail
+/// wolfram_wxf::wl!(<|a : > 1|>);
+/// ```
+///
+/// ```compile_fail
+/// wolfram_wxf::wl!(1e400);
+/// ```
+#[proc_macro]
+pub fn wl(input: TokenStream) -> TokenStream {
+    match parser::Parser::new(input.into()).parse_all() {
+        Ok(o) => o.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,205 @@
+use proc_macro2::{Delimiter, Literal, Spacing, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::{Error, Lit, Result};
+
+pub struct Parser {
+    tokens: Vec<TokenTree>,
+    cursor: usize,
+}
+
+impl Parser {
+    pub fn new(input: TokenStream) -> Self {
+        Self { tokens: input.into_iter().collect(), cursor: 0 }
+    }
+    pub fn parse_all(mut self) -> Result<TokenStream> {
+        let out = self.parse_expr()?;
+        match self.peek() {
+            Some(t) => Err(Error::new(t.span(), "unexpected token after expression")),
+            None => Ok(out),
+        }
+    }
+    /// `lhs -> rhs`, `lhs :> rhs`
+    fn parse_expr(&mut self) -> Result<TokenStream> {
+        let lhs = self.parse_application()?;
+        let head = if self.eat_punct2('-', '>') {
+            "Rule"
+        }
+        else if self.eat_punct2(':', '>') {
+            "RuleDelayed"
+        }
+        else {
+            return Ok(lhs);
+        };
+        let rhs = self.parse_expr()?;
+        Ok(quote!(::wolfram_wxf::WolframValue::Function(
+            ::wolfram_wxf::__private::Box::new(::wolfram_wxf::WolframValue::symbol(#head)),
+            ::wolfram_wxf::__private::vec![#lhs, #rhs]
+        )))
+    }
+    /// `head[args][args]...`
+    fn parse_application(&mut self) -> Result<TokenStream> {
+        let mut out = self.parse_primary()?;
+        while let Some(TokenTree::Group(g)) = self.peek() {
+            if g.delimiter() != Delimiter::Bracket {
+                break;
+            }
+            let args = Parser::new(g.stream()).parse_sequence()?;
+            self.cursor += 1;
+            out = quote!(::wolfram_wxf::WolframValue::Function(::wolfram_wxf::__private::Box::new(#out), ::wolfram_wxf::__private::vec![#(#args),*]));
+        }
+        Ok(out)
+    }
+    fn parse_primary(&mut self) -> Result<TokenStream> {
+        let token = match self.next() {
+            Some(s) => s,
+            None => return Err(Error::new(self.span(), "expected expression")),
+        };
+        match token {
+            TokenTree::Ident(i) => {
+                let name = i.to_string();
+                Ok(quote!(::wolfram_wxf::WolframValue::symbol(#name)))
+            }
+            TokenTree::Literal(l) => literal(l, false),
+            TokenTree::Group(g) => match g.delimiter() {
+                Delimiter::Brace => {
+                    let items = Parser::new(g.stream()).parse_sequence()?;
+                    Ok(quote!(::wolfram_wxf::WolframValue::list(::wolfram_wxf::__private::vec![#(#items),*])))
+                }
+                Delimiter::Parenthesis => Parser::new(g.stream()).parse_all(),
+                _ => Err(Error::new(g.span(), "unexpected group")),
+            },
+            TokenTree::Punct(p) => match p.as_char() {
+                '-' => match self.next() {
+                    Some(TokenTree::Literal(l)) => literal(l, true),
+                    _ => Err(Error::new(p.span(), "expected number after `-`")),
+                },
+                '#' => match self.next() {
+                    Some(TokenTree::Ident(i)) => Ok(quote!(::wolfram_wxf::ToWolfram::to_wolfram(&#i))),
+                    Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
+                        let e = g.stream();
+                        Ok(quote!(::wolfram_wxf::ToWolfram::to_wolfram(&(#e))))
+                    }
+                    _ => Err(Error::new(p.span(), "expected `#name` or `#(expr)`")),
+                },
+                '<' if self.eat_punct('|') => self.parse_association(),
+                _ => Err(Error::new(p.span(), "unexpected punctuation")),
+            },
+        }
+    }
+    /// `<|k -> v, ...|>`, the leading `<|` is consumed
+    fn parse_association(&mut self) -> Result<TokenStream> {
+        let mut items = vec![];
+        loop {
+            if self.eat_punct2('|', '>') {
+                break;
+            }
+            if !items.is_empty() && !self.eat_punct(',') {
+                return Err(Error::new(self.span(), "expected `,` or `|>`"));
+            }
+            let key = self.parse_application()?;
+            let rule = if self.eat_punct2('-', '>') {
+                quote!(::wolfram_wxf::WolframValue::Rule)
+            }
+            else if self.eat_punct2(':', '>') {
+                quote!(::wolfram_wxf::WolframValue::RuleDelayed)
+            }
+            else {
+                return Err(Error::new(self.span(), "expected `->` or `:>`"));
+            };
+            let value = self.parse_expr()?;
+            items.push(quote!((#key, (#rule, #value))));
+        }
+        Ok(quote!(::wolfram_wxf::WolframValue::Association(::wolfram_wxf::__private::FromIterator::from_iter(::wolfram_wxf::__private::vec![#(#items),*]))))
+    }
+    /// Comma separated expressions, may be empty
+    fn parse_sequence(mut self) -> Result<Vec<TokenStream>> {
+        let mut out = vec![];
+        while self.peek().is_some() {
+            if !out.is_empty() && !self.eat_punct(',') {
+                return Err(Error::new(self.span(), "expected `,`"));
+            }
+            out.push(self.parse_expr()?);
+        }
+        Ok(out)
+    }
+    fn peek(&self) -> Option<&TokenTree> {
+        self.tokens.get(self.cursor)
+    }
+    fn next(&mut self) -> Option<TokenTree> {
+        let out = self.tokens.get(self.cursor).cloned();
+        self.cursor += 1;
+        out
+    }
+    fn span(&self) -> Span {
+        match self.peek().or_else(|| self.tokens.last()) {
+            Some(t) => t.span(),
+            None => Span::call_site(),
+        }
+    }
+    fn eat_punct(&mut self, c: char) -> bool {
+        match self.peek() {
+            Some(TokenTree::Punct(p)) if p.as_char() == c => {
+                self.cursor += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+    /// Two characters written together, `- >` is not `->`
+    fn eat_punct2(&mut self, a: char, b: char) -> bool {
+        match (self.tokens.get(self.cursor), self.tokens.get(self.cursor + 1)) {
+            (Some(TokenTree::Punct(p)), Some(TokenTree::Punct(q))) if p.as_char() == a && p.spacing() == Spacing::Joint && q.as_char() == b => {
+                self.cursor += 2;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Integers are narrowed to the smallest token, the same as `BinarySerialize`
+fn literal(l: Literal, negative: bool) -> Result<TokenStream> {
+    let sign = if negative { "-" } else { "" };
+    match Lit::new(l) {
+        Lit::Str(s) if !negative => {
+            let s = s.value();
+            Ok(quote!(::wolfram_wxf::WolframValue::String(::wolfram_wxf::__private::String::from(#s))))
+        }
+        Lit::ByteStr(s) if !negative => {
+            let s = Literal::byte_string(&s.value());
+            Ok(quote!(::wolfram_wxf::WolframValue::Bytes(#s.to_vec())))
+        }
+        Lit::Int(i) => {
+            let digits = format!("{}{}", sign, i.base10_digits());
+            let out = match digits.parse::<i64>() {
+                Ok(n) if n as i8 as i64 == n => {
+                    let n = Literal::i8_suffixed(n as i8);
+                    quote!(::wolfram_wxf::WolframValue::Integer8(#n))
+                }
+                Ok(n) if n as i16 as i64 == n => {
+                    let n = Literal::i16_suffixed(n as i16);
+                    quote!(::wolfram_wxf::WolframValue::Integer16(#n))
+                }
+                Ok(n) if n as i32 as i64 == n => {
+                    let n = Literal::i32_suffixed(n as i32);
+                    quote!(::wolfram_wxf::WolframValue::Integer32(#n))
+                }
+                Ok(n) => {
+                    let n = Literal::i64_suffixed(n);
+                    quote!(::wolfram_wxf::WolframValue::Integer64(#n))
+                }
+                Err(_) => quote!(::wolfram_wxf::__big_integer!(#digits)),
+            };
+            Ok(out)
+        }
+        Lit::Float(f) => {
+            let n = format!("{}{}", sign, f.base10_digits()).parse::<f64>().map_err(|e| Error::new(f.span(), e))?;
+            if !n.is_finite() {
+                return Err(Error::new(f.span(), "real literal out of the range of f64"));
+            }
+            let n = Literal::f64_suffixed(n);
+            Ok(quote!(::wolfram_wxf::WolframValue::Decimal64((#n).to_le_bytes())))
+        }
+        other => Err(Error::new(other.span(), "unsupported literal")),
+    }
+}
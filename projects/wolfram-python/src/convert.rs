@@ -0,0 +1,143 @@
+use crate::{Function, Symbol};
+use num::BigInt;
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    prelude::*,
+    types::{IntoPyDict, PyBool, PyBytes, PyDict, PyFloat, PyFrozenSet, PyList, PyLong, PySet, PyString, PyTuple},
+};
+use std::collections::BTreeMap;
+use wolfram_wxf::{
+    utils::{is_machine_number, normalized_symbol_name},
+    FromWolfram, ToWolfram, WolframValue,
+};
+
+pub fn to_value(obj: &Bound<'_, PyAny>) -> PyResult<WolframValue> {
+    if obj.is_none() {
+        return Ok(WolframValue::symbol("None"));
+    }
+    // before `int`, of which `bool` is a subclass
+    if let Ok(b) = obj.downcast::<PyBool>() {
+        return Ok(b.is_true().to_wolfram());
+    }
+    if let Ok(n) = obj.downcast::<PyLong>() {
+        return match n.extract::<i64>() {
+            Ok(n) => Ok(n.to_wolfram()),
+            // `str` is capped at 4300 digits, go through two's complement bytes
+            Err(_) => {
+                let length = n.call_method0("bit_length")?.extract::<usize>()? / 8 + 1;
+                let kwargs = [("signed", true)].into_py_dict_bound(n.py());
+                let bytes = n.call_method("to_bytes", (length, "little"), Some(&kwargs))?;
+                let bytes = bytes.downcast::<PyBytes>()?;
+                Ok(WolframValue::BigInteger(BigInt::from_signed_bytes_le(bytes.as_bytes())))
+            }
+        };
+    }
+    if let Ok(f) = obj.downcast::<PyFloat>() {
+        return Ok(f.value().to_wolfram());
+    }
+    if let Ok(s) = obj.downcast::<PyString>() {
+        return Ok(s.to_str()?.to_wolfram());
+    }
+    if let Ok(b) = obj.downcast::<PyBytes>() {
+        return Ok(WolframValue::Bytes(b.as_bytes().to_vec()));
+    }
+    if let Ok(d) = obj.downcast::<PyDict>() {
+        let mut dict = BTreeMap::new();
+        for (k, v) in d.iter() {
+            dict.insert(to_value(&k)?, (WolframValue::Rule, to_value(&v)?));
+        }
+        return Ok(WolframValue::Association(dict));
+    }
+    if obj.is_instance_of::<PyList>() || obj.is_instance_of::<PyTuple>() || obj.is_instance_of::<PySet>() || obj.is_instance_of::<PyFrozenSet>() {
+        return Ok(WolframValue::list(obj.iter()?.map(|v| to_value(&v?)).collect::<PyResult<_>>()?));
+    }
+    if let Ok(s) = obj.downcast::<Symbol>() {
+        return Ok(WolframValue::symbol(s.borrow().name.as_str()));
+    }
+    if let Ok(f) = obj.downcast::<Function>() {
+        return f.borrow().to_value(obj.py());
+    }
+    if obj.hasattr("dtype")? && obj.hasattr("tolist")? {
+        return array(obj);
+    }
+    Err(PyTypeError::new_err(format!("cannot convert {} to WXF", obj.get_type().name()?)))
+}
+
+/// numpy arrays of integers and reals are packed, others become plain lists
+fn array(obj: &Bound<'_, PyAny>) -> PyResult<WolframValue> {
+    let kind: String = obj.getattr("dtype")?.getattr("kind")?.extract()?;
+    let value = to_value(&obj.call_method0("tolist")?)?;
+    match value {
+        WolframValue::Function(_, items) if "iuf".contains(kind.as_str()) && items.iter().all(is_machine) => {
+            Ok(WolframValue::PackedArray(items))
+        }
+        value => Ok(value),
+    }
+}
+
+/// Nested lists of machine numbers, `uint64` values above `i64::MAX` are not
+fn is_machine(value: &WolframValue) -> bool {
+    match value {
+        WolframValue::Function(_, items) => items.iter().all(is_machine),
+        _ => is_machine_number(value),
+    }
+}
+
+pub fn from_value(py: Python<'_>, value: &WolframValue) -> PyResult<PyObject> {
+    let out = match value {
+        WolframValue::Skip => py.None(),
+        WolframValue::Symbol(s) => match normalized_symbol_name(s).as_str() {
+            "None" => py.None(),
+            "True" => true.into_py(py),
+            "False" => false.into_py(py),
+            _ => Symbol { name: s.clone() }.into_py(py),
+        },
+        WolframValue::Rule => Symbol { name: "Rule".to_string() }.into_py(py),
+        WolframValue::RuleDelayed => Symbol { name: "RuleDelayed".to_string() }.into_py(py),
+        WolframValue::String(s) => s.into_py(py),
+        WolframValue::Bytes(b) => PyBytes::new_bound(py, b).into_py(py),
+        WolframValue::Integer8(n) => n.into_py(py),
+        WolframValue::Integer16(n) => n.into_py(py),
+        WolframValue::Integer32(n) => n.into_py(py),
+        WolframValue::Integer64(n) => n.into_py(py),
+        WolframValue::BigInteger(n) => {
+            let bytes = PyBytes::new_bound(py, &n.to_signed_bytes_le());
+            let kwargs = [("signed", true)].into_py_dict_bound(py);
+            py.get_type_bound::<PyLong>().call_method("from_bytes", (bytes, "little"), Some(&kwargs))?.unbind()
+        }
+        WolframValue::Decimal64(d) => f64::from_le_bytes(*d).into_py(py),
+        WolframValue::BigDecimal(_) => f64::from_wolfram(value).map_err(|e| PyValueError::new_err(e.to_string()))?.into_py(py),
+        WolframValue::PackedArray(items) | WolframValue::NumericArray(items) => list(py, items)?,
+        WolframValue::Function(head, args) if **head == WolframValue::symbol("List") => list(py, args)?,
+        WolframValue::Function(head, args) => {
+            let args = args.iter().map(|v| from_value(py, v)).collect::<PyResult<Vec<_>>>()?;
+            Function::new(from_value(py, head)?, PyTuple::new_bound(py, args).unbind()).into_py(py)
+        }
+        WolframValue::Association(dict) => {
+            let out = PyDict::new_bound(py);
+            for (k, (_, v)) in dict {
+                out.set_item(key(py, k)?, from_value(py, v)?)?;
+            }
+            out.into_py(py)
+        }
+        _ => return Err(PyTypeError::new_err(format!("cannot convert {} to Python", value))),
+    };
+    Ok(out)
+}
+
+fn list(py: Python<'_>, items: &[WolframValue]) -> PyResult<PyObject> {
+    let items = items.iter().map(|v| from_value(py, v)).collect::<PyResult<Vec<_>>>()?;
+    Ok(PyList::new_bound(py, items).into_py(py))
+}
+
+/// Lists are not hashable, keys use tuples instead
+fn key(py: Python<'_>, value: &WolframValue) -> PyResult<PyObject> {
+    match value {
+        WolframValue::Function(head, args) if **head == WolframValue::symbol("List") => {
+            let items = args.iter().map(|v| key(py, v)).collect::<PyResult<Vec<_>>>()?;
+            Ok(PyTuple::new_bound(py, items).into_py(py))
+        }
+        _ => from_value(py, value),
+    }
+}
+
@@ -0,0 +1,49 @@
+//! `dumps` and `loads` for Python, with the mapping of the `pickle` feature of `wolfram_wxf`
+//!
+//! | Python                    | Wolfram Language               |
+//! |---------------------------|--------------------------------|
+//! | `None`, `True`, `False`   | `None`, `True`, `False`        |
+//! | `int` of any size         | `Integer`                      |
+//! | `float`                   | `Real`                         |
+//! | `str`, `bytes`            | `String`, `ByteArray`          |
+//! | `list`, `tuple`, `set`    | `List`                         |
+//! | `dict`                    | `Association`                  |
+//! | numeric numpy array       | packed `List`                  |
+//! | [`Symbol`], [`Function`]  | any other symbol or expression |
+//!
+//! numpy is not a dependency, arrays are recognized by their `dtype` and `tolist`.
+
+// expanded from `#[pyfunction]`
+#![allow(clippy::useless_conversion)]
+
+mod convert;
+mod objects;
+
+pub use self::objects::{Function, Symbol};
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
+use wolfram_wxf::WolframValue;
+
+/// `BinarySerialize[expr]`, or `BinarySerialize[expr, PerformanceGoal -> "Size"]` with `compress`
+#[pyfunction]
+#[pyo3(signature = (obj, compress = false))]
+fn dumps<'py>(py: Python<'py>, obj: &Bound<'py, PyAny>, compress: bool) -> PyResult<Bound<'py, PyBytes>> {
+    let value = convert::to_value(obj)?;
+    let bytes = if compress { value.try_to_compressed() } else { value.try_to_bytes() };
+    Ok(PyBytes::new_bound(py, &bytes.map_err(|e| PyValueError::new_err(e.to_string()))?))
+}
+
+/// `BinaryDeserialize[bytes]`, compressed or not
+#[pyfunction]
+fn loads(py: Python<'_>, data: &[u8]) -> PyResult<PyObject> {
+    let value = WolframValue::from_bytes(data).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    convert::from_value(py, &value)
+}
+
+#[pymodule]
+pub fn wxf(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(dumps, m)?)?;
+    m.add_function(wrap_pyfunction!(loads, m)?)?;
+    m.add_class::<Symbol>()?;
+    m.add_class::<Function>()?;
+    Ok(())
+}
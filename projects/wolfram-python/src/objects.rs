@@ -0,0 +1,86 @@
+use crate::convert::to_value;
+use pyo3::{basic::CompareOp, prelude::*, types::PyTuple};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+use wolfram_wxf::WolframValue;
+
+/// A symbol other than `True`, `False` and `None`, `Symbol("x")`
+#[pyclass(module = "wxf", frozen, eq, hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    #[pyo3(get)]
+    pub name: String,
+}
+
+#[pymethods]
+impl Symbol {
+    #[new]
+    fn py_new(name: String) -> Self {
+        Self { name }
+    }
+    fn __repr__(&self) -> String {
+        format!("Symbol({:?})", self.name)
+    }
+    fn __str__(&self) -> String {
+        self.name.clone()
+    }
+}
+
+/// An expression other than a list, `Function(Symbol("f"), 1, 2)`
+#[pyclass(module = "wxf", frozen)]
+pub struct Function {
+    #[pyo3(get)]
+    head: PyObject,
+    #[pyo3(get)]
+    args: Py<PyTuple>,
+}
+
+impl Function {
+    pub(crate) fn new(head: PyObject, args: Py<PyTuple>) -> Self {
+        Self { head, args }
+    }
+    pub(crate) fn to_value(&self, py: Python<'_>) -> PyResult<WolframValue> {
+        let args = self.args.bind(py).iter().map(|v| to_value(&v)).collect::<PyResult<_>>()?;
+        Ok(WolframValue::Function(Box::new(to_value(self.head.bind(py))?), args))
+    }
+}
+
+#[pymethods]
+impl Function {
+    #[new]
+    #[pyo3(signature = (head, *args))]
+    fn py_new(head: PyObject, args: Py<PyTuple>) -> Self {
+        Self { head, args }
+    }
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let mut out = format!("Function({}", self.head.bind(py).repr()?);
+        for arg in self.args.bind(py).iter() {
+            out.push_str(&format!(", {}", arg.repr()?));
+        }
+        out.push(')');
+        Ok(out)
+    }
+    /// `InputForm`
+    fn __str__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(self.to_value(py)?.to_string())
+    }
+    fn __richcmp__(&self, other: &Bound<'_, PyAny>, op: CompareOp) -> PyResult<PyObject> {
+        let py = other.py();
+        let equal = match other.downcast::<Function>() {
+            Ok(other) => self.to_value(py)? == other.get().to_value(py)?,
+            Err(_) => false,
+        };
+        Ok(match op {
+            CompareOp::Eq => equal.into_py(py),
+            CompareOp::Ne => (!equal).into_py(py),
+            _ => py.NotImplemented(),
+        })
+    }
+    fn __hash__(&self, py: Python<'_>) -> PyResult<u64> {
+        let mut hasher = DefaultHasher::new();
+        self.to_value(py)?.to_string().hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+}
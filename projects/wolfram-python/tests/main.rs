@@ -0,0 +1,78 @@
+use pyo3::{prelude::*, types::PyDict};
+use wolfram_wxf::{wl, ToWolfram, WolframValue};
+
+/// Run `code` with the module imported as `wxf`, then evaluate `expr`
+fn eval<'py>(py: Python<'py>, code: &str, expr: &str) -> Bound<'py, PyAny> {
+    let globals = PyDict::new_bound(py);
+    globals.set_item("wxf", pyo3::wrap_pymodule!(wxf::wxf)(py)).unwrap();
+    py.run_bound(code, Some(&globals), None).unwrap();
+    py.eval_bound(expr, Some(&globals), None).unwrap()
+}
+
+fn dumps(code: &str) -> Vec<u8> {
+    Python::with_gil(|py| eval(py, "", &format!("wxf.dumps({})", code)).extract().unwrap())
+}
+
+#[test]
+fn test_dumps() {
+    //Normal@BinarySerialize[None]
+    assert_eq!(dumps("None"), [56, 58, 115, 4, 78, 111, 110, 101]);
+    // ints are `Integer64` as in the `pickle` feature
+    assert_eq!(dumps("[True, 1, 2.5, 'a', b'\\x00']"), wl!({True, #(1i64), 2.5, "a", #(WolframValue::Bytes(vec![0]))}).to_bytes());
+    assert_eq!(dumps("2 ** 100"), WolframValue::integer(num::BigInt::from(2).pow(100)).to_bytes());
+    assert_eq!(dumps("{'b': (1, 2), 'a': {3}}"), wl!(<|"a" -> {#(3i64)}, "b" -> {#(1i64), #(2i64)}|>).to_bytes());
+    assert_eq!(dumps("wxf.Function(wxf.Symbol('f'), wxf.Symbol('x'), [])"), wl!(f[x, {}]).to_bytes());
+    assert_eq!(dumps("'中文', compress=True"), "中文".to_wolfram().to_compressed());
+}
+
+#[test]
+fn test_arrays() {
+    // the parts of a numpy array that are used
+    let fake = "class DType:\n    def __init__(self, kind): self.kind = kind\n\
+                class Array:\n    def __init__(self, kind, items): self.dtype, self.items = DType(kind), items\n    def tolist(self): return self.items\n";
+    let packed = WolframValue::PackedArray(vec![wl!({#(1i64), #(2i64)}), wl!({#(3i64), #(4i64)})]);
+    Python::with_gil(|py| {
+        let bytes: Vec<u8> = eval(py, fake, "wxf.dumps(Array('i', [[1, 2], [3, 4]]))").extract().unwrap();
+        assert_eq!(bytes, packed.to_bytes());
+        assert_eq!(bytes[2], 0xC1);
+        let bytes: Vec<u8> = eval(py, fake, "wxf.dumps(Array('f', [0.5, 1.5]))").extract().unwrap();
+        assert_eq!(bytes, WolframValue::PackedArray(vec![0.5.to_wolfram(), 1.5.to_wolfram()]).to_bytes());
+        // too large for a packed array, or not numeric
+        let bytes: Vec<u8> = eval(py, fake, "wxf.dumps(Array('u', [2 ** 64 - 1]))").extract().unwrap();
+        assert_eq!(bytes, WolframValue::list(vec![WolframValue::integer(u64::MAX)]).to_bytes());
+        let bytes: Vec<u8> = eval(py, fake, "wxf.dumps(Array('b', [True]))").extract().unwrap();
+        assert_eq!(bytes, wl!({True}).to_bytes());
+    });
+}
+
+#[test]
+fn test_loads() {
+    let value = wl!(<|"a" -> {1, 2.5, None, True}, {1, 2} -> f[x], "c" -> #(WolframValue::Bytes(vec![1]))|>);
+    let code = format!("value = wxf.loads(bytes({:?}))", value.to_bytes());
+    let expected = "{'a': [1, 2.5, None, True], (1, 2): wxf.Function(wxf.Symbol('f'), wxf.Symbol('x')), 'c': b'\\x01'}";
+    Python::with_gil(|py| {
+        assert!(eval(py, &code, &format!("value == {}", expected)).extract::<bool>().unwrap());
+        assert_eq!(eval(py, &code, "str(value[(1, 2)])").extract::<String>().unwrap(), "f[x]");
+        assert_eq!(eval(py, &code, "repr(value[(1, 2)])").extract::<String>().unwrap(), "Function(Symbol(\"f\"), Symbol(\"x\"))");
+        // round trip through both directions, compressed input included
+        let code = "big = 3 ** 80\nvalue = [big, -big, {'k': [b'', 'x']}, wxf.Symbol('y')]";
+        assert!(eval(py, code, "wxf.loads(wxf.dumps(value, compress=True)) == value").extract::<bool>().unwrap());
+        // past the 4300 digit limit of `int(str)`
+        let code = "big = -(7 ** 6000)\nvalue = [big, 2 ** 63, -2 ** 63 - 1]";
+        assert!(eval(py, code, "wxf.loads(wxf.dumps(value)) == value").extract::<bool>().unwrap());
+        let symbols = WolframValue::list(vec![WolframValue::symbol("System`None"), WolframValue::symbol("System`True")]).to_bytes();
+        assert!(eval(py, &format!("data = bytes({:?})", symbols), "wxf.loads(data) == [None, True]").extract::<bool>().unwrap());
+        let packed = WolframValue::PackedArray(vec![wl!({1, 2}), wl!({3, 4})]).to_bytes();
+        assert!(eval(py, &format!("data = bytes({:?})", packed), "wxf.loads(data) == [[1, 2], [3, 4]]").extract::<bool>().unwrap());
+    });
+}
+
+#[test]
+fn test_errors() {
+    Python::with_gil(|py| {
+        let code = "def error(f, *args):\n    try:\n        f(*args)\n    except Exception as e:\n        return type(e).__name__\n";
+        assert_eq!(eval(py, code, "error(wxf.dumps, object())").extract::<String>().unwrap(), "TypeError");
+        assert_eq!(eval(py, code, "error(wxf.loads, b'8:\\xff')").extract::<String>().unwrap(), "ValueError");
+        assert_eq!(eval(py, code, "error(wxf.dumps, wxf.Symbol('1x'))").extract::<String>().unwrap(), "ValueError");
+    });
+}
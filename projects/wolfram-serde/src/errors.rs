@@ -1,28 +1,3 @@
-use std::{
-    error::Error,
-    fmt::{self, Display, Formatter},
-};
-
-#[derive(Debug)]
-pub enum WXFError {
-    Custom(String),
-}
-
-impl Display for WXFError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
-impl Error for WXFError {}
-
-impl serde::ser::Error for WXFError {
-    fn custom<T>(msg: T) -> Self
-    where
-        T: Display,
-    {
-        Self::Custom(msg.to_string())
-    }
-}
+pub use wolfram_wxf::WXFError;
 
 pub type Result<T> = std::result::Result<T, WXFError>;
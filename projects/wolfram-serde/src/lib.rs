@@ -1,3 +1,5 @@
+#![allow(clippy::unit_arg)]
+
 mod de;
 mod errors;
 mod ser;
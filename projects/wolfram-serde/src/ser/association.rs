@@ -15,7 +15,7 @@ pub struct AssociationBuffer<'s> {
 // `serialize_entry` method allows serializers to optimize for the case where
 // key and value are both available simultaneously. In JSON it doesn't make a
 // difference so the default behavior for `serialize_entry` is fine.
-impl<'a> ser::SerializeMap for &'a mut WXFSerializer {
+impl ser::SerializeMap for &mut WXFSerializer {
     type Ok = ();
     type Error = Error;
 
@@ -31,11 +31,7 @@ impl<'a> ser::SerializeMap for &'a mut WXFSerializer {
     where
         T: ?Sized + Serialize,
     {
-        // if !self.inner.ends_with('{') {
-        //     self.inner += ",";
-        // }
-        // key.serialize(&mut **self)
-        unimplemented!()
+        Err(Error::UnsupportedValue("map".to_string()))
     }
 
     // It doesn't make a difference whether the colon is printed at the end of
@@ -45,9 +41,7 @@ impl<'a> ser::SerializeMap for &'a mut WXFSerializer {
     where
         T: ?Sized + Serialize,
     {
-        // self.inner += ":";
-        // value.serialize(&mut **self)
-        unimplemented!()
+        Err(Error::UnsupportedValue("map".to_string()))
     }
 
     fn end(self) -> Result<()> {
@@ -58,7 +52,7 @@ impl<'a> ser::SerializeMap for &'a mut WXFSerializer {
 // Structs are like maps in which the keys are constrained to be compile-time
 // constant strings.
 // Name[a -> b, c -> d]
-impl<'a> ser::SerializeStruct for &'a mut WXFSerializer {
+impl ser::SerializeStruct for &mut WXFSerializer {
     type Ok = ();
     type Error = Error;
 
@@ -80,7 +74,7 @@ impl<'a> ser::SerializeStruct for &'a mut WXFSerializer {
 
 // Similar to `SerializeTupleVariant`, here the `end` method is responsible for
 // closing both of the curly braces opened by `serialize_struct_variant`.
-impl<'a> ser::SerializeStructVariant for &'a mut WXFSerializer {
+impl ser::SerializeStructVariant for &mut WXFSerializer {
     type Ok = ();
     type Error = Error;
 
@@ -88,13 +82,7 @@ impl<'a> ser::SerializeStructVariant for &'a mut WXFSerializer {
     where
         T: ?Sized + Serialize,
     {
-        // if !self.inner.ends_with('{') {
-        //     self.inner += ",";
-        // }
-        // key.serialize(&mut **self)?;
-        // self.inner += ":";
-        // value.serialize(&mut **self)
-        unimplemented!()
+        Err(Error::UnsupportedValue("struct variant".to_string()))
     }
 
     fn end(self) -> Result<()> {
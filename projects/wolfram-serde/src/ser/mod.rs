@@ -104,9 +104,8 @@ impl<'a> Serializer for &'a mut WXFSerializer {
     // Serialize a byte array as an array of bytes. Could also use a base64
     // string here. Binary formats will typically represent byte arrays more
     // compactly.
-    fn serialize_bytes(self, _v: &[u8]) -> Result<()> {
-        // WolframValue::PackedArray()
-        unimplemented!()
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        Ok(self.this = WolframValue::Bytes(v.to_vec()))
     }
 
     /// None
@@ -130,25 +129,25 @@ impl<'a> Serializer for &'a mut WXFSerializer {
     // Unit struct means a named value containing no data. Again, since there is
     // no data, map this to JSON as `null`. There is no need to serialize the
     // name in most formats.
-    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
-        unimplemented!()
+    fn serialize_unit_struct(self, name: &'static str) -> Result<()> {
+        Ok(self.this = WolframValue::symbol(name))
     }
 
     // When serializing a unit variant (or any other kind of variant), formats
     // can choose whether to keep track of it by index or by name. Binary
     // formats typically use the index of the variant and human-readable formats
     // typically use the name.
-    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str) -> Result<()> {
-        unimplemented!()
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> Result<()> {
+        Ok(self.this = WolframValue::symbol(variant))
     }
 
     // As is done here, serializers are encouraged to treat newtype structs as
     // insignificant wrappers around the data they contain.
-    fn serialize_newtype_struct<T>(self, _name: &'static str, _value: &T) -> Result<()>
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
     {
-        unimplemented!()
+        value.serialize(self)
     }
 
     // Note that newtype variant (and all of the other variant serialization
@@ -156,11 +155,12 @@ impl<'a> Serializer for &'a mut WXFSerializer {
     // representation.
     //
     // Serialize this to JSON in externally tagged form as `{ NAME: VALUE }`.
-    fn serialize_newtype_variant<T>(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T) -> Result<()>
+    fn serialize_newtype_variant<T>(self, _name: &'static str, _variant_index: u32, variant: &'static str, value: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
     {
-        unimplemented!()
+        value.serialize(&mut *self)?;
+        Ok(self.this = WolframValue::function(variant, vec![self.this.to_owned()]))
     }
 
     // Now we get to the serialization of compound types.
@@ -220,15 +220,11 @@ impl<'a> Serializer for &'a mut WXFSerializer {
     // This is the externally tagged representation.
     fn serialize_struct_variant(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
+        variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant> {
-        // self.inner += "{";
-        // variant.serialize(&mut *self)?;
-        // self.inner += ":{";
-        // Ok(self)
-        unimplemented!()
+        Err(Error::UnsupportedValue(format!("struct variant {}::{}", name, variant)))
     }
 }
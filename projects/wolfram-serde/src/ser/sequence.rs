@@ -32,7 +32,7 @@ impl<'a> SerializeSeq for SequenceBuffer<'a> {
         self.push_sequence(value)
     }
 
-    fn end(mut self) -> Result<()> {
+    fn end(self) -> Result<()> {
         Ok(self.ptr.this = self.buffer.to_wolfram())
     }
 }
@@ -49,7 +49,7 @@ impl<'a> SerializeTuple for SequenceBuffer<'a> {
         self.push_sequence(value)
     }
 
-    fn end(mut self) -> Result<()> {
+    fn end(self) -> Result<()> {
         Ok(self.ptr.this = self.buffer.to_wolfram())
     }
 }
@@ -66,7 +66,7 @@ impl<'a> SerializeTupleStruct for SequenceBuffer<'a> {
         self.push_sequence(value)
     }
 
-    fn end(mut self) -> Result<()> {
+    fn end(self) -> Result<()> {
         Ok(self.ptr.this = WolframValue::function(self.name.unwrap(), self.buffer))
     }
 }
@@ -82,7 +82,7 @@ impl<'a> SerializeTupleVariant for SequenceBuffer<'a> {
         self.push_sequence(value)
     }
 
-    fn end(mut self) -> Result<()> {
+    fn end(self) -> Result<()> {
         Ok(self.ptr.this = WolframValue::function(self.name.unwrap(), self.buffer))
     }
 }
@@ -1,5 +1,5 @@
 use serde::Serialize;
-use serde_wxf::WXFSerializer;
+use serde_wxf::{WXFError, WXFSerializer};
 use wolfram_wxf::ToWolfram;
 
 
@@ -87,3 +87,28 @@ fn test_struct() {
 //     let expected = r#"{"Struct":{"a":1}}"#;
 //     assert_eq!(serialize(&s).unwrap(), expected);
 // }
+
+#[test]
+fn test_fallible() {
+    #[derive(Serialize)]
+    struct Meters(u8);
+    #[derive(Serialize)]
+    enum Shape {
+        Point,
+        Circle(u8),
+        Rect { w: u8 },
+    }
+    let mut serializer = WXFSerializer::default();
+
+    Meters(3).serialize(&mut serializer).unwrap();
+    assert_eq!(serializer.to_wolfram_string(), "3");
+
+    Shape::Point.serialize(&mut serializer).unwrap();
+    assert_eq!(serializer.to_wolfram_string(), "Point");
+
+    Shape::Circle(1).serialize(&mut serializer).unwrap();
+    assert_eq!(serializer.to_wolfram_string(), "Circle[1]");
+
+    let e = Shape::Rect { w: 1 }.serialize(&mut serializer).unwrap_err();
+    assert!(matches!(e, WXFError::UnsupportedValue(_)));
+}
@@ -0,0 +1,219 @@
+use js_sys::{
+    Array, ArrayBuffer, BigInt64Array, BigUint64Array, Float32Array, Float64Array, Int16Array, Int32Array, Int8Array, Map, Object, Reflect,
+    Uint16Array, Uint32Array, Uint8Array, Uint8ClampedArray,
+};
+use std::collections::BTreeMap;
+use wasm_bindgen::{prelude::*, JsCast};
+use wolfram_wxf::{utils::normalized_symbol_name, FromWolfram, ToWolfram, WolframValue};
+
+/// `Number.MAX_SAFE_INTEGER`
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+const SYMBOL: &str = "$symbol";
+const HEAD: &str = "$head";
+const ARGS: &str = "$args";
+
+fn error(e: JsValue) -> JsError {
+    JsError::new(&e.as_string().or_else(|| e.dyn_ref::<js_sys::Error>().map(|e| e.message().into())).unwrap_or_default())
+}
+
+fn get(value: &JsValue, key: &str) -> Result<JsValue, JsError> {
+    Reflect::get(value, &JsValue::from_str(key)).map_err(error)
+}
+
+fn has(value: &JsValue, key: &str) -> bool {
+    Reflect::has(value, &JsValue::from_str(key)).unwrap_or(false)
+}
+
+/// Typed arrays become `NumericArray[list, type]` of the same element type
+macro_rules! typed_arrays {
+    ($value:expr, $($t:ty => $kind:literal),*) => {$(
+        if let Some(array) = $value.dyn_ref::<$t>() {
+            return Ok(WolframValue::new_numeric_array(array.to_vec().iter().map(|n| n.to_wolfram()).collect(), $kind));
+        }
+    )*};
+}
+
+pub fn to_value(value: &JsValue) -> Result<WolframValue, JsError> {
+    if value.is_null() || value.is_undefined() {
+        return Ok(WolframValue::symbol("None"));
+    }
+    if let Some(b) = value.as_bool() {
+        return Ok(b.to_wolfram());
+    }
+    if let Some(n) = value.as_f64() {
+        return Ok(number(n));
+    }
+    if value.is_bigint() {
+        let digits: String = value.unchecked_ref::<js_sys::BigInt>().to_string(10).map_err(|e| error(e.into()))?.into();
+        return digits.parse().map(WolframValue::BigInteger).map_err(|e| JsError::new(&format!("{}", e)));
+    }
+    if let Some(s) = value.as_string() {
+        return Ok(s.to_wolfram());
+    }
+    if Array::is_array(value) {
+        return Ok(WolframValue::list(value.unchecked_ref::<Array>().iter().map(|v| to_value(&v)).collect::<Result<_, _>>()?));
+    }
+    if let Some(buffer) = value.dyn_ref::<ArrayBuffer>() {
+        return Ok(WolframValue::Bytes(Uint8Array::new(buffer).to_vec()));
+    }
+    typed_arrays!(
+        value,
+        Int8Array => "Integer8",
+        Int16Array => "Integer16",
+        Int32Array => "Integer32",
+        BigInt64Array => "Integer64",
+        Uint8Array => "UnsignedInteger8",
+        Uint8ClampedArray => "UnsignedInteger8",
+        Uint16Array => "UnsignedInteger16",
+        Uint32Array => "UnsignedInteger32",
+        BigUint64Array => "UnsignedInteger64",
+        Float32Array => "Real32",
+        Float64Array => "Real64"
+    );
+    if let Some(map) = value.dyn_ref::<Map>() {
+        let mut dict = BTreeMap::new();
+        for entry in map.entries() {
+            let entry: Array = entry.map_err(error)?.unchecked_into();
+            dict.insert(to_value(&entry.get(0))?, (WolframValue::Rule, to_value(&entry.get(1))?));
+        }
+        return Ok(WolframValue::Association(dict));
+    }
+    if value.is_function() || !value.is_object() {
+        return Err(JsError::new(&format!("cannot convert {:?} to WXF", value)));
+    }
+    if has(value, SYMBOL) {
+        return match get(value, SYMBOL)?.as_string() {
+            Some(name) => Ok(WolframValue::symbol(name)),
+            None => Err(JsError::new("$symbol must be a string")),
+        };
+    }
+    if has(value, HEAD) {
+        let args = get(value, ARGS)?;
+        let args = match args.dyn_ref::<Array>() {
+            Some(args) => args.iter().map(|v| to_value(&v)).collect::<Result<_, _>>()?,
+            None if args.is_undefined() => vec![],
+            None => return Err(JsError::new("$args must be an array")),
+        };
+        return Ok(WolframValue::Function(Box::new(to_value(&get(value, HEAD)?)?), args));
+    }
+    let mut dict = BTreeMap::new();
+    for entry in Object::entries(value.unchecked_ref::<Object>()).iter() {
+        let entry: Array = entry.unchecked_into();
+        dict.insert(to_value(&entry.get(0))?, (WolframValue::Rule, to_value(&entry.get(1))?));
+    }
+    Ok(WolframValue::Association(dict))
+}
+
+/// Integral numbers in the safe range are integers
+fn number(n: f64) -> WolframValue {
+    if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER { (n as i64).to_wolfram() } else { n.to_wolfram() }
+}
+
+fn integer(n: i64) -> JsValue {
+    if (n as f64).abs() <= MAX_SAFE_INTEGER { JsValue::from_f64(n as f64) } else { js_sys::BigInt::from(n).into() }
+}
+
+/// Own enumerable property, unlike `Reflect::set` a `"__proto__"` key does not replace the prototype
+fn define(object: &Object, key: &JsValue, value: &JsValue) {
+    let descriptor = Object::new();
+    for (name, v) in [("value", value), ("writable", &JsValue::TRUE), ("enumerable", &JsValue::TRUE), ("configurable", &JsValue::TRUE)] {
+        let _ = Reflect::set(&descriptor, &JsValue::from_str(name), v);
+    }
+    Object::define_property(object, key, &descriptor);
+}
+
+fn symbol(name: &str) -> Result<JsValue, JsError> {
+    let out = Object::new();
+    Reflect::set(&out, &JsValue::from_str(SYMBOL), &JsValue::from_str(name)).map_err(error)?;
+    Ok(out.into())
+}
+
+pub fn from_value(value: &WolframValue) -> Result<JsValue, JsError> {
+    let out = match value {
+        WolframValue::Skip => JsValue::undefined(),
+        WolframValue::Symbol(s) => match normalized_symbol_name(s).as_str() {
+            "None" => JsValue::null(),
+            "True" => JsValue::TRUE,
+            "False" => JsValue::FALSE,
+            _ => symbol(s)?,
+        },
+        WolframValue::Rule => symbol("Rule")?,
+        WolframValue::RuleDelayed => symbol("RuleDelayed")?,
+        WolframValue::String(s) => JsValue::from_str(s),
+        WolframValue::Bytes(b) => Uint8Array::from(&b[..]).buffer().into(),
+        WolframValue::Integer8(n) => JsValue::from(*n),
+        WolframValue::Integer16(n) => JsValue::from(*n),
+        WolframValue::Integer32(n) => JsValue::from(*n),
+        WolframValue::Integer64(n) => integer(*n),
+        WolframValue::BigInteger(n) => js_sys::BigInt::new(&JsValue::from_str(&n.to_string())).map_err(|e| error(e.into()))?.into(),
+        WolframValue::Decimal64(d) => JsValue::from_f64(f64::from_le_bytes(*d)),
+        WolframValue::BigDecimal(_) => JsValue::from_f64(f64::from_wolfram(value).map_err(|e| JsError::new(&e.to_string()))?),
+        WolframValue::PackedArray(items) | WolframValue::NumericArray(items) => array(items)?,
+        WolframValue::Function(head, args) if **head == WolframValue::symbol("List") => array(args)?,
+        WolframValue::Function(head, args) => match (head.as_ref(), args.as_slice()) {
+            (WolframValue::Symbol(s), [list, WolframValue::String(kind)]) if s == "NumericArray" => match numeric_array(kind, list) {
+                Some(out) => out,
+                None => function(head, args)?,
+            },
+            _ => function(head, args)?,
+        },
+        WolframValue::Association(dict) if dict.keys().all(|k| matches!(k, WolframValue::String(_))) => {
+            let out = Object::new();
+            for (k, (_, v)) in dict {
+                define(&out, &from_value(k)?, &from_value(v)?);
+            }
+            out.into()
+        }
+        WolframValue::Association(dict) => {
+            let out = Map::new();
+            for (k, (_, v)) in dict {
+                out.set(&from_value(k)?, &from_value(v)?);
+            }
+            out.into()
+        }
+        _ => return Err(JsError::new(&format!("cannot convert {} to JavaScript", value))),
+    };
+    Ok(out)
+}
+
+fn array(items: &[WolframValue]) -> Result<JsValue, JsError> {
+    Ok(items.iter().map(from_value).collect::<Result<Array, _>>()?.into())
+}
+
+fn function(head: &WolframValue, args: &[WolframValue]) -> Result<JsValue, JsError> {
+    let out = Object::new();
+    Reflect::set(&out, &JsValue::from_str(HEAD), &from_value(head)?).map_err(error)?;
+    Reflect::set(&out, &JsValue::from_str(ARGS), &array(args)?).map_err(error)?;
+    Ok(out.into())
+}
+
+fn typed<T: FromWolfram>(items: &[WolframValue]) -> Option<Vec<T>> {
+    items.iter().map(|v| T::from_wolfram(v).ok()).collect()
+}
+
+/// The typed array of the element type, nested in arrays for more than one dimension
+fn numeric_array(kind: &str, list: &WolframValue) -> Option<JsValue> {
+    let items = match list {
+        WolframValue::Function(head, args) if **head == WolframValue::symbol("List") => args,
+        _ => return None,
+    };
+    if items.iter().any(|v| matches!(v, WolframValue::Function(..))) {
+        return items.iter().map(|v| numeric_array(kind, v)).collect::<Option<Array>>().map(JsValue::from);
+    }
+    let out: JsValue = match kind {
+        "Integer8" => Int8Array::from(&typed::<i8>(items)?[..]).into(),
+        "Integer16" => Int16Array::from(&typed::<i16>(items)?[..]).into(),
+        "Integer32" => Int32Array::from(&typed::<i32>(items)?[..]).into(),
+        "Integer64" => BigInt64Array::from(&typed::<i64>(items)?[..]).into(),
+        "UnsignedInteger8" => Uint8Array::from(&typed::<u8>(items)?[..]).into(),
+        "UnsignedInteger16" => Uint16Array::from(&typed::<u16>(items)?[..]).into(),
+        "UnsignedInteger32" => Uint32Array::from(&typed::<u32>(items)?[..]).into(),
+        "UnsignedInteger64" => BigUint64Array::from(&typed::<u64>(items)?[..]).into(),
+        "Real32" => Float32Array::from(&typed::<f32>(items)?[..]).into(),
+        "Real64" => Float64Array::from(&typed::<f64>(items)?[..]).into(),
+        _ => return None,
+    };
+    Some(out)
+}
+
@@ -0,0 +1,50 @@
+//! `encode`, `decode` and `inputForm` for JavaScript, built with `wasm-pack build --target web`
+//!
+//! | JavaScript                        | Wolfram Language      |
+//! |-----------------------------------|-----------------------|
+//! | `null`, `undefined`               | `None`                |
+//! | `true`, `false`                   | `True`, `False`       |
+//! | integral `number`, `bigint`       | `Integer`             |
+//! | other `number`                    | `Real`                |
+//! | `string`                          | `String`              |
+//! | `ArrayBuffer`                     | `ByteArray`           |
+//! | `Array`                           | `List`                |
+//! | typed array                       | `NumericArray`        |
+//! | plain object, `Map`               | `Association`         |
+//! | `{ $symbol: "Pi" }`               | `Pi`                  |
+//! | `{ $head: h, $args: [a, b] }`     | `h[a, b]`             |
+//!
+//! Integers outside the safe range of `number` are decoded as `bigint`, associations with
+//! keys other than strings as `Map`. Encoded numeric arrays take the narrowest signed or real
+//! type that holds their elements.
+//!
+//! Tests run in node with `wasm-pack test --node`.
+
+mod convert;
+mod pretty;
+
+pub use self::pretty::input_form;
+use wasm_bindgen::prelude::*;
+use wolfram_wxf::WolframValue;
+
+/// `BinarySerialize[expr]`, or `BinarySerialize[expr, PerformanceGoal -> "Size"]` with `compress`
+#[wasm_bindgen]
+pub fn encode(value: &JsValue, compress: Option<bool>) -> Result<Vec<u8>, JsError> {
+    let value = convert::to_value(value)?;
+    let bytes = if compress.unwrap_or(false) { value.try_to_compressed() } else { value.try_to_bytes() };
+    bytes.map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// `BinaryDeserialize[bytes]`, compressed or not
+#[wasm_bindgen]
+pub fn decode(bytes: &[u8]) -> Result<JsValue, JsError> {
+    let value = WolframValue::from_bytes(bytes).map_err(|e| JsError::new(&e.to_string()))?;
+    convert::from_value(&value)
+}
+
+/// `InputForm` of the WXF `bytes`, parts longer than `width` (default 80) are broken over indented lines
+#[wasm_bindgen(js_name = inputForm)]
+pub fn input_form_of(bytes: &[u8], width: Option<usize>) -> Result<String, JsError> {
+    let value = WolframValue::from_bytes(bytes).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(input_form(&value, width.unwrap_or(80)))
+}
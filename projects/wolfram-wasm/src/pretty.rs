@@ -0,0 +1,105 @@
+use wolfram_wxf::WolframValue;
+
+/// An element of a list, an argument, or a rule of an association
+struct Entry<'a> {
+    key: Option<(&'a WolframValue, &'static str)>,
+    value: &'a WolframValue,
+}
+
+fn plain(items: &[WolframValue]) -> Vec<Entry<'_>> {
+    items.iter().map(|value| Entry { key: None, value }).collect()
+}
+
+/// Opening, elements and closing of an expression with parts
+fn parts(value: &WolframValue) -> Option<(String, Vec<Entry<'_>>, &'static str)> {
+    let out = match value {
+        WolframValue::Function(head, args) if **head == WolframValue::symbol("List") => ("{".to_string(), plain(args), "}"),
+        WolframValue::Function(head, args) => (format!("{}[", flat(head)), plain(args), "]"),
+        WolframValue::PackedArray(items) => ("{".to_string(), plain(items), "}"),
+        WolframValue::NumericArray(items) => ("NumericArray[{".to_string(), plain(items), "}]"),
+        WolframValue::Association(dict) => {
+            let entries = dict.iter().map(|(k, (r, value))| Entry { key: Some((k, operator(r))), value }).collect();
+            ("<|".to_string(), entries, "|>")
+        }
+        _ => return None,
+    };
+    Some(out)
+}
+
+fn operator(rule: &WolframValue) -> &'static str {
+    if *rule == WolframValue::RuleDelayed { " :> " } else { " -> " }
+}
+
+/// `a -> b` and `a :> b`
+fn rule(value: &WolframValue) -> Option<(&WolframValue, &'static str, &WolframValue)> {
+    match value {
+        WolframValue::Function(head, args) if args.len() == 2 => match head.as_ref() {
+            WolframValue::Symbol(s) if s == "Rule" => Some((&args[0], " -> ", &args[1])),
+            WolframValue::Symbol(s) if s == "RuleDelayed" => Some((&args[0], " :> ", &args[1])),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// One line with spaces after commas and around rules
+fn flat(value: &WolframValue) -> String {
+    if let Some((k, op, v)) = rule(value) {
+        return format!("{}{}{}", flat(k), op, flat(v));
+    }
+    match parts(value) {
+        Some((open, entries, close)) => {
+            let entries: Vec<String> = entries.iter().map(flat_entry).collect();
+            format!("{}{}{}", open, entries.join(", "), close)
+        }
+        None => value.to_string(),
+    }
+}
+
+fn flat_entry(entry: &Entry) -> String {
+    match entry.key {
+        Some((k, op)) => format!("{}{}{}", flat(k), op, flat(entry.value)),
+        None => flat(entry.value),
+    }
+}
+
+fn write(value: &WolframValue, indent: usize, column: usize, width: usize, out: &mut String) {
+    let line = flat(value);
+    if column + line.len() <= width {
+        return out.push_str(&line);
+    }
+    if let Some((k, op, v)) = rule(value) {
+        let key = format!("{}{}", flat(k), op);
+        out.push_str(&key);
+        return write(v, indent, column + key.len(), width, out);
+    }
+    let (open, entries, close) = match parts(value) {
+        Some(parts) if !parts.1.is_empty() => parts,
+        _ => return out.push_str(&line),
+    };
+    out.push_str(&open);
+    for (i, entry) in entries.iter().enumerate() {
+        out.push('\n');
+        out.push_str(&" ".repeat(indent + 2));
+        let mut column = indent + 2;
+        if let Some((k, op)) = entry.key {
+            let key = format!("{}{}", flat(k), op);
+            out.push_str(&key);
+            column += key.len();
+        }
+        write(entry.value, indent + 2, column, width, out);
+        if i + 1 < entries.len() {
+            out.push(',')
+        }
+    }
+    out.push('\n');
+    out.push_str(&" ".repeat(indent));
+    out.push_str(close);
+}
+
+/// `InputForm[value]`, parts that do not fit in `width` columns are broken over indented lines
+pub fn input_form(value: &WolframValue, width: usize) -> String {
+    let mut out = String::new();
+    write(value, 0, 0, width, &mut out);
+    out
+}
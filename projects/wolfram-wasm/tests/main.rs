@@ -0,0 +1,2 @@
+mod node;
+mod pretty;
@@ -0,0 +1,84 @@
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Array, BigInt, BigUint64Array, Float32Array, Float64Array, Int16Array, Map, Object, Reflect, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+use wolfram_wasm::{decode, encode, input_form_of};
+use wolfram_wxf::{wl, ToWolfram, WolframValue};
+
+fn js(source: &str) -> JsValue {
+    js_sys::eval(&format!("({})", source)).unwrap()
+}
+
+fn round_trip(value: &JsValue) -> JsValue {
+    decode(&encode(value, None).unwrap()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_encode() {
+    let value = js(r#"{ name: "x", sizes: [1, 2.5, null], flag: true }"#);
+    let expected = wl!(<|"flag" -> True, "name" -> "x", "sizes" -> {#(1i64), 2.5, None}|>);
+    assert_eq!(WolframValue::from_bytes(&encode(&value, None).unwrap()).unwrap(), expected);
+    let value = js(r#"{ $head: { $symbol: "f" }, $args: [{ $symbol: "x" }, 12345678901234567890n] }"#);
+    let big: num::BigInt = "12345678901234567890".parse().unwrap();
+    let expected = WolframValue::Function(Box::new(WolframValue::symbol("f")), vec![WolframValue::symbol("x"), big.to_wolfram()]);
+    assert_eq!(WolframValue::from_bytes(&encode(&value, Some(true)).unwrap()).unwrap(), expected);
+}
+
+#[wasm_bindgen_test]
+fn test_round_trip() {
+    let out = round_trip(&js(r#"{ a: [1, "two", { $symbol: "Pi" }], b: { c: 9007199254740993n } }"#));
+    let a: Array = Reflect::get(&out, &"a".into()).unwrap().unchecked_into();
+    assert_eq!(a.get(0).as_f64(), Some(1.0));
+    assert_eq!(a.get(1).as_string().as_deref(), Some("two"));
+    assert_eq!(Reflect::get(&a.get(2), &"$symbol".into()).unwrap().as_string().as_deref(), Some("Pi"));
+    let c = Reflect::get(&Reflect::get(&out, &"b".into()).unwrap(), &"c".into()).unwrap();
+    assert!(c.is_bigint());
+    assert_eq!(c.unchecked_into::<BigInt>().to_string(10).unwrap(), "9007199254740993");
+}
+
+#[wasm_bindgen_test]
+fn test_arrays() {
+    let value = Float64Array::from(&[0.5, 1.5][..]);
+    assert_eq!(WolframValue::from_bytes(&encode(&value, None).unwrap()).unwrap(), wl!(NumericArray[{0.5, 1.5}, "Real64"]));
+    // one byte per element, as `NumericArray[{200}, "UnsignedInteger8"]`
+    assert_eq!(encode(&Uint8Array::from(&[200][..]), None).unwrap(), [56, 58, 194, 16, 1, 1, 200]);
+    assert_eq!(encode(&Float32Array::from(&[0.5][..]), None).unwrap()[3], 0x22);
+    let out = round_trip(&BigUint64Array::from(&[u64::MAX][..]).into());
+    assert_eq!(out.dyn_into::<BigUint64Array>().unwrap().to_vec(), vec![u64::MAX]);
+    let out = round_trip(&Int16Array::from(&[1, -300][..]).into());
+    assert_eq!(out.dyn_into::<Int16Array>().unwrap().to_vec(), vec![1, -300]);
+    // `8:` and a 2x2 UnsignedInteger8 numeric array
+    let bytes = [56, 58, 194, 16, 2, 2, 2, 1, 2, 3, 4];
+    let rows: Array = decode(&bytes).unwrap().unchecked_into();
+    assert_eq!(rows.get(1).dyn_into::<Uint8Array>().unwrap().to_vec(), vec![3, 4]);
+    let buffer = round_trip(&Uint8Array::from(&[7, 8][..]).buffer().into());
+    assert_eq!(Uint8Array::new(&buffer).to_vec(), vec![7, 8]);
+}
+
+#[wasm_bindgen_test]
+fn test_map_keys() {
+    let map = Map::new();
+    map.set(&JsValue::from(1), &"one".into());
+    let out = round_trip(&map.into());
+    let map: Map = out.dyn_into().unwrap();
+    assert_eq!(map.get(&JsValue::from(1)).as_string().as_deref(), Some("one"));
+    assert!(round_trip(&Object::new().into()).is_object());
+    let out = round_trip(&js(r#"JSON.parse('{"__proto__": 1}')"#));
+    assert_eq!(Object::keys(out.unchecked_ref()).length(), 1);
+    assert_eq!(JsValue::from(Object::get_prototype_of(&out)), JsValue::from(Object::get_prototype_of(&Object::new().into())));
+}
+
+#[wasm_bindgen_test]
+fn test_errors() {
+    assert!(encode(&js("() => 1"), None).is_err());
+    assert!(encode(&js(r#"{ $symbol: 1 }"#), None).is_err());
+    assert!(decode(b"8:\x99").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_input_form() {
+    let bytes = encode(&js(r#"{ $head: { $symbol: "f" }, $args: [[1, 2], "s"] }"#), None).unwrap();
+    assert_eq!(input_form_of(&bytes, None).unwrap(), r#"f[{1, 2}, "s"]"#);
+    assert_eq!(input_form_of(&bytes, Some(8)).unwrap(), "f[\n  {1, 2},\n  \"s\"\n]");
+}
@@ -0,0 +1,24 @@
+use wolfram_wasm::input_form;
+use wolfram_wxf::{wl, WolframValue};
+
+#[test]
+fn test_flat() {
+    let value = wl!(f[{1, 2}, <|"a" -> x, "b" :> y|>, Rule[p, q]]);
+    assert_eq!(input_form(&value, 80), r#"f[{1, 2}, <|"a" -> x, "b" :> y|>, p -> q]"#);
+    assert_eq!(input_form(&WolframValue::list(vec![]), 0), "{}");
+}
+
+#[test]
+fn test_break() {
+    let value = wl!(<|"points" -> {{1, 2}, {3, 4}}, "label" -> "triangle"|>);
+    let expected = r#"<|
+  "label" -> "triangle",
+  "points" -> {
+    {1, 2},
+    {3, 4}
+  }
+|>"#;
+    assert_eq!(input_form(&value, 20), expected);
+    let rule = wl!(Rule[key, {alpha, beta}]);
+    assert_eq!(input_form(&rule, 12), "key -> {\n  alpha,\n  beta\n}");
+}